@@ -1,4 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use locust::core::spatial::{Direction, SpatialConfig};
 use locust::core::targets::{NavTarget, TargetPriority, TargetRegistry};
 use ratatui::layout::Rect;
 
@@ -25,7 +26,7 @@ fn create_registry_with_targets(count: usize) -> TargetRegistry {
 fn bench_target_registration(c: &mut Criterion) {
     let mut group = c.benchmark_group("target_registration");
 
-    for size in [10, 50, 100, 500, 1000].iter() {
+    for size in [10, 50, 100, 500, 1000, 10_000].iter() {
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
             b.iter(|| {
                 let mut registry = TargetRegistry::new();
@@ -49,7 +50,7 @@ fn bench_target_registration(c: &mut Criterion) {
 fn bench_spatial_at_point(c: &mut Criterion) {
     let mut group = c.benchmark_group("spatial_at_point");
 
-    for size in [100, 500, 1000].iter() {
+    for size in [100, 500, 1000, 10_000].iter() {
         let registry = create_registry_with_targets(*size);
 
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
@@ -66,7 +67,7 @@ fn bench_spatial_at_point(c: &mut Criterion) {
 fn bench_spatial_in_area(c: &mut Criterion) {
     let mut group = c.benchmark_group("spatial_in_area");
 
-    for size in [100, 500, 1000].iter() {
+    for size in [100, 500, 1000, 10_000].iter() {
         let registry = create_registry_with_targets(*size);
 
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
@@ -101,7 +102,7 @@ fn bench_by_id_lookup(c: &mut Criterion) {
 fn bench_closest_to(c: &mut Criterion) {
     let mut group = c.benchmark_group("closest_to");
 
-    for size in [100, 500, 1000].iter() {
+    for size in [100, 500, 1000, 10_000].iter() {
         let registry = create_registry_with_targets(*size);
 
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
@@ -115,6 +116,49 @@ fn bench_closest_to(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_k_nearest(c: &mut Criterion) {
+    let mut group = c.benchmark_group("k_nearest");
+
+    for size in [100, 1000, 10_000].iter() {
+        let registry = create_registry_with_targets(*size);
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
+            b.iter(|| {
+                let nearest = registry.k_nearest(black_box(250), black_box(50), black_box(8));
+                black_box(nearest);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_nearest_in_direction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nearest_in_direction");
+    let config = SpatialConfig::default();
+
+    for size in [100, 1000, 10_000].iter() {
+        let registry = create_registry_with_targets(*size);
+        let from = (*size / 2) as u64;
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
+            b.iter(|| {
+                for direction in [
+                    Direction::Up,
+                    Direction::Down,
+                    Direction::Left,
+                    Direction::Right,
+                ] {
+                    let next = registry.nearest_in_direction(black_box(from), direction, &config);
+                    black_box(next);
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_filter_by_priority(c: &mut Criterion) {
     let mut group = c.benchmark_group("filter_by_priority");
 
@@ -185,6 +229,8 @@ criterion_group!(
     bench_spatial_in_area,
     bench_by_id_lookup,
     bench_closest_to,
+    bench_k_nearest,
+    bench_nearest_in_direction,
     bench_filter_by_priority,
     bench_sorted_by_priority,
     bench_sorted_by_area,
@@ -6,11 +6,14 @@ use ratatui::widgets::{Block, Borders, Paragraph};
 use std::io::{self, Stdout};
 use std::fs::File;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use log::{debug, info, LevelFilter};
 use simplelog::{CombinedLogger, Config, WriteLogger};
 
 use locust::prelude::*;
+use locust::core::focus::FocusEvent;
+use locust::core::keybindings::{KeyBinding, KeyCodeDef};
 use locust::core::targets::{TargetBuilder, TargetAction, TargetPriority};
 use locust::ratatui_ext::LogTailer;
 
@@ -47,6 +50,14 @@ fn main() -> io::Result<()> {
     let mut locust = Locust::<CrosstermBackend<Stdout>>::new(LocustConfig::default());
     locust.register_plugin(NavPlugin::new());
 
+    // Keyboard focus is opt-in; let Tab cycle between the panes
+    for (action, key) in [("focus_next", "tab"), ("focus_prev", "backtab")] {
+        locust
+            .ctx
+            .bind_key(action, KeyBinding::new(KeyCodeDef::Named(key.into())))
+            .expect("focus bindings are valid");
+    }
+
     let mut left_text = String::from("Hello from left pane!");
     let mut left_cursor: usize = left_text.len();
     let mut right_text = String::from("Hello from right pane!");
@@ -139,20 +150,29 @@ fn main() -> io::Result<()> {
             locust.render_overlay(f);
         })?;
 
-        if event::poll(std::time::Duration::from_millis(250))? {
+        let timeout = locust
+            .poll_timeout(Instant::now())
+            .unwrap_or(Duration::from_millis(250));
+        if event::poll(timeout)? {
             let ev = event::read()?;
             let outcome = locust.on_event(&ev);
+
+            // Tab moves Locust's focus between the panes' targets
+            for focus_event in locust.ctx.focus.drain_events() {
+                if let FocusEvent::Focused(_) = focus_event {
+                    let label = locust.ctx.focused_target().and_then(|t| t.label.clone());
+                    focused_pane = match label.as_deref() {
+                        Some("Right Text Input") => FocusedPane::Right,
+                        _ => FocusedPane::Left,
+                    };
+                    info!("Focused: {:?}", focused_pane);
+                }
+            }
+
             if !outcome.consumed {
                 if let Event::Key(key) = ev {
                     match key.code {
                         KeyCode::Char('q') => break 'outer,
-                        KeyCode::Tab => {
-                            focused_pane = match focused_pane {
-                                FocusedPane::Left => FocusedPane::Right,
-                                FocusedPane::Right => FocusedPane::Left,
-                            };
-                            info!("Focused: {:?}", focused_pane);
-                        }
                         KeyCode::Backspace => {
                             match focused_pane {
                                 FocusedPane::Left => {
@@ -220,6 +240,9 @@ fn main() -> io::Result<()> {
                 }
             }
         }
+
+        // The loop redraws every iteration, so the redraw hint is not needed.
+        locust.tick(Instant::now());
     }
 
     disable_raw_mode()?;
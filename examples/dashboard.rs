@@ -16,6 +16,7 @@
 /// - `f` - Enter hint mode to navigate within the active pane
 /// - `/` - Open omnibar for pane switching or command execution
 /// - `Tab` / `Shift+Tab` - Cycle between panes
+/// - `Shift+Arrows` - Move focus to the nearest control, `Enter` to activate it
 /// - `1-4` - Directly select pane (1=Metrics, 2=Logs, 3=Status, 4=Controls)
 /// - `q` - Quit the application
 /// - `r` - Refresh data
@@ -31,6 +32,8 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use locust::core::focus::FocusTracking;
+use locust::core::keybindings::{KeyBinding, KeyCodeDef};
 use locust::prelude::*;
 use ratatui::{
     backend::CrosstermBackend,
@@ -879,6 +882,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         OmnibarConfig::new().with_activation_key('O'),
     ));
 
+    // Target IDs change every frame, so follow focus by label. Tab keeps
+    // cycling panes; Shift+Arrows move between controls spatially.
+    locust.ctx.focus.tracking = FocusTracking::Label;
+    for (action, key) in [
+        ("focus_up", "up"),
+        ("focus_down", "down"),
+        ("focus_left", "left"),
+        ("focus_right", "right"),
+    ] {
+        let binding = KeyBinding::with_modifiers(KeyCodeDef::Named(key.into()), KeyModifiers::SHIFT);
+        locust
+            .ctx
+            .bind_key(action, binding)
+            .expect("focus bindings are valid");
+    }
+
     // Create dashboard
     let mut dashboard = Dashboard::new();
     let mut log_tailer = LogTailer::new(log_file_path, 10); // Display last 10 log lines
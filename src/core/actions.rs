@@ -0,0 +1,159 @@
+//! Target activation dispatch.
+//!
+//! When a plugin activates a navigation target (for example, after the user
+//! types a complete hint), the target's [`TargetAction`] is emitted into an
+//! [`ActionQueue`] on the `LocustContext`. The host application drains this
+//! queue after calling `Locust::on_event` and performs the actual work.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::core::context::LocustContext;
+//! use locust::core::targets::{NavTarget, TargetAction};
+//! use ratatui::layout::Rect;
+//!
+//! let mut ctx = LocustContext::default();
+//! ctx.targets.register(
+//!     NavTarget::new(1, Rect::new(0, 0, 10, 1))
+//!         .with_action(TargetAction::Navigate("/settings".into())),
+//! );
+//!
+//! assert!(ctx.activate_target(1));
+//!
+//! for activation in ctx.actions.drain() {
+//!     assert_eq!(activation.target_id, 1);
+//!     assert_eq!(activation.action, TargetAction::Navigate("/settings".into()));
+//! }
+//! ```
+
+use crate::core::targets::TargetAction;
+use std::collections::VecDeque;
+
+/// Direction of a scroll request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A target activation waiting to be handled by the host application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetActivation {
+    /// ID of the activated target.
+    pub target_id: u64,
+
+    /// Action attached to the target at the time of activation.
+    pub action: TargetAction,
+
+    /// Label of the target, if it had one.
+    pub label: Option<String>,
+
+    /// Scroll direction, set for `TargetAction::Scroll` requests
+    /// produced by the scroll wheel.
+    pub scroll: Option<ScrollDirection>,
+}
+
+impl TargetActivation {
+    /// Creates a new activation for the given target and action.
+    pub fn new(target_id: u64, action: TargetAction) -> Self {
+        Self {
+            target_id,
+            action,
+            label: None,
+            scroll: None,
+        }
+    }
+
+    /// Sets the label of the activated target.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the scroll direction of this activation.
+    pub fn with_scroll(mut self, direction: ScrollDirection) -> Self {
+        self.scroll = Some(direction);
+        self
+    }
+}
+
+/// FIFO queue of target activations emitted by plugins.
+///
+/// Activations accumulate until the host application drains them, so
+/// nothing is lost if several events are processed between drains.
+#[derive(Debug, Default)]
+pub struct ActionQueue {
+    pending: VecDeque<TargetActivation>,
+}
+
+impl ActionQueue {
+    /// Creates a new empty action queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an activation to the end of the queue.
+    pub fn push(&mut self, activation: TargetActivation) {
+        self.pending.push_back(activation);
+    }
+
+    /// Removes and returns the oldest pending activation.
+    pub fn pop(&mut self) -> Option<TargetActivation> {
+        self.pending.pop_front()
+    }
+
+    /// Removes and returns all pending activations in emission order.
+    pub fn drain(&mut self) -> Vec<TargetActivation> {
+        self.pending.drain(..).collect()
+    }
+
+    /// Returns an iterator over pending activations without removing them.
+    pub fn iter(&self) -> impl Iterator<Item = &TargetActivation> {
+        self.pending.iter()
+    }
+
+    /// Returns the number of pending activations.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns true if no activations are pending.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Discards all pending activations.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_is_fifo() {
+        let mut queue = ActionQueue::new();
+        queue.push(TargetActivation::new(1, TargetAction::Activate));
+        queue.push(TargetActivation::new(2, TargetAction::Select));
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop().unwrap().target_id, 1);
+        assert_eq!(queue.pop().unwrap().target_id, 2);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn test_drain_empties_queue() {
+        let mut queue = ActionQueue::new();
+        queue.push(TargetActivation::new(1, TargetAction::Scroll).with_label("Logs"));
+
+        let drained = queue.drain();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].label.as_deref(), Some("Logs"));
+        assert!(queue.is_empty());
+    }
+}
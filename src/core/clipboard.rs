@@ -0,0 +1,174 @@
+//! System clipboard access for plugins that copy text.
+//!
+//! Terminal apps usually run where no clipboard daemon is reachable (over
+//! SSH, inside tmux), so [`Osc52Clipboard`] asks the terminal itself to set
+//! the clipboard with an OSC 52 escape sequence. Write it to the same stream
+//! as the ratatui backend, normally stdout. [`MemoryClipboard`] keeps the
+//! text in memory instead, for tests and terminals without OSC 52 support.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::core::clipboard::{Clipboard, MemoryClipboard, Osc52Clipboard};
+//!
+//! let mut osc52 = Osc52Clipboard::new(Vec::new());
+//! osc52.set_text("hi").unwrap();
+//! assert_eq!(osc52.writer(), b"\x1b]52;c;aGk=\x07");
+//!
+//! let memory = MemoryClipboard::new();
+//! memory.clone().set_text("hi").unwrap();
+//! assert_eq!(memory.contents().as_deref(), Some("hi"));
+//! ```
+
+use std::io::{self, Stdout, Write};
+use std::sync::{Arc, Mutex};
+
+/// Longest base64 payload written in one OSC 52 sequence by default.
+///
+/// Terminals drop longer sequences; xterm's limit is close to 100 KB.
+pub const DEFAULT_OSC52_MAX_LEN: usize = 100_000;
+
+/// Errors from setting the clipboard.
+#[derive(Debug, thiserror::Error)]
+pub enum ClipboardError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Text too long for the clipboard: {len} bytes encoded, at most {max}")]
+    TooLong { len: usize, max: usize },
+}
+
+/// A clipboard text can be copied to.
+pub trait Clipboard: Send {
+    /// Replaces the clipboard contents with `text`.
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError>;
+}
+
+/// Sets the terminal's clipboard with OSC 52 escape sequences.
+#[derive(Debug)]
+pub struct Osc52Clipboard<W: Write + Send> {
+    writer: W,
+    max_len: usize,
+}
+
+impl Osc52Clipboard<Stdout> {
+    /// Writes to stdout, where `CrosstermBackend::new(io::stdout())` draws.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write + Send> Osc52Clipboard<W> {
+    /// Writes the escape sequences to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            max_len: DEFAULT_OSC52_MAX_LEN,
+        }
+    }
+
+    /// Sets the longest base64 payload to write; longer text is refused.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Returns the underlying writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+}
+
+impl<W: Write + Send> Clipboard for Osc52Clipboard<W> {
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        let payload = base64(text.as_bytes());
+        if payload.len() > self.max_len {
+            return Err(ClipboardError::TooLong {
+                len: payload.len(),
+                max: self.max_len,
+            });
+        }
+        write!(self.writer, "\x1b]52;c;{}\x07", payload)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Keeps copied text in memory.
+///
+/// Clones share their contents, so a test can keep one and hand another
+/// to a plugin.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryClipboard {
+    contents: Arc<Mutex<Option<String>>>,
+}
+
+impl MemoryClipboard {
+    /// Creates an empty clipboard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The last text copied, if any.
+    pub fn contents(&self) -> Option<String> {
+        self.contents.lock().unwrap().clone()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        *self.contents.lock().unwrap() = Some(text.to_string());
+        Ok(())
+    }
+}
+
+/// Standard base64 with padding, as OSC 52 expects.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        let cases = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foobar", "Zm9vYmFy"),
+            ("héllo", "aMOpbGxv"),
+        ];
+        for (text, encoded) in cases {
+            assert_eq!(base64(text.as_bytes()), encoded, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_osc52_refuses_long_text() {
+        let mut clipboard = Osc52Clipboard::new(Vec::new()).with_max_len(8);
+        clipboard.set_text("foobar").unwrap();
+        assert!(matches!(
+            clipboard.set_text("foobarbaz"),
+            Err(ClipboardError::TooLong { len: 12, max: 8 })
+        ));
+        assert_eq!(clipboard.writer(), b"\x1b]52;c;Zm9vYmFy\x07");
+    }
+}
@@ -1,18 +1,31 @@
+use crate::core::actions::{ActionQueue, ScrollDirection, TargetActivation};
 use crate::core::config::{ConfigError, LocustConfig as Config};
-use crate::core::input::LocustEventOutcome;
-use crate::core::keybindings::{KeyBinding, KeyMap, KeyMapError};
+use crate::core::focus::{FocusManager, FocusTracking};
+use crate::core::input::{LocustEventOutcome, MouseState};
+use crate::core::keybindings::{
+    KeyBinding, KeyCodeDef, KeyMap, KeyMapChanges, KeyMapError, KeySequenceState, SequenceOutcome,
+};
+use crate::core::metrics::{timed, MetricsState, PluginPhase};
+use crate::core::modes::{Mode, ModeStack};
 use crate::core::overlay::OverlayState;
-use crate::core::plugin::LocustPlugin;
-use crate::core::targets::TargetRegistry;
+use crate::core::plugin::{resolve_order, LocustPlugin, OrderNode, PluginOrderError};
+use crate::core::recording::{EventRecorder, RecordedEvent};
+use crate::core::spatial::Direction;
+use crate::core::targets::{NavTarget, TargetAction, TargetKey, TargetRegistry, TargetState};
 use crate::core::theme::{Theme, ThemeError};
 use crate::core::theme_manager::ThemeManager;
+use crate::plugins::tooltip::registry::TooltipChanges;
 use crate::plugins::tooltip::TooltipRegistry;
-use crossterm::event::Event;
+use crate::ratatui_ext::discovery::{self, DiscoveryConfig};
+use crossterm::event::{Event, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
 use std::any::Any;
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use ratatui::backend::Backend;
+use ratatui::buffer::Buffer;
 use ratatui::Frame;
 
 /// Legacy global configuration for Locust.
@@ -37,11 +50,14 @@ impl Default for LocustConfig {
 ///
 /// This context is shared between all plugins and provides:
 /// - Target registry for navigation
+/// - Queue of target activations for the host application
 /// - Tooltip registry for contextual help
 /// - Overlay state management
 /// - Frame lifecycle tracking
 /// - Configuration management
 /// - Theme and keybinding management
+/// - The stack of active input modes
+/// - Keyboard focus over targets
 /// - Plugin communication channels (future)
 ///
 /// # Thread Safety
@@ -53,6 +69,12 @@ pub struct LocustContext {
     /// Registry of all navigation targets discovered in the current frame.
     pub targets: TargetRegistry,
 
+    /// Target activations emitted by plugins, drained by the host application.
+    pub actions: ActionQueue,
+
+    /// Pointer position and hovered target.
+    pub mouse: MouseState,
+
     /// Registry of tooltips mapped to target IDs.
     pub tooltips: TooltipRegistry,
 
@@ -71,9 +93,40 @@ pub struct LocustContext {
     /// Keybinding configuration
     #[allow(clippy::derivable_impls)]
     pub keymap: KeyMap,
+
+    /// Progress through multi-key sequences from the keymap.
+    pub key_sequences: KeySequenceState,
+
+    /// Input modes pushed by plugins; the top one owns modal keys.
+    pub modes: ModeStack,
+
+    /// Target with keyboard focus and pending focus/blur events.
+    pub focus: FocusManager,
+
+    /// Rolling timings of plugin hooks; off unless `metrics.enabled` is set.
+    pub metrics: MetricsState,
+
+    /// Heuristics finding targets in the rendered frame; off unless
+    /// `discovery.enabled` is set.
+    pub discovery: DiscoveryConfig,
+
+    /// Instant of the event or tick `Locust` is handling, see [`now`](Self::now).
+    clock: Option<Instant>,
+
+    /// States shown for the selected, focused and hovered targets, by key.
+    derived_states: HashMap<TargetKey, TargetState>,
 }
 
 impl LocustContext {
+    /// Current time for plugin timers.
+    ///
+    /// While `Locust` handles an event or a tick this is the instant it was
+    /// given, so timers started on input run on the same clock as
+    /// `on_tick`. Otherwise it is the wall clock.
+    pub fn now(&self) -> Instant {
+        self.clock.unwrap_or_else(Instant::now)
+    }
+
     /// Updates the configuration and returns the old one if it existed.
     pub fn update_config(&mut self, config: Config) -> Option<Config> {
         self.config.replace(config)
@@ -131,6 +184,328 @@ impl LocustContext {
     pub fn unbind_key(&mut self, action: &str) {
         self.keymap.unbind(action)
     }
+
+    /// Binds an action to the key a plugin's configuration sets, if any.
+    ///
+    /// Built-in plugins call this from `init`, so a key set in code replaces
+    /// the keymap binding while `None` leaves the keymap (and any user remap
+    /// loaded into it) alone.
+    pub fn bind_configured_key(&mut self, action: &str, key: Option<char>) {
+        let Some(key) = key else {
+            return;
+        };
+        if let Err(err) = self.bind_key(action, KeyBinding::new(KeyCodeDef::Char(key))) {
+            log::warn!("Failed to bind {}: {}", action, err);
+        }
+    }
+
+    /// Resolves the keymap action a key event triggers for a plugin.
+    ///
+    /// `plugin` is the keymap section name (e.g. `"nav"`); the returned
+    /// action is qualified, e.g. `"nav.activate"`. Bindings scoped to the
+    /// current mode win over the plugin section. While a completed key
+    /// sequence is being dispatched, only the sequence's action resolves.
+    pub fn plugin_action(&self, plugin: &str, key: &KeyEvent) -> Option<String> {
+        if let Some(action) = self.key_sequences.matched_action() {
+            let owned = action
+                .strip_prefix(plugin)
+                .is_some_and(|rest| rest.starts_with('.'));
+            return owned.then(|| action.to_string());
+        }
+
+        let binding = KeyBinding::from(key);
+        self.keymap
+            .get_mode_action(self.modes.current().name(), plugin, &binding)
+            .or_else(|| self.keymap.get_plugin_action(plugin, &binding))
+    }
+
+    /// Returns whether Locust is treating keys as text input.
+    ///
+    /// While this is `true` (hint mode, the command palette), the host
+    /// application should not interpret keys as its own shortcuts.
+    pub fn is_capturing_input(&self) -> bool {
+        self.modes.is_capturing_input()
+    }
+
+    /// Activates a registered target.
+    ///
+    /// The target's activation callback (or its group's) runs first. If no
+    /// callback handles it, the target's action is emitted into the queue.
+    ///
+    /// Returns `false` if no target with this ID is registered or the
+    /// target is disabled.
+    pub fn activate_target(&mut self, target_id: u64) -> bool {
+        let Some(target) = self.targets.by_id(target_id) else {
+            return false;
+        };
+
+        if target.state == TargetState::Disabled {
+            return false;
+        }
+
+        if self.targets.invoke_callback(target_id) == Some(true) {
+            return true;
+        }
+
+        let mut activation = TargetActivation::new(target.id, target.action.clone());
+        activation.label = target.label.clone();
+        self.actions.push(activation);
+        true
+    }
+
+    /// Emits a `TargetAction::Scroll` request for a registered target.
+    ///
+    /// Returns `false` if no target with this ID is registered or the
+    /// target is disabled.
+    pub fn scroll_target(&mut self, target_id: u64, direction: ScrollDirection) -> bool {
+        let Some(target) = self.targets.by_id(target_id) else {
+            return false;
+        };
+
+        if target.state == TargetState::Disabled {
+            return false;
+        }
+
+        let mut activation =
+            TargetActivation::new(target.id, TargetAction::Scroll).with_scroll(direction);
+        activation.label = target.label.clone();
+        self.actions.push(activation);
+        true
+    }
+
+    /// Moves keyboard focus to a registered target.
+    ///
+    /// The target is shown as `TargetState::Highlighted` and the previously
+    /// focused one is blurred. Returns `false` if no target with this ID is
+    /// registered or the target is disabled.
+    pub fn focus_target(&mut self, target_id: u64) -> bool {
+        let Some(target) = self
+            .targets
+            .by_id(target_id)
+            .filter(|t| t.state != TargetState::Disabled)
+        else {
+            return false;
+        };
+
+        self.focus.set(Some(target));
+        self.refresh_target_states();
+        true
+    }
+
+    /// Clears keyboard focus.
+    pub fn blur(&mut self) {
+        self.focus.set(None);
+        self.refresh_target_states();
+    }
+
+    /// Focuses the next target in the focus order, wrapping around.
+    ///
+    /// Returns `false` if there is no target to focus.
+    pub fn focus_next(&mut self) -> bool {
+        self.focus
+            .neighbor(&self.targets, true)
+            .is_some_and(|id| self.focus_target(id))
+    }
+
+    /// Focuses the previous target in the focus order, wrapping around.
+    ///
+    /// Returns `false` if there is no target to focus.
+    pub fn focus_prev(&mut self) -> bool {
+        self.focus
+            .neighbor(&self.targets, false)
+            .is_some_and(|id| self.focus_target(id))
+    }
+
+    /// Moves focus to the nearest target in `direction`.
+    ///
+    /// Follows `focus.spatial` for group restriction and wrapping. With
+    /// nothing focused, the first target in the focus order is focused.
+    /// Returns `false` if focus did not move.
+    pub fn focus_direction(&mut self, direction: Direction) -> bool {
+        let Some(from) = self.focus.focused() else {
+            return self.focus_next();
+        };
+
+        self.targets
+            .nearest_in_direction(from, direction, &self.focus.spatial)
+            .map(|t| t.id)
+            .is_some_and(|id| self.focus_target(id))
+    }
+
+    /// Returns the focused target, if it is registered this frame.
+    pub fn focused_target(&self) -> Option<&NavTarget> {
+        self.focus.focused().and_then(|id| self.targets.by_id(id))
+    }
+
+    /// Activates the focused target and marks it `TargetState::Selected`.
+    ///
+    /// Returns `false` if nothing is focused or no activation was emitted:
+    /// the target is not registered this frame or is disabled. Enter then
+    /// reaches the application.
+    pub fn activate_focused(&mut self) -> bool {
+        let Some(target_id) = self.focused_target().map(|t| t.id) else {
+            return false;
+        };
+        if !self.activate_target(target_id) {
+            return false;
+        }
+
+        if let Some(key) = self.targets.key_of(target_id).cloned() {
+            self.focus.select(target_id, key);
+        }
+        self.refresh_target_states();
+        true
+    }
+
+    /// Finds the focused, selected and hovered targets and the targets of
+    /// tooltips again after targets were re-registered.
+    ///
+    /// Focus follows `focus.tracking`, so it can survive IDs that change
+    /// every frame. If the target is gone, focus is cleared and a blur is
+    /// queued; if group tracking settles on another target of the group,
+    /// focus moves there like any other focus change. The selection follows
+    /// its target's key and the hovered target is looked up again under the
+    /// pointer. Tooltips follow their targets, see
+    /// [`TooltipRegistry::follow_targets`].
+    pub fn sync_targets(&mut self) {
+        // Targets are not registered yet (e.g. between begin_frame and draw)
+        if self.targets.is_empty() {
+            return;
+        }
+
+        if let Some(focused) = self.focus.focused() {
+            match self.focus.resolve(&self.targets).cloned() {
+                // Another target of the group stands in for the lost one
+                Some(target)
+                    if target.id != focused && self.focus.tracking == FocusTracking::Group =>
+                {
+                    self.focus_target(target.id);
+                }
+                Some(target) => self.focus.retarget(&target),
+                None => {
+                    self.focus.set(None);
+                }
+            }
+        }
+        self.focus.sync_selected(&self.targets);
+        if let Some((x, y)) = self.mouse.position {
+            self.mouse.hovered_target = self.targets.topmost_at(x, y).map(|t| t.id);
+        }
+        self.refresh_target_states();
+        self.tooltips.follow_targets(&self.targets);
+    }
+
+    /// Runs a focus keymap action (`focus_next`, `focus_prev`,
+    /// `focus_left` and the other directions, `activate_focus`). Returns
+    /// `true` if the action was handled.
+    pub fn handle_focus_action(&mut self, action: &str) -> bool {
+        match action {
+            "focus_next" => self.focus_next(),
+            "focus_prev" => self.focus_prev(),
+            "activate_focus" => self.activate_focused(),
+            _ => Direction::from_focus_action(action)
+                .is_some_and(|direction| self.focus_direction(direction)),
+        }
+    }
+
+    /// Runs the focus action bound to a key, if any.
+    fn handle_focus_key(&mut self, key: &KeyEvent) -> bool {
+        let binding = KeyBinding::from(key);
+        [
+            "focus_next",
+            "focus_prev",
+            "focus_up",
+            "focus_down",
+            "focus_left",
+            "focus_right",
+            "activate_focus",
+        ]
+        .into_iter()
+        .find(|action| self.keymap.is_bound(action, &binding))
+        .is_some_and(|action| self.handle_focus_action(action))
+    }
+
+    /// Re-derives the state overrides targets get from selection, focus
+    /// and hover, clearing the ones that no longer apply.
+    ///
+    /// Selection wins over focus and hover, which show as highlighted. The
+    /// overrides are stored by key, so a frame that regenerates IDs shows
+    /// them on the same targets even before the next `sync_targets`.
+    fn refresh_target_states(&mut self) {
+        let sources = [
+            (self.mouse.hovered_target, TargetState::Highlighted),
+            (self.focus.focused(), TargetState::Highlighted),
+            (self.focus.selected(), TargetState::Selected),
+        ];
+        // Later sources win, so a selected target shows as selected
+        let mut states = HashMap::new();
+        for (id, state) in sources {
+            if let Some(key) = id.and_then(|id| self.targets.key_of(id)) {
+                states.insert(key.clone(), state);
+            }
+        }
+
+        for key in self.derived_states.keys() {
+            if !states.contains_key(key) {
+                self.targets.clear_key_state_override(key);
+            }
+        }
+        for (key, &state) in &states {
+            if self.derived_states.get(key) != Some(&state) {
+                self.targets.set_key_state_override(key.clone(), state);
+            }
+        }
+        self.derived_states = states;
+    }
+
+    /// Returns whether mouse events should be routed to targets.
+    ///
+    /// Follows `GlobalConfig::mouse_support`, enabled when no configuration
+    /// is loaded.
+    pub fn mouse_enabled(&self) -> bool {
+        self.get_global_config()
+            .is_none_or(|global| global.mouse_support)
+    }
+
+    /// Moves the pointer and updates the hovered target.
+    ///
+    /// The hovered target is shown as `TargetState::Highlighted`. Returns
+    /// `true` if the hovered target changed.
+    pub fn update_hover(&mut self, x: u16, y: u16) -> bool {
+        self.mouse.position = Some((x, y));
+        let hovered = self.targets.topmost_at(x, y).map(|t| t.id);
+        if hovered == self.mouse.hovered_target {
+            return false;
+        }
+
+        self.mouse.hovered_target = hovered;
+        self.refresh_target_states();
+        true
+    }
+
+    /// Resolves clicks and scroll-wheel events against the target registry.
+    ///
+    /// A left click activates the topmost target under the pointer and the
+    /// scroll wheel emits a scroll request for it. Returns `true` if the
+    /// event was handled.
+    fn handle_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        let Some(target_id) = self
+            .targets
+            .topmost_at(mouse.column, mouse.row)
+            .map(|t| t.id)
+        else {
+            return false;
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.activate_target(target_id),
+            MouseEventKind::ScrollUp => self.scroll_target(target_id, ScrollDirection::Up),
+            MouseEventKind::ScrollDown => self.scroll_target(target_id, ScrollDirection::Down),
+            MouseEventKind::ScrollLeft => self.scroll_target(target_id, ScrollDirection::Left),
+            MouseEventKind::ScrollRight => self.scroll_target(target_id, ScrollDirection::Right),
+            _ => false,
+        }
+    }
 }
 
 /// Central entry point for embedding Locust into a ratatui app.
@@ -141,6 +516,17 @@ where
     pub config: LocustConfig,
     pub ctx: LocustContext,
     plugins: Vec<Box<dyn LocustPlugin<B>>>,
+    disabled: HashSet<&'static str>,
+    /// Keymap bindings each plugin's `init` changed, reverted when the
+    /// plugin is removed.
+    keymap_changes: HashMap<&'static str, KeyMapChanges>,
+    /// Tooltips each plugin's `init` registered, removed with the plugin.
+    tooltip_changes: HashMap<&'static str, TooltipChanges>,
+    last_consumer: Option<&'static str>,
+    recorder: Option<EventRecorder>,
+    /// Render timings of the current frame, moved to `ctx.metrics` when the
+    /// next frame begins.
+    render_timings: RefCell<Vec<(&'static str, Duration)>>,
 }
 
 impl<B> Locust<B>
@@ -157,69 +543,581 @@ where
             config,
             ctx: LocustContext::default(),
             plugins: Vec::new(),
+            disabled: HashSet::new(),
+            keymap_changes: HashMap::new(),
+            tooltip_changes: HashMap::new(),
+            last_consumer: None,
+            recorder: None,
+            render_timings: RefCell::new(Vec::new()),
         }
     }
 
     /// Register a plugin. Its `init` hook will be called immediately.
     ///
-    /// Plugins are automatically sorted by priority after registration.
-    /// Lower priority numbers are processed first.
-    pub fn register_plugin<P>(&mut self, mut plugin: P)
+    /// Plugins are ordered by their `depends_on`/`before`/`after`
+    /// declarations, then by priority (lower numbers are processed first),
+    /// then by registration order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin's ordering declarations cannot be satisfied: a
+    /// plugin it `depends_on` is not registered, or the `depends_on`,
+    /// `before` and `after` declarations form a cycle. Use
+    /// [`try_register_plugin`](Self::try_register_plugin) to handle that.
+    pub fn register_plugin<P>(&mut self, plugin: P)
+    where
+        P: LocustPlugin<B> + 'static,
+    {
+        if let Err(err) = self.try_register_plugin(plugin) {
+            panic!("Locust: Cannot register plugin: {}", err);
+        }
+    }
+
+    /// Register a plugin, reporting unsatisfiable ordering declarations.
+    ///
+    /// Fails without registering or initializing the plugin if one of its
+    /// dependencies is not registered or the declarations form a cycle.
+    pub fn try_register_plugin<P>(&mut self, mut plugin: P) -> Result<(), PluginOrderError>
     where
         P: LocustPlugin<B> + 'static,
     {
-        plugin.init(&mut self.ctx);
+        let nodes: Vec<OrderNode> = self
+            .plugins
+            .iter()
+            .map(|p| OrderNode::of(p.as_ref()))
+            .chain(std::iter::once(OrderNode::of(
+                &plugin as &dyn LocustPlugin<B>,
+            )))
+            .collect();
+        let order = resolve_order(&nodes)?;
+
+        self.init_plugin(&mut plugin);
         self.plugins.push(Box::new(plugin));
-        self.plugins.sort_by_key(|p| p.priority());
+
+        let mut slots: Vec<_> = self.plugins.drain(..).map(Some).collect();
+        self.plugins = order
+            .into_iter()
+            .filter_map(|index| slots[index].take())
+            .collect();
+        Ok(())
+    }
+
+    /// Run a plugin's `init` hook, timing it if metrics are enabled.
+    ///
+    /// Keymap bindings and tooltips the hook changes are remembered so
+    /// removing the plugin can put back what they replaced.
+    fn init_plugin(&mut self, plugin: &mut dyn LocustPlugin<B>) {
+        let ctx = &mut self.ctx;
+        let keymap = ctx.keymap.clone();
+        let tooltips = ctx.tooltips.snapshot();
+        let ((), elapsed) = timed(ctx.metrics.enabled, || plugin.init(ctx));
+        if let Some(elapsed) = elapsed {
+            ctx.metrics.record(plugin.id(), PluginPhase::Init, elapsed);
+        }
+        self.keymap_changes
+            .insert(plugin.id(), ctx.keymap.changes_since(&keymap));
+        self.tooltip_changes
+            .insert(plugin.id(), ctx.tooltips.changes_since(&tooltips));
+    }
+
+    /// Revert the keymap bindings and tooltips a plugin's `init` changed.
+    fn revert_init_changes(&mut self, id: &str) {
+        if let Some(changes) = self.keymap_changes.remove(id) {
+            self.ctx.keymap.revert(changes);
+        }
+        if let Some(changes) = self.tooltip_changes.remove(id) {
+            self.ctx.tooltips.revert(changes);
+        }
+    }
+
+    /// IDs of the registered plugins in the order they see events.
+    pub fn plugin_ids(&self) -> Vec<&'static str> {
+        self.plugins.iter().map(|p| p.id()).collect()
+    }
+
+    /// Remove a plugin at runtime.
+    ///
+    /// Calls the plugin's `cleanup` hook, then drops its overlay layers and
+    /// input modes and reverts the keymap bindings and tooltips its `init`
+    /// changed. Returns `false` if no plugin with this ID is registered, or
+    /// if another registered plugin `depends_on` it; use
+    /// [`try_unregister_plugin`](Self::try_unregister_plugin) to tell these
+    /// apart.
+    pub fn unregister_plugin(&mut self, id: &str) -> bool {
+        self.try_unregister_plugin(id).unwrap_or_else(|err| {
+            log::warn!("Locust: Cannot unregister plugin: {}", err);
+            false
+        })
+    }
+
+    /// Remove a plugin at runtime, reporting plugins that depend on it.
+    ///
+    /// Fails without removing anything if another registered plugin
+    /// `depends_on` this one. Otherwise returns whether a plugin with this
+    /// ID was registered, like [`unregister_plugin`](Self::unregister_plugin).
+    pub fn try_unregister_plugin(&mut self, id: &str) -> Result<bool, PluginOrderError> {
+        let Some(index) = self.plugins.iter().position(|p| p.id() == id) else {
+            return Ok(false);
+        };
+        if let Some(dependent) = self
+            .plugins
+            .iter()
+            .find(|p| p.id() != id && p.depends_on().contains(&id))
+        {
+            return Err(PluginOrderError::Required {
+                plugin: id.to_string(),
+                dependent: dependent.id().to_string(),
+            });
+        }
+
+        let mut plugin = self.plugins.remove(index);
+        plugin.cleanup(&mut self.ctx);
+
+        self.ctx.overlay.remove_layer(id);
+        self.ctx.modes.remove_owned_by(id);
+        self.revert_init_changes(id);
+        self.disabled.remove(id);
+        Ok(true)
+    }
+
+    /// Replace a registered plugin with a new instance of the same ID.
+    ///
+    /// The old instance is cleaned up and the new one takes over its place
+    /// in the dispatch order, whatever its own priority or ordering
+    /// declarations, as well as its enabled state. Keymap bindings and
+    /// tooltips the old instance's `init` changed are reverted before the
+    /// new one's `init` runs. If no plugin with this ID is registered, this
+    /// is the same as `register_plugin`. Returns whether a plugin was replaced.
+    pub fn replace_plugin<P>(&mut self, mut plugin: P) -> bool
+    where
+        P: LocustPlugin<B> + 'static,
+    {
+        let id = plugin.id();
+        let Some(index) = self.plugins.iter().position(|p| p.id() == id) else {
+            self.register_plugin(plugin);
+            return false;
+        };
+
+        self.plugins[index].cleanup(&mut self.ctx);
+        self.ctx.overlay.remove_layer(id);
+        self.ctx.modes.remove_owned_by(id);
+        self.revert_init_changes(id);
+
+        self.init_plugin(&mut plugin);
+        self.plugins[index] = Box::new(plugin);
+        true
+    }
+
+    /// Enable or disable a plugin without losing its state.
+    ///
+    /// Disabled plugins receive no events or ticks and are not rendered.
+    /// Disabling a plugin exits the modes it pushed (through its
+    /// `on_mode_exit` hook) so it cannot hold the keyboard while inactive.
+    /// Returns `false` if no plugin with this ID is registered.
+    pub fn set_plugin_enabled(&mut self, id: &str, enabled: bool) -> bool {
+        let Some(plugin) = self.plugins.iter_mut().find(|p| p.id() == id) else {
+            return false;
+        };
+
+        if enabled {
+            self.disabled.remove(plugin.id());
+        } else if self.disabled.insert(plugin.id()) {
+            for frame in self.ctx.modes.remove_owned_by(id) {
+                plugin.on_mode_exit(&frame.mode, &mut self.ctx);
+            }
+        }
+        true
+    }
+
+    /// Check if a registered plugin is enabled.
+    ///
+    /// Returns `false` if no plugin with this ID is registered.
+    pub fn is_plugin_enabled(&self, id: &str) -> bool {
+        self.has_plugin(id) && !self.disabled.contains(id)
     }
 
     /// Offer an input event to all plugins in registration order.
     ///
+    /// Mouse events first update the hovered target. Clicks and scroll-wheel
+    /// events no plugin consumed are then resolved against the target
+    /// registry.
+    ///
+    /// Timers are advanced to the current time first, as [`tick`] would, so
+    /// they also fire on input in hosts that never call [`tick`].
+    ///
     /// Returns whether the event was consumed and whether the caller
     /// should trigger a redraw.
+    ///
+    /// [`tick`]: Self::tick
     pub fn on_event(&mut self, event: &Event) -> LocustEventOutcome {
+        self.on_event_at(event, Instant::now())
+    }
+
+    /// Like [`on_event`](Self::on_event), for an event that arrived at `now`.
+    ///
+    /// Use this with an injected clock, e.g. to replay input with its
+    /// original timing.
+    pub fn on_event_at(&mut self, event: &Event, now: Instant) -> LocustEventOutcome {
+        self.ctx.clock = Some(now);
+        let ticked = self.advance_timers(now);
+        self.last_consumer = None;
+        let mut outcome = self.handle_event(event, now);
+        outcome.request_redraw |= ticked;
+        self.ctx.clock = None;
+
+        let consumed_by = self.last_consumer.map(str::to_string);
+        self.record(Some(event), now, outcome, consumed_by);
+        outcome
+    }
+
+    /// Appends an event, or a tick if `event` is `None`, to the recording.
+    fn record(
+        &mut self,
+        event: Option<&Event>,
+        now: Instant,
+        outcome: LocustEventOutcome,
+        consumed_by: Option<String>,
+    ) {
+        let Some(recorder) = self.recorder.as_mut() else {
+            return;
+        };
+        let entry = RecordedEvent {
+            at: recorder.elapsed(now),
+            frame: self.ctx.frame_count,
+            event: event.cloned(),
+            outcome,
+            consumed_by,
+        };
+        if let Err(err) = recorder.record(entry) {
+            log::warn!("Locust: Stopped recording events: {}", err);
+            self.recorder = None;
+        }
+    }
+
+    /// Start recording every event passed to `on_event` and every `tick`.
+    ///
+    /// Replaces any recorder already attached. See
+    /// [`recording`](crate::core::recording) for the format and replay.
+    pub fn start_recording(&mut self, recorder: EventRecorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Stop recording and return the recorder, if one was attached.
+    ///
+    /// Recording also stops on its own if writing an event fails.
+    pub fn stop_recording(&mut self) -> Option<EventRecorder> {
+        self.recorder.take()
+    }
+
+    /// Returns whether events are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// ID of the plugin that consumed the last event passed to `on_event`.
+    ///
+    /// `None` if no plugin consumed it, including events Locust handled
+    /// itself (focus keys, mode pops and pending key sequences).
+    pub fn last_consumer(&self) -> Option<&'static str> {
+        self.last_consumer
+    }
+
+    fn handle_event(&mut self, event: &Event, now: Instant) -> LocustEventOutcome {
+        self.ctx.sync_targets();
+
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Release {
+                return self.on_key(*key, now);
+            }
+        }
+
+        let mouse = match event {
+            Event::Mouse(mouse) if self.ctx.mouse_enabled() => Some(*mouse),
+            _ => None,
+        };
+        let hover_changed = mouse.is_some_and(|m| self.ctx.update_hover(m.column, m.row));
+
+        let mut outcome = self.dispatch(event);
+
+        if let Some(mouse) = mouse {
+            if !outcome.consumed && self.ctx.handle_mouse(&mouse) {
+                outcome = LocustEventOutcome::CONSUMED_REDRAW;
+            }
+        }
+
+        if hover_changed {
+            outcome.request_redraw = true;
+        }
+
+        outcome
+    }
+
+    /// Offer an event to plugins in priority order until one consumes it.
+    fn dispatch(&mut self, event: &Event) -> LocustEventOutcome {
         let mut results = Vec::with_capacity(self.plugins.len());
-        for plugin in self.plugins.iter_mut() {
-            let res = plugin.on_event(event, &mut self.ctx);
+        let disabled = &self.disabled;
+        for plugin in self
+            .plugins
+            .iter_mut()
+            .filter(|p| !disabled.contains(p.id()))
+        {
+            let ctx = &mut self.ctx;
+            let (res, elapsed) = timed(ctx.metrics.enabled, || plugin.on_event(event, ctx));
+            if let Some(elapsed) = elapsed {
+                ctx.metrics.record(plugin.id(), PluginPhase::Event, elapsed);
+            }
             results.push(res);
             if res.is_consumed() {
                 // Stop on first consumer; plugins are ordered.
+                self.last_consumer = Some(plugin.id());
                 break;
             }
         }
 
-        use crate::core::input::LocustEventOutcome as Outcome;
-        if results.is_empty() {
-            Outcome::NOT_HANDLED
-        } else {
-            let consumed = results.iter().any(|r| r.is_consumed());
-            let request_redraw = results.iter().any(|r| r.requests_redraw());
-            Outcome {
-                consumed,
-                request_redraw,
+        LocustEventOutcome {
+            consumed: results.iter().any(|r| r.is_consumed()),
+            request_redraw: results.iter().any(|r| r.requests_redraw()),
+        }
+    }
+
+    /// Run a key press through the sequence dispatcher, then the plugins.
+    ///
+    /// The `pop_mode` binding leaves an escapable top mode before anything
+    /// else sees the key. Keys that begin a sequence are held back and
+    /// reported as consumed so the status line can show them; sequences are
+    /// not matched while a mode captures text input.
+    fn on_key(&mut self, key: KeyEvent, now: Instant) -> LocustEventOutcome {
+        let pending = self.ctx.key_sequences.is_pending();
+        if !pending
+            && self.ctx.modes.top().is_some_and(|frame| frame.escapable)
+            && self
+                .ctx
+                .keymap
+                .is_bound("pop_mode", &KeyBinding::from(&key))
+        {
+            self.pop_mode();
+            return LocustEventOutcome::CONSUMED_REDRAW;
+        }
+
+        if !pending && self.ctx.is_capturing_input() {
+            return self.dispatch(&Event::Key(key));
+        }
+
+        match self.ctx.key_sequences.feed(&self.ctx.keymap, &key, now) {
+            SequenceOutcome::Unmatched => self.dispatch_key(key),
+            SequenceOutcome::Pending => LocustEventOutcome::CONSUMED_REDRAW,
+            SequenceOutcome::Matched { action, key } => self.dispatch_sequence(action, key),
+            SequenceOutcome::Replay(held) => {
+                self.replay_keys(held);
+                let mut outcome = self.on_key(key, now);
+                outcome.request_redraw = true;
+                outcome
+            }
+        }
+    }
+
+    /// Offer a key to plugins, then to the focus manager.
+    ///
+    /// Focus keys (Tab, Shift-Tab, Enter) only act if no plugin wanted them.
+    fn dispatch_key(&mut self, key: KeyEvent) -> LocustEventOutcome {
+        let outcome = self.dispatch(&Event::Key(key));
+        if !outcome.consumed && self.ctx.handle_focus_key(&key) {
+            return LocustEventOutcome::CONSUMED_REDRAW;
+        }
+        outcome
+    }
+
+    /// Deliver a completed sequence to plugins as its final key event.
+    ///
+    /// Actions no plugin handles are queued on `ctx.key_sequences` for the
+    /// application.
+    fn dispatch_sequence(&mut self, action: String, key: KeyEvent) -> LocustEventOutcome {
+        self.ctx.key_sequences.set_matched(Some(action.clone()));
+        let outcome = self.dispatch(&Event::Key(key));
+        self.ctx.key_sequences.set_matched(None);
+
+        if !outcome.consumed && !self.ctx.handle_focus_action(&action) {
+            self.ctx.key_sequences.push_unhandled_action(action);
+        }
+        LocustEventOutcome::CONSUMED_REDRAW
+    }
+
+    /// Deliver held keys of an abandoned sequence as ordinary input.
+    fn replay_keys(&mut self, held: Vec<KeyEvent>) {
+        for key in held {
+            if !self.dispatch_key(key).consumed {
+                self.ctx.key_sequences.push_unhandled_key(key);
             }
         }
     }
 
+    /// Pop the top input mode and notify the plugin that pushed it.
+    ///
+    /// Returns the popped mode, or `None` if Locust was in normal mode.
+    pub fn pop_mode(&mut self) -> Option<Mode> {
+        let frame = self.ctx.modes.pop()?;
+        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id() == frame.owner) {
+            plugin.on_mode_exit(&frame.mode, &mut self.ctx);
+        }
+        Some(frame.mode)
+    }
+
+    /// Returns whether Locust is treating keys as text input.
+    ///
+    /// Check this before handling keys Locust did not consume, e.g. to
+    /// avoid quitting on `q` while a prompt is open.
+    pub fn is_capturing_input(&self) -> bool {
+        self.ctx.is_capturing_input()
+    }
+
+    /// Advance time-based plugin state to `now`.
+    ///
+    /// Call this from the event loop whenever input polling times out so
+    /// delays, timeouts and animations fire while the user is idle;
+    /// `on_event` advances them too. Returns `true` if a redraw is needed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let timeout = locust
+    ///     .poll_timeout(Instant::now())
+    ///     .unwrap_or(Duration::from_millis(250));
+    /// if crossterm::event::poll(timeout)? {
+    ///     locust.on_event(&crossterm::event::read()?);
+    /// }
+    /// let needs_redraw = locust.tick(Instant::now());
+    /// ```
+    pub fn tick(&mut self, now: Instant) -> bool {
+        self.ctx.clock = Some(now);
+        let request_redraw = self.advance_timers(now);
+        self.ctx.clock = None;
+
+        let outcome = LocustEventOutcome {
+            consumed: false,
+            request_redraw,
+        };
+        self.record(None, now, outcome, None);
+        request_redraw
+    }
+
+    /// Expires key sequences and runs the plugins' `on_tick` at `now`.
+    fn advance_timers(&mut self, now: Instant) -> bool {
+        self.ctx.sync_targets();
+
+        let mut request_redraw = match self.ctx.key_sequences.expire(&self.ctx.keymap, now) {
+            SequenceOutcome::Matched { action, key } => {
+                self.dispatch_sequence(action, key);
+                true
+            }
+            SequenceOutcome::Replay(held) => {
+                self.replay_keys(held);
+                true
+            }
+            SequenceOutcome::Pending | SequenceOutcome::Unmatched => false,
+        };
+
+        let disabled = &self.disabled;
+        for plugin in self
+            .plugins
+            .iter_mut()
+            .filter(|p| !disabled.contains(p.id()))
+        {
+            request_redraw |= plugin.on_tick(now, &mut self.ctx);
+        }
+        request_redraw
+    }
+
+    /// Earliest instant at which any plugin needs `tick` to be called.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.plugins
+            .iter()
+            .filter(|p| !self.disabled.contains(p.id()))
+            .filter_map(|plugin| plugin.next_deadline(&self.ctx))
+            .chain(self.ctx.key_sequences.deadline(&self.ctx.keymap))
+            .min()
+    }
+
+    /// How long the event loop may wait for input before calling `tick`.
+    ///
+    /// Returns `None` if no timers are pending, and `Duration::ZERO` if a
+    /// deadline has already passed.
+    pub fn poll_timeout(&self, now: Instant) -> Option<Duration> {
+        self.next_deadline()
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
     /// Clear any per-frame state before drawing.
     ///
     /// This should be called at the start of each render loop iteration.
     pub fn begin_frame(&mut self) {
+        if self.ctx.metrics.enabled {
+            for (id, elapsed) in self.render_timings.get_mut().drain(..) {
+                self.ctx.metrics.record(id, PluginPhase::Render, elapsed);
+            }
+            if self.ctx.frame_count > 0 {
+                self.ctx
+                    .metrics
+                    .record_frame_targets(self.ctx.targets.len());
+            }
+        }
         self.ctx.targets.clear();
         self.ctx.overlay.begin_frame();
         self.ctx.frame_count = self.ctx.frame_count.wrapping_add(1);
     }
 
+    /// Add targets found in the rendered frame by heuristics.
+    ///
+    /// Call this after the application has drawn its widgets and before
+    /// `render_overlay`, passing `frame.buffer_mut()`. Does nothing unless
+    /// `ctx.discovery.enabled` is set. Discovered targets are low priority
+    /// and never replace registered ones; see
+    /// [`discovery`](crate::ratatui_ext::discovery). Returns the number of
+    /// targets added.
+    pub fn discover_targets(&mut self, buffer: &Buffer) -> usize {
+        if !self.ctx.discovery.enabled {
+            return 0;
+        }
+        let found = discovery::discover(buffer, &self.ctx.discovery);
+        discovery::merge_into(&mut self.ctx.targets, found)
+    }
+
     /// Ask all plugins to render their overlays on top of the frame.
     ///
     /// This should be called *after* the application has rendered its
-    /// widgets for the current frame. Plugins render in priority order.
+    /// widgets for the current frame. Visible layers in `ctx.overlay` are
+    /// drawn bottom to top by z-index; plugins without a registered layer
+    /// are drawn at the default z-index in priority order.
     pub fn render_overlay(&self, frame: &mut Frame) {
-        for plugin in self.plugins.iter() {
-            plugin.render_overlay(frame, &self.ctx);
+        let ids: Vec<&str> = self
+            .plugins
+            .iter()
+            .map(|p| p.id())
+            .filter(|id| !self.disabled.contains(id))
+            .collect();
+        for (layer, mask) in self.ctx.overlay.render_plan(&ids) {
+            if let Some(plugin) = self.plugins.iter().find(|p| p.id() == layer.plugin_id) {
+                let ((), elapsed) = timed(self.ctx.metrics.enabled, || {
+                    plugin.render_layer(frame, &layer, &mask, &self.ctx)
+                });
+                if let Some(elapsed) = elapsed {
+                    self.render_timings
+                        .borrow_mut()
+                        .push((plugin.id(), elapsed));
+                }
+            }
         }
     }
 
+    /// Take all target activations emitted since the last call.
+    ///
+    /// Call this after `on_event` to perform the actions of targets the
+    /// user picked through a plugin.
+    pub fn drain_actions(&mut self) -> Vec<TargetActivation> {
+        self.ctx.actions.drain()
+    }
+
     /// Get the number of registered plugins.
     pub fn plugin_count(&self) -> usize {
         self.plugins.len()
@@ -296,4 +1194,22 @@ where
             plugin.cleanup(&mut self.ctx);
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::targets::{NavTarget, TargetState};
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_disabled_target_is_not_activated() {
+        let mut ctx = LocustContext::default();
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(0, 0, 5, 1)).with_state(TargetState::Disabled));
+
+        assert!(!ctx.activate_target(1));
+        assert!(!ctx.activate_target(2));
+        assert!(ctx.actions.is_empty());
+    }
+}
@@ -0,0 +1,390 @@
+//! Keyboard focus over navigation targets.
+//!
+//! The [`FocusManager`] on `LocustContext` remembers which target has
+//! keyboard focus. Targets are re-registered every frame, so the focused
+//! target is found again by its ID, label, group or stable key (see
+//! [`FocusTracking`]).
+//! Focus cycles with the `focus_next`/`focus_prev` keymap actions in reading
+//! order or by [`TargetPriority`](crate::core::targets::TargetPriority),
+//! moves to the nearest target with `focus_left` and the other directions
+//! (see [`spatial`](crate::core::spatial)), and Enter (`activate_focus`)
+//! activates the focused target. Only Enter is bound by default, so Tab and
+//! Shift+arrows stay with the application until these actions are bound.
+//!
+//! The focused target is shown as `TargetState::Highlighted` and the last
+//! target activated through focus as `TargetState::Selected`. Focus changes
+//! are queued as [`FocusEvent`]s for the host application.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::core::context::LocustContext;
+//! use locust::core::focus::FocusEvent;
+//! use locust::core::targets::{NavTarget, TargetState};
+//! use ratatui::layout::Rect;
+//!
+//! let mut ctx = LocustContext::default();
+//! ctx.targets.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+//! ctx.targets.register(NavTarget::new(2, Rect::new(0, 2, 10, 1)));
+//!
+//! assert!(ctx.focus_next());
+//! assert_eq!(ctx.focus.focused(), Some(1));
+//! assert_eq!(ctx.targets.by_id(1).unwrap().state, TargetState::Highlighted);
+//!
+//! ctx.focus_next();
+//! assert_eq!(
+//!     ctx.focus.drain_events(),
+//!     vec![FocusEvent::Focused(1), FocusEvent::Blurred(1), FocusEvent::Focused(2)]
+//! );
+//! ```
+
+use crate::core::spatial::SpatialConfig;
+use crate::core::targets::{NavTarget, TargetKey, TargetRegistry, TargetState};
+use std::collections::VecDeque;
+
+/// Order in which Tab cycles through targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FocusOrder {
+    /// Top to bottom, then left to right
+    #[default]
+    ReadingOrder,
+    /// Highest priority first, reading order within a priority
+    Priority,
+}
+
+/// How the focused target is recognised in later frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FocusTracking {
+    /// By target ID; use when IDs are stable across frames
+    #[default]
+    Id,
+    /// By label; use when IDs are regenerated every frame
+    Label,
+    /// By group; focus stays in the group if the exact target is gone
+    Group,
+    /// By stable key (see `NavTarget::stable_key`); use with `with_key`
+    /// or unique labels
+    Key,
+}
+
+/// A focus change, queued for the host application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusEvent {
+    /// The target gained keyboard focus.
+    Focused(u64),
+    /// The target lost keyboard focus.
+    Blurred(u64),
+}
+
+/// What is remembered about the focused target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FocusedTarget {
+    id: u64,
+    label: Option<String>,
+    group: Option<String>,
+    key: TargetKey,
+}
+
+impl FocusedTarget {
+    fn of(target: &NavTarget) -> Self {
+        Self {
+            id: target.id,
+            label: target.label.clone(),
+            group: target.group.clone(),
+            key: target.stable_key(),
+        }
+    }
+}
+
+/// Tracks the target with keyboard focus.
+///
+/// Changing focus goes through `LocustContext` (`focus_target`,
+/// `focus_next`, ...) so target states stay in sync.
+#[derive(Debug, Default)]
+pub struct FocusManager {
+    /// Order used by `focus_next`/`focus_prev`.
+    pub order: FocusOrder,
+
+    /// How the focused target is found again after targets are re-registered.
+    pub tracking: FocusTracking,
+
+    /// Group restriction and wrapping for directional moves.
+    pub spatial: SpatialConfig,
+
+    focused: Option<FocusedTarget>,
+    /// ID and registry key of the selected target.
+    selected: Option<(u64, TargetKey)>,
+    events: VecDeque<FocusEvent>,
+}
+
+impl FocusManager {
+    /// Creates a focus manager with nothing focused.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the cycling order.
+    pub fn with_order(mut self, order: FocusOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets how focus is tracked across frames.
+    pub fn with_tracking(mut self, tracking: FocusTracking) -> Self {
+        self.tracking = tracking;
+        self
+    }
+
+    /// Sets the options for directional moves.
+    pub fn with_spatial(mut self, spatial: SpatialConfig) -> Self {
+        self.spatial = spatial;
+        self
+    }
+
+    /// ID of the focused target.
+    pub fn focused(&self) -> Option<u64> {
+        self.focused.as_ref().map(|f| f.id)
+    }
+
+    /// ID of the target last activated through focus.
+    pub fn selected(&self) -> Option<u64> {
+        self.selected.as_ref().map(|(id, _)| *id)
+    }
+
+    /// Returns whether `id` has focus.
+    pub fn is_focused(&self, id: u64) -> bool {
+        self.focused() == Some(id)
+    }
+
+    /// Removes and returns all pending focus events in order.
+    pub fn drain_events(&mut self) -> Vec<FocusEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Moves focus to `target`, or clears it. Returns the previous ID.
+    pub(crate) fn set(&mut self, target: Option<&NavTarget>) -> Option<u64> {
+        let previous = self.focused();
+        let next = target.map(|t| t.id);
+        if previous == next {
+            self.focused = target.map(FocusedTarget::of);
+            return previous;
+        }
+
+        if let Some(id) = previous {
+            self.events.push_back(FocusEvent::Blurred(id));
+        }
+        if let Some(id) = next {
+            self.events.push_back(FocusEvent::Focused(id));
+        }
+        self.focused = target.map(FocusedTarget::of);
+        previous
+    }
+
+    /// Follows the focused target to a new ID without queueing events.
+    pub(crate) fn retarget(&mut self, target: &NavTarget) {
+        self.focused = Some(FocusedTarget::of(target));
+    }
+
+    /// Marks the target with `id` and `key` as selected.
+    pub(crate) fn select(&mut self, id: u64, key: TargetKey) {
+        self.selected = Some((id, key));
+    }
+
+    /// Follows the selected target to its ID in `targets` by key, or drops
+    /// the selection if the target is gone.
+    pub(crate) fn sync_selected(&mut self, targets: &TargetRegistry) {
+        self.selected = self
+            .selected
+            .take()
+            .and_then(|(_, key)| Some((targets.by_key(&key)?.id, key)));
+    }
+
+    /// IDs of focusable targets in cycling order.
+    ///
+    /// Disabled targets are skipped.
+    pub fn cycle_order(&self, targets: &TargetRegistry) -> Vec<u64> {
+        let mut focusable: Vec<&NavTarget> = targets
+            .all()
+            .iter()
+            .filter(|t| t.state != TargetState::Disabled)
+            .collect();
+
+        match self.order {
+            FocusOrder::ReadingOrder => {
+                focusable.sort_by_key(|t| (t.rect.y, t.rect.x));
+            }
+            FocusOrder::Priority => {
+                focusable.sort_by_key(|t| (std::cmp::Reverse(t.priority), t.rect.y, t.rect.x));
+            }
+        }
+        focusable.into_iter().map(|t| t.id).collect()
+    }
+
+    /// The target after (or before) the focused one, wrapping around.
+    ///
+    /// With nothing focused, this is the first (or last) target.
+    pub fn neighbor(&self, targets: &TargetRegistry, forward: bool) -> Option<u64> {
+        let order = self.cycle_order(targets);
+        if order.is_empty() {
+            return None;
+        }
+
+        let position = self
+            .focused()
+            .and_then(|id| order.iter().position(|&o| o == id));
+        let index = match (position, forward) {
+            (Some(i), true) => (i + 1) % order.len(),
+            (Some(i), false) => (i + order.len() - 1) % order.len(),
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+        };
+        Some(order[index])
+    }
+
+    /// Finds the focused target among the currently registered targets.
+    ///
+    /// Returns `None` if nothing is focused or the target is gone.
+    pub fn resolve<'a>(&self, targets: &'a TargetRegistry) -> Option<&'a NavTarget> {
+        let focused = self.focused.as_ref()?;
+        let usable = |t: &&NavTarget| t.state != TargetState::Disabled;
+
+        match self.tracking {
+            FocusTracking::Id => targets.by_id(focused.id).filter(usable),
+            FocusTracking::Label => {
+                let label = focused.label.as_deref()?;
+                targets
+                    .all()
+                    .iter()
+                    .filter(usable)
+                    .find(|t| t.label.as_deref() == Some(label))
+            }
+            FocusTracking::Group => {
+                let group = focused.group.as_deref()?;
+                targets
+                    .by_id(focused.id)
+                    .filter(usable)
+                    .filter(|t| t.group.as_deref() == Some(group))
+                    .or_else(|| {
+                        self.cycle_order(targets)
+                            .into_iter()
+                            .filter_map(|id| targets.by_id(id))
+                            .find(|t| t.group.as_deref() == Some(group))
+                    })
+            }
+            FocusTracking::Key => targets.by_key(&focused.key).filter(usable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::targets::TargetPriority;
+    use ratatui::layout::Rect;
+
+    fn registry() -> TargetRegistry {
+        let mut targets = TargetRegistry::new();
+        targets.register(NavTarget::new(1, Rect::new(20, 0, 5, 1)).with_label("B"));
+        targets.register(
+            NavTarget::new(2, Rect::new(0, 3, 5, 1))
+                .with_label("C")
+                .with_priority(TargetPriority::High),
+        );
+        targets.register(NavTarget::new(3, Rect::new(0, 0, 5, 1)).with_label("A"));
+        targets
+    }
+
+    #[test]
+    fn test_reading_order() {
+        let focus = FocusManager::new();
+        assert_eq!(focus.cycle_order(&registry()), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let focus = FocusManager::new().with_order(FocusOrder::Priority);
+        assert_eq!(focus.cycle_order(&registry()), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_disabled_targets_are_skipped() {
+        let mut targets = registry();
+        targets.by_id_mut(1).unwrap().state = TargetState::Disabled;
+        assert_eq!(FocusManager::new().cycle_order(&targets), vec![3, 2]);
+    }
+
+    #[test]
+    fn test_neighbor_wraps() {
+        let targets = registry();
+        let mut focus = FocusManager::new();
+        assert_eq!(focus.neighbor(&targets, true), Some(3));
+        assert_eq!(focus.neighbor(&targets, false), Some(2));
+
+        focus.set(targets.by_id(2));
+        assert_eq!(focus.neighbor(&targets, true), Some(3));
+        assert_eq!(focus.neighbor(&targets, false), Some(1));
+    }
+
+    #[test]
+    fn test_set_queues_events() {
+        let targets = registry();
+        let mut focus = FocusManager::new();
+        focus.set(targets.by_id(1));
+        focus.set(targets.by_id(1));
+        focus.set(None);
+
+        assert_eq!(
+            focus.drain_events(),
+            vec![FocusEvent::Focused(1), FocusEvent::Blurred(1)]
+        );
+    }
+
+    #[test]
+    fn test_resolve_by_label_after_ids_change() {
+        let mut focus = FocusManager::new().with_tracking(FocusTracking::Label);
+        focus.set(registry().by_id(1));
+
+        let mut next_frame = TargetRegistry::new();
+        next_frame.register(NavTarget::new(10, Rect::new(0, 0, 5, 1)).with_label("A"));
+        next_frame.register(NavTarget::new(11, Rect::new(20, 0, 5, 1)).with_label("B"));
+
+        assert_eq!(focus.resolve(&next_frame).map(|t| t.id), Some(11));
+    }
+
+    #[test]
+    fn test_resolve_by_group_falls_back_to_group() {
+        let mut targets = TargetRegistry::new();
+        targets.register(NavTarget::new(1, Rect::new(0, 0, 5, 1)).with_group("list"));
+        let mut focus = FocusManager::new().with_tracking(FocusTracking::Group);
+        focus.set(targets.by_id(1));
+
+        let mut next_frame = TargetRegistry::new();
+        next_frame.register(NavTarget::new(5, Rect::new(0, 0, 5, 1)));
+        next_frame.register(NavTarget::new(7, Rect::new(0, 2, 5, 1)).with_group("list"));
+
+        assert_eq!(focus.resolve(&next_frame).map(|t| t.id), Some(7));
+        assert!(FocusManager::new().resolve(&next_frame).is_none());
+    }
+
+    #[test]
+    fn test_resolve_by_key_after_relabel() {
+        let mut targets = TargetRegistry::new();
+        targets.register(
+            NavTarget::new(1, Rect::new(0, 0, 5, 1))
+                .with_key("unread")
+                .with_label("Unread (3)"),
+        );
+        let mut focus = FocusManager::new().with_tracking(FocusTracking::Key);
+        focus.set(targets.by_id(1));
+
+        let mut next_frame = TargetRegistry::new();
+        next_frame.register(NavTarget::new(1, Rect::new(0, 0, 5, 1)).with_label("Unread (3)"));
+        next_frame.register(
+            NavTarget::new(2, Rect::new(0, 1, 5, 1))
+                .with_key("unread")
+                .with_label("Unread (4)"),
+        );
+
+        assert_eq!(focus.resolve(&next_frame).map(|t| t.id), Some(2));
+    }
+}
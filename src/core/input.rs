@@ -25,7 +25,7 @@ impl PluginEventResult {
 }
 
 /// Indicates whether Locust consumed an event and whether the caller should redraw.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct LocustEventOutcome {
     pub consumed: bool,
     pub request_redraw: bool,
@@ -46,6 +46,16 @@ impl LocustEventOutcome {
     };
 }
 
+/// Pointer state tracked by Locust from mouse events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MouseState {
+    /// Last known pointer position as (column, row).
+    pub position: Option<(u16, u16)>,
+
+    /// Topmost target under the pointer, if any.
+    pub hovered_target: Option<u64>,
+}
+
 // Tiny compile-time guard that Event is what we expect.
 #[allow(dead_code)]
 fn _assert_event_is_crossterm_event(_: &Event) {}
@@ -1,8 +1,11 @@
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, thiserror::Error)]
 pub enum KeyMapError {
@@ -44,16 +47,255 @@ impl KeyBinding {
     pub fn get_modifiers(&self) -> KeyModifiers {
         KeyModifiers::from_bits_truncate(self.modifiers)
     }
+
+    /// Returns the binding in the form used for matching.
+    ///
+    /// Terminals disagree on whether Shift is reported for character keys,
+    /// and the character itself already carries the case, so Shift is
+    /// dropped from character bindings: `'O'` matches with or without it.
+    /// The same goes for `backtab`, which is Shift-Tab by definition.
+    pub fn normalized(&self) -> Self {
+        let mut modifiers = self.get_modifiers();
+        let implies_shift = match &self.key {
+            KeyCodeDef::Char(_) => true,
+            KeyCodeDef::Named(name) => name == "backtab",
+            _ => false,
+        };
+        if implies_shift {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self::with_modifiers(self.key.clone(), modifiers)
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(event: &KeyEvent) -> Self {
+        KeyBinding::with_modifiers(event.code.into(), event.modifiers).normalized()
+    }
+}
+
+/// Formats the binding in sequence notation, e.g. `C-w`, `<esc>`, `<F5>`.
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let binding = self.normalized();
+        let modifiers = binding.get_modifiers();
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "A-")?;
+        }
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "S-")?;
+        }
+        match &binding.key {
+            KeyCodeDef::Char(' ') => write!(f, "<space>"),
+            KeyCodeDef::Char(c) => write!(f, "{}", c),
+            KeyCodeDef::F(n) => write!(f, "<F{}>", n),
+            KeyCodeDef::Named(name) => write!(f, "<{}>", name),
+        }
+    }
 }
 
+/// Parses a single key in sequence notation.
+///
+/// A key is a character (`g`), a named key (`esc`, `<enter>`, `space`) or
+/// a function key (`F5`), optionally prefixed by modifiers: `C-`/`Ctrl-`,
+/// `A-`/`Alt-` and `S-`/`Shift-`. Vim-style brackets around the whole key
+/// (`<C-w>`) are accepted.
+impl FromStr for KeyBinding {
+    type Err = KeyMapError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = || KeyMapError::ParseError(format!("Invalid key: {:?}", token));
+
+        let mut rest = match token.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+            Some(inner) if !inner.is_empty() => inner,
+            _ => token,
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        while let Some((prefix, tail)) = rest.split_once('-') {
+            if tail.is_empty() {
+                break;
+            }
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "c" | "ctrl" => KeyModifiers::CONTROL,
+                "a" | "alt" | "m" | "meta" => KeyModifiers::ALT,
+                "s" | "shift" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            rest = tail;
+        }
+        if let Some(inner) = rest.strip_prefix('<').and_then(|r| r.strip_suffix('>')) {
+            if !inner.is_empty() {
+                rest = inner;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let key = match (chars.next(), chars.next()) {
+            (None, _) => return Err(invalid()),
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCodeDef::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCodeDef::Char(c),
+            _ => {
+                let name = rest.to_ascii_lowercase();
+                match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n)) => KeyCodeDef::F(n),
+                    _ if name == "space" => KeyCodeDef::Char(' '),
+                    _ => {
+                        let named = KeyCodeDef::Named(name);
+                        if named.to_keycode() == KeyCode::Null {
+                            return Err(invalid());
+                        }
+                        named
+                    }
+                }
+            }
+        };
+
+        Ok(KeyBinding::with_modifiers(key, modifiers).normalized())
+    }
+}
+
+/// One step of a [`KeySequence`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceKey {
+    /// Stands for the keymap's leader key.
+    Leader,
+    /// A concrete key.
+    Key(KeyBinding),
+}
+
+/// An ordered series of keys bound to one action, such as `g g`,
+/// `<leader> f` or `C-w h`.
+///
+/// Sequences are written as whitespace-separated keys (see the
+/// [`KeyBinding`] `FromStr` impl for the per-key syntax) and serialize
+/// to the same string form.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeySequence {
+    keys: Vec<SequenceKey>,
+}
+
+impl KeySequence {
+    /// Creates a sequence from its keys.
+    pub fn new(keys: Vec<SequenceKey>) -> Self {
+        Self { keys }
+    }
+
+    /// Returns the keys of the sequence.
+    pub fn keys(&self) -> &[SequenceKey] {
+        &self.keys
+    }
+
+    /// Returns the number of keys in the sequence.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the sequence has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the concrete keys, with `<leader>` replaced by `leader`.
+    pub fn resolve(&self, leader: &KeyBinding) -> Vec<KeyBinding> {
+        self.keys
+            .iter()
+            .map(|key| match key {
+                SequenceKey::Leader => leader.normalized(),
+                SequenceKey::Key(binding) => binding.normalized(),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = KeyMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(|token| {
+                if token.eq_ignore_ascii_case("<leader>") {
+                    Ok(SequenceKey::Leader)
+                } else {
+                    token.parse().map(SequenceKey::Key)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if keys.is_empty() {
+            return Err(KeyMapError::ParseError("Empty key sequence".to_string()));
+        }
+        Ok(Self { keys })
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.keys.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match key {
+                SequenceKey::Leader => write!(f, "<leader>")?,
+                SequenceKey::Key(binding) => write!(f, "{}", binding)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for KeySequence {
+    type Error = KeyMapError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<KeySequence> for String {
+    fn from(sequence: KeySequence) -> Self {
+        sequence.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged, from = "KeyCodeRepr")]
 pub enum KeyCodeDef {
     Char(char),
     F(u8),
     Named(String),
 }
 
+/// Accepted TOML spellings of a key: `'q'`, `"esc"`, `1` or `{ F = 1 }`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyCodeRepr {
+    Char(char),
+    F(u8),
+    FTable {
+        #[serde(rename = "F")]
+        f: u8,
+    },
+    Named(String),
+}
+
+impl From<KeyCodeRepr> for KeyCodeDef {
+    fn from(repr: KeyCodeRepr) -> Self {
+        match repr {
+            KeyCodeRepr::Char(c) => KeyCodeDef::Char(c),
+            KeyCodeRepr::F(n) | KeyCodeRepr::FTable { f: n } => KeyCodeDef::F(n),
+            KeyCodeRepr::Named(name) => KeyCodeDef::Named(name),
+        }
+    }
+}
+
 impl KeyCodeDef {
     pub fn to_keycode(&self) -> KeyCode {
         match self {
@@ -106,25 +348,74 @@ impl From<KeyCode> for KeyCodeDef {
     }
 }
 
+/// How two or more bindings collide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// The same keys are bound to several actions.
+    Duplicate,
+    /// The keys of one action begin a longer sequence bound to another, so
+    /// the shorter one only fires after the sequence timeout.
+    Prefix,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConflictError {
+    /// First key of the conflicting keys.
     pub binding: KeyBinding,
     pub actions: Vec<String>,
+    pub kind: ConflictKind,
+    /// The full conflicting keys (for prefix conflicts, the shorter ones).
+    pub sequence: Vec<KeyBinding>,
 }
 
 impl std::fmt::Display for ConflictError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Key binding {:?} conflicts between actions: {}",
-            self.binding,
-            self.actions.join(", ")
-        )
+        let keys = self
+            .sequence
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        match self.kind {
+            ConflictKind::Duplicate => write!(
+                f,
+                "Key binding {} conflicts between actions: {}",
+                keys,
+                self.actions.join(", ")
+            ),
+            ConflictKind::Prefix => write!(
+                f,
+                "Key binding {} for {} is a prefix of: {}",
+                keys,
+                self.actions[0],
+                self.actions[1..].join(", ")
+            ),
+        }
     }
 }
 
+fn default_leader() -> KeyBinding {
+    KeyBinding::new(KeyCodeDef::Char('\\'))
+}
+
+fn default_sequence_timeout_ms() -> u64 {
+    1000
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyMap {
+    /// How long to wait for the next key of a sequence, in milliseconds
+    #[serde(default = "default_sequence_timeout_ms")]
+    pub sequence_timeout_ms: u64,
+
+    /// Key that `<leader>` stands for in sequences (default: `\`)
+    #[serde(default = "default_leader")]
+    pub leader: KeyBinding,
+
+    /// Multi-key sequences, keyed by action name (`"top"`, `"nav.activate"`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sequences: HashMap<String, KeySequence>,
+
     /// Global keybindings
     #[serde(default)]
     pub global: HashMap<String, KeyBinding>,
@@ -132,13 +423,22 @@ pub struct KeyMap {
     /// Per-plugin keybindings
     #[serde(default)]
     pub plugins: HashMap<String, HashMap<String, KeyBinding>>,
+
+    /// Bindings that apply only while a mode is on top of the mode stack,
+    /// keyed by mode name then qualified action (`"nav.cancel"`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub modes: HashMap<String, HashMap<String, KeyBinding>>,
 }
 
 impl Default for KeyMap {
     fn default() -> Self {
         let mut keymap = KeyMap {
+            sequence_timeout_ms: default_sequence_timeout_ms(),
+            leader: default_leader(),
+            sequences: HashMap::new(),
             global: HashMap::new(),
             plugins: HashMap::new(),
+            modes: HashMap::new(),
         };
 
         // Global bindings
@@ -148,6 +448,14 @@ impl Default for KeyMap {
         keymap
             .global
             .insert("help".to_string(), KeyBinding::new(KeyCodeDef::F(1)));
+        keymap.global.insert(
+            "pop_mode".to_string(),
+            KeyBinding::new(KeyCodeDef::Named("esc".to_string())),
+        );
+        keymap.global.insert(
+            "activate_focus".to_string(),
+            KeyBinding::new(KeyCodeDef::Named("enter".to_string())),
+        );
 
         // Nav plugin bindings
         let mut nav_bindings = HashMap::new();
@@ -188,6 +496,7 @@ impl Default for KeyMap {
 
         // Highlight plugin bindings
         let mut highlight_bindings = HashMap::new();
+        highlight_bindings.insert("start".to_string(), KeyBinding::new(KeyCodeDef::Char('?')));
         highlight_bindings.insert(
             "next_step".to_string(),
             KeyBinding::new(KeyCodeDef::Char('n')),
@@ -204,6 +513,40 @@ impl Default for KeyMap {
             .plugins
             .insert("highlight".to_string(), highlight_bindings);
 
+        // Arrow keys, Enter and Esc also drive a running tour
+        let mut tour_bindings = HashMap::new();
+        for (action, key) in [
+            ("highlight.next_step", "right"),
+            ("highlight.previous_step", "left"),
+            ("highlight.advance", "enter"),
+            ("highlight.skip_tour", "esc"),
+        ] {
+            tour_bindings.insert(
+                action.to_string(),
+                KeyBinding::new(KeyCodeDef::Named(key.to_string())),
+            );
+        }
+        keymap.modes.insert("tour".to_string(), tour_bindings);
+
+        // Metrics HUD bindings
+        let mut hud_bindings = HashMap::new();
+        hud_bindings.insert("toggle".to_string(), KeyBinding::new(KeyCodeDef::F(12)));
+        keymap.plugins.insert("hud".to_string(), hud_bindings);
+
+        // Pattern hint bindings
+        let mut patterns_bindings = HashMap::new();
+        patterns_bindings.insert(
+            "activate".to_string(),
+            KeyBinding::new(KeyCodeDef::Char('F')),
+        );
+        patterns_bindings.insert(
+            "cancel".to_string(),
+            KeyBinding::new(KeyCodeDef::Named("esc".to_string())),
+        );
+        keymap
+            .plugins
+            .insert("patterns".to_string(), patterns_bindings);
+
         keymap
     }
 }
@@ -234,7 +577,83 @@ impl KeyMap {
         Ok(())
     }
 
+    /// Binds a multi-key sequence to an action.
+    ///
+    /// Action names follow [`bind`](Self::bind): `"plugin.action"` or a
+    /// global name. A sequence is independent of any single-key binding
+    /// the action also has.
+    pub fn bind_sequence(
+        &mut self,
+        action: &str,
+        sequence: KeySequence,
+    ) -> Result<(), KeyMapError> {
+        if sequence.is_empty() {
+            return Err(KeyMapError::ParseError("Empty key sequence".to_string()));
+        }
+        self.sequences.insert(action.to_string(), sequence);
+        Ok(())
+    }
+
+    /// Binds a key to an action only while `mode` is the current mode.
+    ///
+    /// Mode bindings take precedence over the action's plugin section, so a
+    /// key can mean different things in different modes.
+    pub fn bind_in_mode(
+        &mut self,
+        mode: &str,
+        action: &str,
+        binding: KeyBinding,
+    ) -> Result<(), KeyMapError> {
+        if !action.contains('.') {
+            return Err(KeyMapError::ParseError(format!(
+                "Mode binding '{}' must name a plugin action",
+                action
+            )));
+        }
+        self.modes
+            .entry(mode.to_string())
+            .or_default()
+            .insert(action.to_string(), binding);
+        Ok(())
+    }
+
+    pub fn get_sequence(&self, action: &str) -> Option<&KeySequence> {
+        self.sequences.get(action)
+    }
+
+    /// Returns how long a partial sequence waits for its next key.
+    pub fn sequence_timeout(&self) -> Duration {
+        Duration::from_millis(self.sequence_timeout_ms)
+    }
+
+    /// Matches typed keys against the bound sequences.
+    pub fn match_sequence(&self, keys: &[KeyBinding]) -> SequenceMatch {
+        let keys: Vec<KeyBinding> = keys.iter().map(KeyBinding::normalized).collect();
+        let mut exact = None;
+        let mut longer = false;
+
+        for (action, sequence) in &self.sequences {
+            let resolved = sequence.resolve(&self.leader);
+            if resolved == keys {
+                exact = Some(action.clone());
+            } else if resolved.len() > keys.len() && resolved.starts_with(&keys) {
+                longer = true;
+            }
+        }
+
+        match (exact, longer) {
+            (Some(action), false) => SequenceMatch::Complete(action),
+            (Some(action), true) => SequenceMatch::Ambiguous(action),
+            (None, true) => SequenceMatch::Partial,
+            (None, false) => SequenceMatch::None,
+        }
+    }
+
     pub fn unbind(&mut self, action: &str) {
+        self.sequences.remove(action);
+        for bindings in self.modes.values_mut() {
+            bindings.remove(action);
+        }
         if let Some((plugin, action_name)) = action.split_once('.') {
             if let Some(bindings) = self.plugins.get_mut(plugin) {
                 bindings.remove(action_name);
@@ -244,6 +663,22 @@ impl KeyMap {
         }
     }
 
+    /// Removes a plugin's section and every sequence or mode binding for
+    /// its actions (`"<plugin>.*"`).
+    pub fn unbind_plugin(&mut self, plugin: &str) {
+        let owned = |action: &String| {
+            action
+                .strip_prefix(plugin)
+                .is_some_and(|rest| rest.starts_with('.'))
+        };
+        self.plugins.remove(plugin);
+        self.sequences.retain(|action, _| !owned(action));
+        for bindings in self.modes.values_mut() {
+            bindings.retain(|action, _| !owned(action));
+        }
+        self.modes.retain(|_, bindings| !bindings.is_empty());
+    }
+
     pub fn get_binding(&self, action: &str) -> Option<&KeyBinding> {
         if let Some((plugin, action_name)) = action.split_once('.') {
             self.plugins
@@ -255,25 +690,70 @@ impl KeyMap {
     }
 
     pub fn get_action(&self, binding: &KeyBinding) -> Option<String> {
+        let binding = binding.normalized();
+
         // Check global bindings
         for (action, bound) in &self.global {
-            if bound == binding {
+            if bound.normalized() == binding {
                 return Some(action.clone());
             }
         }
 
         // Check plugin bindings
-        for (plugin, bindings) in &self.plugins {
-            for (action, bound) in bindings {
-                if bound == binding {
-                    return Some(format!("{}.{}", plugin, action));
-                }
+        for plugin in self.plugins.keys() {
+            if let Some(action) = self.get_plugin_action(plugin, &binding) {
+                return Some(action);
             }
         }
 
         None
     }
 
+    /// Looks up the action a binding triggers within one plugin's section.
+    ///
+    /// Several plugins may share a key (every built-in plugin cancels on
+    /// Esc), so plugins resolve their own triggers here rather than through
+    /// [`get_action`](Self::get_action). Returns the qualified action name,
+    /// e.g. `"nav.activate"`.
+    pub fn get_plugin_action(&self, plugin: &str, binding: &KeyBinding) -> Option<String> {
+        let binding = binding.normalized();
+        self.plugins
+            .get(plugin)?
+            .iter()
+            .find_map(|(action, bound)| {
+                (bound.normalized() == binding).then(|| format!("{}.{}", plugin, action))
+            })
+    }
+
+    /// Looks up a plugin action bound in one mode's section.
+    ///
+    /// Returns the qualified action name, or `None` if the mode has no
+    /// binding for this key in the plugin's namespace.
+    pub fn get_mode_action(
+        &self,
+        mode: &str,
+        plugin: &str,
+        binding: &KeyBinding,
+    ) -> Option<String> {
+        let binding = binding.normalized();
+        self.modes
+            .get(mode)?
+            .iter()
+            .find(|(action, bound)| {
+                action
+                    .strip_prefix(plugin)
+                    .is_some_and(|rest| rest.starts_with('.'))
+                    && bound.normalized() == binding
+            })
+            .map(|(action, _)| action.clone())
+    }
+
+    /// Returns whether `binding` is the key bound to `action`.
+    pub fn is_bound(&self, action: &str, binding: &KeyBinding) -> bool {
+        self.get_binding(action)
+            .is_some_and(|bound| bound.normalized() == binding.normalized())
+    }
+
     pub fn validate(&self) -> Result<(), Vec<ConflictError>> {
         let conflicts = detect_conflicts(self);
         if conflicts.is_empty() {
@@ -282,15 +762,105 @@ impl KeyMap {
             Err(conflicts)
         }
     }
+
+    /// Records the bindings changed since `before`, so they can be reverted.
+    pub(crate) fn changes_since(&self, before: &KeyMap) -> KeyMapChanges {
+        KeyMapChanges {
+            global: diff(Some(&before.global), Some(&self.global)),
+            plugins: diff_sections(&before.plugins, &self.plugins),
+            modes: diff_sections(&before.modes, &self.modes),
+            sequences: diff(Some(&before.sequences), Some(&self.sequences)),
+        }
+    }
+
+    /// Puts back the bindings `changes` replaced.
+    ///
+    /// Bindings changed again since `changes` was recorded are left alone.
+    pub(crate) fn revert(&mut self, changes: KeyMapChanges) {
+        revert(&mut self.global, changes.global);
+        revert_sections(&mut self.plugins, changes.plugins);
+        revert_sections(&mut self.modes, changes.modes);
+        revert(&mut self.sequences, changes.sequences);
+    }
+}
+
+/// A binding that changed: its name, the value before and the value after.
+type Change<V> = (String, Option<V>, Option<V>);
+
+/// Bindings changed in a keymap, with the values they replaced.
+#[derive(Debug, Default)]
+pub(crate) struct KeyMapChanges {
+    global: Vec<Change<KeyBinding>>,
+    plugins: Vec<(String, Vec<Change<KeyBinding>>)>,
+    modes: Vec<(String, Vec<Change<KeyBinding>>)>,
+    sequences: Vec<Change<KeySequence>>,
+}
+
+fn diff<V: Clone + PartialEq>(
+    before: Option<&HashMap<String, V>>,
+    after: Option<&HashMap<String, V>>,
+) -> Vec<Change<V>> {
+    let keys = before
+        .into_iter()
+        .chain(after)
+        .flat_map(|map| map.keys())
+        .collect::<BTreeSet<_>>();
+    keys.into_iter()
+        .filter_map(|key| {
+            let old = before.and_then(|map| map.get(key));
+            let new = after.and_then(|map| map.get(key));
+            (old != new).then(|| (key.clone(), old.cloned(), new.cloned()))
+        })
+        .collect()
+}
+
+fn diff_sections<V: Clone + PartialEq>(
+    before: &HashMap<String, HashMap<String, V>>,
+    after: &HashMap<String, HashMap<String, V>>,
+) -> Vec<(String, Vec<Change<V>>)> {
+    let sections = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+    sections
+        .into_iter()
+        .map(|section| {
+            let changes = diff(before.get(section), after.get(section));
+            (section.clone(), changes)
+        })
+        .filter(|(_, changes)| !changes.is_empty())
+        .collect()
+}
+
+fn revert<V: PartialEq>(map: &mut HashMap<String, V>, changes: Vec<Change<V>>) {
+    for (key, old, new) in changes {
+        if map.get(&key) != new.as_ref() {
+            continue;
+        }
+        match old {
+            Some(value) => map.insert(key, value),
+            None => map.remove(&key),
+        };
+    }
+}
+
+fn revert_sections<V: PartialEq>(
+    sections: &mut HashMap<String, HashMap<String, V>>,
+    changes: Vec<(String, Vec<Change<V>>)>,
+) {
+    for (section, changes) in changes {
+        let map = sections.entry(section.clone()).or_default();
+        revert(map, changes);
+        if map.is_empty() {
+            sections.remove(&section);
+        }
+    }
 }
 
 pub fn detect_conflicts(keymap: &KeyMap) -> Vec<ConflictError> {
-    let mut binding_map: HashMap<KeyBinding, Vec<String>> = HashMap::new();
+    let mut binding_map: HashMap<Vec<KeyBinding>, Vec<String>> = HashMap::new();
 
-    // Collect all bindings
+    // Collect all bindings; single keys are sequences of length one
     for (action, binding) in &keymap.global {
         binding_map
-            .entry(binding.clone())
+            .entry(vec![binding.normalized()])
             .or_default()
             .push(action.clone());
     }
@@ -298,18 +868,236 @@ pub fn detect_conflicts(keymap: &KeyMap) -> Vec<ConflictError> {
     for (plugin, bindings) in &keymap.plugins {
         for (action, binding) in bindings {
             binding_map
-                .entry(binding.clone())
+                .entry(vec![binding.normalized()])
                 .or_default()
                 .push(format!("{}.{}", plugin, action));
         }
     }
 
-    // Find conflicts (bindings with multiple actions)
-    binding_map
-        .into_iter()
-        .filter(|(_, actions)| actions.len() > 1)
-        .map(|(binding, actions)| ConflictError { binding, actions })
-        .collect()
+    for (action, sequence) in &keymap.sequences {
+        binding_map
+            .entry(sequence.resolve(&keymap.leader))
+            .or_default()
+            .push(action.clone());
+    }
+
+    let mut conflicts = Vec::new();
+    for (keys, actions) in &binding_map {
+        // Keys bound to multiple actions
+        if actions.len() > 1 {
+            conflicts.push(ConflictError {
+                binding: keys[0].clone(),
+                actions: actions.clone(),
+                kind: ConflictKind::Duplicate,
+                sequence: keys.clone(),
+            });
+        }
+
+        // Keys that begin a longer sequence
+        let mut extended: Vec<String> = binding_map
+            .iter()
+            .filter(|(other, _)| other.len() > keys.len() && other.starts_with(keys))
+            .flat_map(|(_, other_actions)| other_actions.iter().cloned())
+            .collect();
+        if !extended.is_empty() {
+            extended.sort();
+            for action in actions {
+                let mut involved = vec![action.clone()];
+                involved.extend(extended.iter().cloned());
+                conflicts.push(ConflictError {
+                    binding: keys[0].clone(),
+                    actions: involved,
+                    kind: ConflictKind::Prefix,
+                    sequence: keys.clone(),
+                });
+            }
+        }
+    }
+
+    // Mode bindings shadow the plugin sections, so only clash within a mode
+    for bindings in keymap.modes.values() {
+        let mut by_key: HashMap<KeyBinding, Vec<String>> = HashMap::new();
+        for (action, binding) in bindings {
+            by_key
+                .entry(binding.normalized())
+                .or_default()
+                .push(action.clone());
+        }
+        for (binding, actions) in by_key {
+            if actions.len() > 1 {
+                conflicts.push(ConflictError {
+                    sequence: vec![binding.clone()],
+                    binding,
+                    actions,
+                    kind: ConflictKind::Duplicate,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// Result of matching typed keys against the keymap's sequences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceMatch {
+    /// No sequence starts with these keys.
+    None,
+    /// The keys begin one or more sequences.
+    Partial,
+    /// The keys complete a sequence.
+    Complete(String),
+    /// The keys complete a sequence but also begin a longer one.
+    Ambiguous(String),
+}
+
+/// What the dispatcher did with a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceOutcome {
+    /// The key is not part of any sequence; handle it as ordinary input.
+    Unmatched,
+    /// The key continues a sequence; more keys are needed.
+    Pending,
+    /// A sequence completed. `key` is the last key event of the sequence.
+    Matched { action: String, key: KeyEvent },
+    /// Held keys stopped matching any sequence and must be handled as
+    /// ordinary input, in order.
+    Replay(Vec<KeyEvent>),
+}
+
+/// Partial-match state for multi-key sequences.
+///
+/// Keys that begin a sequence are held back until the sequence completes,
+/// stops matching, or the keymap's `sequence_timeout_ms` passes without
+/// another key. [`pending_display`](Self::pending_display) renders the
+/// held keys for a status line.
+#[derive(Debug, Default)]
+pub struct KeySequenceState {
+    pending: Vec<KeyEvent>,
+    last_key_at: Option<Instant>,
+    matched: Option<String>,
+    unhandled_actions: VecDeque<String>,
+    unhandled_keys: VecDeque<KeyEvent>,
+}
+
+impl KeySequenceState {
+    /// Creates an idle dispatcher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a key press into the dispatcher.
+    ///
+    /// On [`SequenceOutcome::Replay`] the state is reset but `key` itself
+    /// has not been consumed; feed it again after replaying the held keys.
+    pub fn feed(&mut self, keymap: &KeyMap, key: &KeyEvent, now: Instant) -> SequenceOutcome {
+        let mut keys: Vec<KeyBinding> = self.pending.iter().map(KeyBinding::from).collect();
+        keys.push(KeyBinding::from(key));
+
+        match keymap.match_sequence(&keys) {
+            SequenceMatch::Complete(action) => {
+                self.clear();
+                SequenceOutcome::Matched { action, key: *key }
+            }
+            SequenceMatch::Partial | SequenceMatch::Ambiguous(_) => {
+                self.pending.push(*key);
+                self.last_key_at = Some(now);
+                SequenceOutcome::Pending
+            }
+            SequenceMatch::None if self.pending.is_empty() => SequenceOutcome::Unmatched,
+            SequenceMatch::None => {
+                self.last_key_at = None;
+                SequenceOutcome::Replay(std::mem::take(&mut self.pending))
+            }
+        }
+    }
+
+    /// Resolves a pending sequence whose timeout has passed.
+    ///
+    /// Held keys that complete a shorter sequence fire it; otherwise they
+    /// are returned for replay. Returns `Pending` while still waiting and
+    /// `Unmatched` when nothing is held.
+    pub fn expire(&mut self, keymap: &KeyMap, now: Instant) -> SequenceOutcome {
+        match self.deadline(keymap) {
+            None => return SequenceOutcome::Unmatched,
+            Some(deadline) if now < deadline => return SequenceOutcome::Pending,
+            Some(_) => {}
+        }
+
+        let keys: Vec<KeyBinding> = self.pending.iter().map(KeyBinding::from).collect();
+        let held = std::mem::take(&mut self.pending);
+        self.last_key_at = None;
+
+        match (keymap.match_sequence(&keys), held.last()) {
+            (SequenceMatch::Ambiguous(action), Some(key)) => {
+                SequenceOutcome::Matched { action, key: *key }
+            }
+            _ => SequenceOutcome::Replay(held),
+        }
+    }
+
+    /// When the pending sequence times out, if one is pending.
+    pub fn deadline(&self, keymap: &KeyMap) -> Option<Instant> {
+        self.last_key_at
+            .filter(|_| !self.pending.is_empty())
+            .map(|at| at + keymap.sequence_timeout())
+    }
+
+    /// Returns the keys typed so far of an unfinished sequence.
+    pub fn pending(&self) -> &[KeyEvent] {
+        &self.pending
+    }
+
+    /// Returns true if a sequence is in progress.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Formats the pending keys for display, e.g. `"C-w"` or `"\ f"`.
+    pub fn pending_display(&self) -> String {
+        self.pending
+            .iter()
+            .map(|key| KeyBinding::from(key).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Drops any pending keys without replaying them.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.last_key_at = None;
+    }
+
+    /// The sequence action being dispatched to plugins, if any.
+    pub fn matched_action(&self) -> Option<&str> {
+        self.matched.as_deref()
+    }
+
+    pub(crate) fn set_matched(&mut self, action: Option<String>) {
+        self.matched = action;
+    }
+
+    pub(crate) fn push_unhandled_action(&mut self, action: String) {
+        self.unhandled_actions.push_back(action);
+    }
+
+    pub(crate) fn push_unhandled_key(&mut self, key: KeyEvent) {
+        self.unhandled_keys.push_back(key);
+    }
+
+    /// Takes the next completed sequence action no plugin handled, such as
+    /// a global action the application should perform.
+    pub fn take_action(&mut self) -> Option<String> {
+        self.unhandled_actions.pop_front()
+    }
+
+    /// Takes the next replayed key no plugin consumed.
+    ///
+    /// Keys held for a sequence that was then abandoned reach the
+    /// application through this queue rather than through the outcome of
+    /// the event that abandoned them.
+    pub fn take_key(&mut self) -> Option<KeyEvent> {
+        self.unhandled_keys.pop_front()
+    }
 }
 
 #[cfg(test)]
@@ -383,6 +1171,303 @@ mod tests {
         assert!(esc_conflict.is_some());
     }
 
+    #[test]
+    fn test_get_plugin_action_scopes_shared_keys() {
+        let keymap = KeyMap::default();
+        let esc = KeyBinding::new(KeyCodeDef::Named("esc".to_string()));
+        assert_eq!(
+            keymap.get_plugin_action("nav", &esc),
+            Some("nav.cancel".to_string())
+        );
+        assert_eq!(
+            keymap.get_plugin_action("omnibar", &esc),
+            Some("omnibar.cancel".to_string())
+        );
+        assert_eq!(keymap.get_plugin_action("highlight", &esc), None);
+    }
+
+    #[test]
+    fn test_binding_from_event_matches_modifier_combo() {
+        let keymap = KeyMap::default();
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert_eq!(
+            keymap.get_action(&KeyBinding::from(&ctrl_p)),
+            Some("omnibar.activate".to_string())
+        );
+
+        let plain_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(
+            keymap.get_plugin_action("omnibar", &KeyBinding::from(&plain_p)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shift_is_ignored_on_char_bindings() {
+        let mut keymap = KeyMap::default();
+        keymap
+            .bind("omnibar.activate", KeyBinding::new(KeyCodeDef::Char('O')))
+            .unwrap();
+
+        let shifted = KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT);
+        assert_eq!(
+            keymap.get_plugin_action("omnibar", &KeyBinding::from(&shifted)),
+            Some("omnibar.activate".to_string())
+        );
+
+        // Shift is still significant on non-character keys
+        let shift_tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::SHIFT);
+        assert_eq!(
+            KeyBinding::from(&shift_tab).get_modifiers(),
+            KeyModifiers::SHIFT
+        );
+    }
+
+    #[test]
+    fn test_shipped_keymap_matches_default() {
+        let shipped: KeyMap = toml::from_str(include_str!("../../keymaps/default.toml")).unwrap();
+        let default = KeyMap::default();
+
+        assert_eq!(shipped.global, default.global);
+        assert_eq!(shipped.plugins, default.plugins);
+        assert_eq!(shipped.modes, default.modes);
+        assert_eq!(shipped.leader, default.leader);
+        assert_eq!(shipped.sequence_timeout_ms, default.sequence_timeout_ms);
+    }
+
+    fn seq(s: &str) -> KeySequence {
+        s.parse().unwrap()
+    }
+
+    fn press(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_parse_sequence_notation() {
+        let sequence = seq("C-w h");
+        assert_eq!(
+            sequence.keys(),
+            &[
+                SequenceKey::Key(KeyBinding::with_modifiers(
+                    KeyCodeDef::Char('w'),
+                    KeyModifiers::CONTROL
+                )),
+                SequenceKey::Key(KeyBinding::new(KeyCodeDef::Char('h'))),
+            ]
+        );
+
+        assert_eq!(seq("<leader> f").keys()[0], SequenceKey::Leader);
+        assert_eq!(seq("<C-w>"), seq("Ctrl-w"));
+        assert_eq!(seq("S-a"), seq("A"));
+        assert_eq!(seq("<F5>").keys(), seq("f5").keys());
+        assert_eq!(
+            seq("<esc>").keys(),
+            &[SequenceKey::Key(KeyBinding::new(KeyCodeDef::Named(
+                "esc".to_string()
+            )))]
+        );
+        assert_eq!(
+            seq("space").keys(),
+            &[SequenceKey::Key(KeyBinding::new(KeyCodeDef::Char(' ')))]
+        );
+
+        assert!("".parse::<KeySequence>().is_err());
+        assert!("C- x".parse::<KeySequence>().is_err());
+        assert!("<bogus>".parse::<KeySequence>().is_err());
+    }
+
+    #[test]
+    fn test_sequence_display_round_trips() {
+        for text in ["g g", "<leader> f", "C-w h", "A-<enter>", "<F5> <space>"] {
+            let sequence = seq(text);
+            assert_eq!(
+                sequence.to_string().parse::<KeySequence>().unwrap(),
+                sequence
+            );
+        }
+        assert_eq!(seq("Ctrl-w h").to_string(), "C-w h");
+    }
+
+    #[test]
+    fn test_sequences_in_toml() {
+        let keymap: KeyMap = toml::from_str(
+            r#"
+            sequence_timeout_ms = 500
+            leader = { key = ' ' }
+
+            [sequences]
+            top = "g g"
+            "nav.activate" = "<leader> f"
+
+            [global]
+            quit = { key = 'q' }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(keymap.sequence_timeout(), Duration::from_millis(500));
+        assert_eq!(keymap.get_sequence("top"), Some(&seq("g g")));
+        assert_eq!(
+            keymap.match_sequence(&[
+                KeyBinding::new(KeyCodeDef::Char(' ')),
+                KeyBinding::new(KeyCodeDef::Char('f')),
+            ]),
+            SequenceMatch::Complete("nav.activate".to_string())
+        );
+
+        let round_trip: KeyMap = toml::from_str(&toml::to_string(&keymap).unwrap()).unwrap();
+        assert_eq!(round_trip.sequences, keymap.sequences);
+        assert_eq!(round_trip.leader, keymap.leader);
+    }
+
+    #[test]
+    fn test_match_sequence() {
+        let mut keymap = KeyMap::default();
+        keymap.bind_sequence("window.next", seq("C-w")).unwrap();
+        keymap.bind_sequence("window.left", seq("C-w h")).unwrap();
+
+        let ctrl_w = KeyBinding::with_modifiers(KeyCodeDef::Char('w'), KeyModifiers::CONTROL);
+        let h = KeyBinding::new(KeyCodeDef::Char('h'));
+
+        assert_eq!(
+            keymap.match_sequence(std::slice::from_ref(&ctrl_w)),
+            SequenceMatch::Ambiguous("window.next".to_string())
+        );
+        assert_eq!(
+            keymap.match_sequence(&[ctrl_w.clone(), h.clone()]),
+            SequenceMatch::Complete("window.left".to_string())
+        );
+        assert_eq!(keymap.match_sequence(&[h]), SequenceMatch::None);
+
+        keymap.unbind("window.next");
+        assert_eq!(keymap.match_sequence(&[ctrl_w]), SequenceMatch::Partial);
+    }
+
+    #[test]
+    fn test_detect_prefix_conflicts() {
+        let mut keymap = KeyMap::default();
+        keymap.bind_sequence("top", seq("g g")).unwrap();
+        keymap.bind_sequence("goto", seq("g")).unwrap();
+        // 'f' is nav.activate, so "f x" is shadowed by it
+        keymap.bind_sequence("find", seq("f x")).unwrap();
+
+        let conflicts = detect_conflicts(&keymap);
+        let prefix = |action: &str| {
+            conflicts
+                .iter()
+                .find(|c| c.kind == ConflictKind::Prefix && c.actions[0] == action)
+        };
+
+        assert_eq!(prefix("goto").unwrap().actions, vec!["goto", "top"]);
+        assert_eq!(
+            prefix("nav.activate").unwrap().actions,
+            vec!["nav.activate", "find"]
+        );
+        assert!(prefix("top").is_none());
+        assert!(prefix("nav.activate")
+            .unwrap()
+            .to_string()
+            .contains("is a prefix of: find"));
+    }
+
+    #[test]
+    fn test_detect_duplicate_sequences() {
+        let mut keymap = KeyMap::default();
+        keymap.bind_sequence("top", seq("<leader> g")).unwrap();
+        keymap.bind_sequence("first", seq("\\ g")).unwrap();
+
+        let conflicts = detect_conflicts(&keymap);
+        let duplicate = conflicts
+            .iter()
+            .find(|c| c.kind == ConflictKind::Duplicate && c.sequence.len() == 2)
+            .unwrap();
+        assert!(duplicate.actions.contains(&"top".to_string()));
+        assert!(duplicate.actions.contains(&"first".to_string()));
+    }
+
+    #[test]
+    fn test_sequence_state_completes_sequence() {
+        let mut keymap = KeyMap::default();
+        keymap.bind_sequence("top", seq("g g")).unwrap();
+        let mut state = KeySequenceState::new();
+        let now = Instant::now();
+
+        assert_eq!(
+            state.feed(&keymap, &press('g'), now),
+            SequenceOutcome::Pending
+        );
+        assert!(state.is_pending());
+        assert_eq!(state.pending_display(), "g");
+        assert_eq!(
+            state.deadline(&keymap),
+            Some(now + Duration::from_millis(1000))
+        );
+
+        assert_eq!(
+            state.feed(&keymap, &press('g'), now),
+            SequenceOutcome::Matched {
+                action: "top".to_string(),
+                key: press('g'),
+            }
+        );
+        assert!(!state.is_pending());
+        assert_eq!(state.deadline(&keymap), None);
+    }
+
+    #[test]
+    fn test_sequence_state_replays_abandoned_keys() {
+        let mut keymap = KeyMap::default();
+        keymap.bind_sequence("top", seq("g g")).unwrap();
+        let mut state = KeySequenceState::new();
+        let now = Instant::now();
+
+        assert_eq!(
+            state.feed(&keymap, &press('x'), now),
+            SequenceOutcome::Unmatched
+        );
+        state.feed(&keymap, &press('g'), now);
+        assert_eq!(
+            state.feed(&keymap, &press('x'), now),
+            SequenceOutcome::Replay(vec![press('g')])
+        );
+        assert!(!state.is_pending());
+    }
+
+    #[test]
+    fn test_sequence_state_expires() {
+        let mut keymap = KeyMap::default();
+        keymap.bind_sequence("window.next", seq("C-w")).unwrap();
+        keymap.bind_sequence("window.left", seq("C-w h")).unwrap();
+        keymap.bind_sequence("top", seq("g g")).unwrap();
+        let mut state = KeySequenceState::new();
+        let now = Instant::now();
+        let later = now + keymap.sequence_timeout();
+        let ctrl_w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+
+        // A complete but ambiguous sequence fires on timeout
+        state.feed(&keymap, &ctrl_w, now);
+        assert_eq!(
+            state.expire(&keymap, now + Duration::from_millis(999)),
+            SequenceOutcome::Pending
+        );
+        assert_eq!(
+            state.expire(&keymap, later),
+            SequenceOutcome::Matched {
+                action: "window.next".to_string(),
+                key: ctrl_w,
+            }
+        );
+
+        // An incomplete one is handed back
+        state.feed(&keymap, &press('g'), now);
+        assert_eq!(
+            state.expire(&keymap, later),
+            SequenceOutcome::Replay(vec![press('g')])
+        );
+        assert_eq!(state.expire(&keymap, later), SequenceOutcome::Unmatched);
+    }
+
     #[test]
     fn test_keycode_def_char() {
         let code = KeyCodeDef::Char('a');
@@ -408,4 +1493,98 @@ mod tests {
         let deserialized: KeyMap = toml::from_str(&toml_str).unwrap();
         assert_eq!(keymap.global.len(), deserialized.global.len());
     }
+
+    #[test]
+    fn test_backtab_ignores_shift() {
+        let shift_tab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        let mut keymap = KeyMap::default();
+        keymap
+            .bind(
+                "focus_prev",
+                KeyBinding::new(KeyCodeDef::Named("backtab".to_string())),
+            )
+            .unwrap();
+        assert!(keymap.is_bound("focus_prev", &KeyBinding::from(&shift_tab)));
+    }
+
+    #[test]
+    fn test_mode_bindings_are_scoped() {
+        let mut keymap = KeyMap::default();
+        let q = KeyBinding::new(KeyCodeDef::Char('q'));
+        keymap
+            .bind_in_mode("hint", "nav.cancel", q.clone())
+            .unwrap();
+
+        assert_eq!(
+            keymap.get_mode_action("hint", "nav", &q),
+            Some("nav.cancel".to_string())
+        );
+        assert_eq!(keymap.get_mode_action("command", "nav", &q), None);
+        assert_eq!(keymap.get_mode_action("hint", "omnibar", &q), None);
+        assert!(keymap.bind_in_mode("hint", "quit", q).is_err());
+    }
+
+    #[test]
+    fn test_mode_binding_conflicts_stay_within_mode() {
+        let mut keymap = KeyMap::default();
+        let x = KeyBinding::new(KeyCodeDef::Char('x'));
+        keymap
+            .bind_in_mode("hint", "nav.cancel", x.clone())
+            .unwrap();
+        keymap
+            .bind_in_mode("tour", "highlight.skip_tour", x.clone())
+            .unwrap();
+        assert!(!detect_conflicts(&keymap).iter().any(|c| c.binding == x));
+
+        keymap.bind_in_mode("hint", "nav.other", x.clone()).unwrap();
+        assert!(detect_conflicts(&keymap)
+            .iter()
+            .any(|c| c.binding == x && c.kind == ConflictKind::Duplicate));
+    }
+
+    #[test]
+    fn test_revert_changes() {
+        let before = KeyMap::default();
+        let mut keymap = before.clone();
+        let g = KeyBinding::new(KeyCodeDef::Char('g'));
+        keymap.bind("nav.activate", g.clone()).unwrap();
+        keymap.bind("panel.close", g.clone()).unwrap();
+        keymap.bind_sequence("panel.top", seq("g g")).unwrap();
+        let changes = keymap.changes_since(&before);
+
+        // Rebound after the changes were recorded, so kept
+        keymap
+            .bind("panel.close", KeyBinding::new(KeyCodeDef::Char('c')))
+            .unwrap();
+        keymap.revert(changes);
+
+        assert_eq!(
+            keymap.get_binding("nav.activate"),
+            Some(&KeyBinding::new(KeyCodeDef::Char('f')))
+        );
+        assert_eq!(
+            keymap.get_binding("panel.close"),
+            Some(&KeyBinding::new(KeyCodeDef::Char('c')))
+        );
+        assert!(keymap.get_sequence("panel.top").is_none());
+    }
+
+    #[test]
+    fn test_unbind_plugin() {
+        let mut keymap = KeyMap::default();
+        let x = KeyBinding::new(KeyCodeDef::Char('x'));
+        keymap
+            .bind_in_mode("hint", "nav.cancel", x.clone())
+            .unwrap();
+        keymap.bind_sequence("nav.top", seq("g g")).unwrap();
+        keymap.bind_sequence("navigate", seq("g n")).unwrap();
+
+        keymap.unbind_plugin("nav");
+
+        assert!(!keymap.plugins.contains_key("nav"));
+        assert!(keymap.plugins.contains_key("omnibar"));
+        assert!(!keymap.modes.contains_key("hint"));
+        assert!(!keymap.sequences.contains_key("nav.top"));
+        assert!(keymap.sequences.contains_key("navigate"));
+    }
 }
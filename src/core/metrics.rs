@@ -0,0 +1,258 @@
+//! Runtime timing of plugin hooks.
+//!
+//! With `ctx.metrics.enabled` set, `Locust` times every plugin's `init`,
+//! `on_event` and overlay rendering and keeps the last
+//! [`window`](MetricsState::window) samples per plugin and hook. Rolling
+//! [`Percentiles`] show which plugin is slow at runtime, where the benches
+//! in `benches/` cannot. The number of targets registered per frame is kept
+//! the same way.
+//!
+//! Timing is off by default and costs nothing while off. Render timings
+//! are recorded when the next frame begins, since `render_overlay` only
+//! borrows `Locust` immutably.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::core::metrics::PluginPhase;
+//! use locust::prelude::*;
+//! use ratatui::backend::TestBackend;
+//!
+//! let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+//! locust.ctx.metrics.enabled = true;
+//! locust.register_plugin(NavPlugin::new());
+//! locust.on_event(&Event::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)));
+//!
+//! let event = locust.ctx.metrics.percentiles("locust.nav", PluginPhase::Event).unwrap();
+//! assert_eq!(event.samples, 1);
+//! assert!(event.p50 <= event.max);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Number of samples kept per plugin and hook by default.
+pub const DEFAULT_WINDOW: usize = 240;
+
+/// A timed plugin hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluginPhase {
+    /// `LocustPlugin::init`
+    Init,
+    /// `LocustPlugin::on_event`
+    Event,
+    /// `LocustPlugin::render_layer`, once per layer
+    Render,
+}
+
+/// Summary of a window of samples, by the nearest-rank method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percentiles {
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    /// Number of samples in the window.
+    pub samples: usize,
+}
+
+/// The most recent samples of one measurement.
+#[derive(Debug, Clone, Default)]
+pub struct RollingSamples {
+    samples: VecDeque<Duration>,
+}
+
+impl RollingSamples {
+    /// Appends a sample, dropping the oldest ones beyond `window`.
+    pub fn push(&mut self, sample: Duration, window: usize) {
+        self.samples.push_back(sample);
+        while self.samples.len() > window.max(1) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Number of samples kept.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns whether no sample has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// The most recent sample.
+    pub fn last(&self) -> Option<Duration> {
+        self.samples.back().copied()
+    }
+
+    /// Percentiles of the kept samples, or `None` if there are none.
+    pub fn percentiles(&self) -> Option<Percentiles> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = |p: f64| {
+            let index = (p * sorted.len() as f64).ceil() as usize;
+            sorted[index.clamp(1, sorted.len()) - 1]
+        };
+
+        Some(Percentiles {
+            p50: rank(0.50),
+            p95: rank(0.95),
+            p99: rank(0.99),
+            max: sorted[sorted.len() - 1],
+            samples: sorted.len(),
+        })
+    }
+}
+
+/// Rolling plugin timings and per-frame counters, kept on `LocustContext`.
+#[derive(Debug)]
+pub struct MetricsState {
+    /// Whether `Locust` times plugin hooks.
+    pub enabled: bool,
+
+    /// Number of samples kept per plugin and hook.
+    pub window: usize,
+
+    timings: HashMap<&'static str, HashMap<PluginPhase, RollingSamples>>,
+    frame_targets: VecDeque<usize>,
+}
+
+impl Default for MetricsState {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: DEFAULT_WINDOW,
+            timings: HashMap::new(),
+            frame_targets: VecDeque::new(),
+        }
+    }
+}
+
+impl MetricsState {
+    /// Creates a disabled metrics state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records how long a plugin hook took.
+    pub fn record(&mut self, plugin_id: &'static str, phase: PluginPhase, elapsed: Duration) {
+        self.timings
+            .entry(plugin_id)
+            .or_default()
+            .entry(phase)
+            .or_default()
+            .push(elapsed, self.window);
+    }
+
+    /// Records the number of targets registered during a frame.
+    pub fn record_frame_targets(&mut self, count: usize) {
+        self.frame_targets.push_back(count);
+        while self.frame_targets.len() > self.window.max(1) {
+            self.frame_targets.pop_front();
+        }
+    }
+
+    /// The samples of one plugin hook.
+    pub fn samples(&self, plugin_id: &str, phase: PluginPhase) -> Option<&RollingSamples> {
+        self.timings.get(plugin_id)?.get(&phase)
+    }
+
+    /// Percentiles of one plugin hook over the window.
+    pub fn percentiles(&self, plugin_id: &str, phase: PluginPhase) -> Option<Percentiles> {
+        self.samples(plugin_id, phase)?.percentiles()
+    }
+
+    /// IDs of the plugins with recorded timings, sorted.
+    pub fn plugin_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.timings.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Number of targets in the last completed frame.
+    pub fn frame_targets(&self) -> Option<usize> {
+        self.frame_targets.back().copied()
+    }
+
+    /// Largest number of targets in a frame over the window.
+    pub fn peak_frame_targets(&self) -> Option<usize> {
+        self.frame_targets.iter().copied().max()
+    }
+
+    /// Drops all samples. `enabled` and `window` are kept.
+    pub fn reset(&mut self) {
+        self.timings.clear();
+        self.frame_targets.clear();
+    }
+}
+
+/// Runs `f`, timing it if `enabled`.
+pub(crate) fn timed<T>(enabled: bool, f: impl FnOnce() -> T) -> (T, Option<Duration>) {
+    if !enabled {
+        return (f(), None);
+    }
+    let start = Instant::now();
+    let result = f();
+    (result, Some(start.elapsed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_percentiles_nearest_rank() {
+        let mut samples = RollingSamples::default();
+        for n in (1..=100).rev() {
+            samples.push(ms(n), 1000);
+        }
+
+        let p = samples.percentiles().unwrap();
+        assert_eq!(p.p50, ms(50));
+        assert_eq!(p.p95, ms(95));
+        assert_eq!(p.p99, ms(99));
+        assert_eq!(p.max, ms(100));
+        assert_eq!(p.samples, 100);
+        assert_eq!(samples.last(), Some(ms(1)));
+    }
+
+    #[test]
+    fn test_window_drops_oldest() {
+        let mut metrics = MetricsState {
+            window: 3,
+            ..MetricsState::default()
+        };
+        for n in [50, 1, 2, 3] {
+            metrics.record("app.slow", PluginPhase::Event, ms(n));
+            metrics.record_frame_targets(n as usize);
+        }
+
+        let p = metrics.percentiles("app.slow", PluginPhase::Event).unwrap();
+        assert_eq!((p.samples, p.max), (3, ms(3)));
+        assert_eq!(metrics.peak_frame_targets(), Some(3));
+        assert!(metrics
+            .percentiles("app.slow", PluginPhase::Render)
+            .is_none());
+    }
+
+    #[test]
+    fn test_plugin_ids_are_sorted_and_unique() {
+        let mut metrics = MetricsState::new();
+        metrics.record("b", PluginPhase::Init, ms(1));
+        metrics.record("a", PluginPhase::Event, ms(1));
+        metrics.record("b", PluginPhase::Render, ms(1));
+        assert_eq!(metrics.plugin_ids(), vec!["a", "b"]);
+
+        metrics.reset();
+        assert!(metrics.plugin_ids().is_empty());
+        assert!(RollingSamples::default().percentiles().is_none());
+    }
+}
@@ -1,15 +1,24 @@
+pub mod actions;
+pub mod clipboard;
 pub mod config;
 pub mod context;
+pub mod focus;
 pub mod fuzzy;
 pub mod input;
 pub mod keybindings;
+pub mod metrics;
+pub mod modes;
 pub mod overlay;
 pub mod plugin;
+pub mod recording;
+pub mod spatial;
 pub mod targets;
 pub mod theme;
 pub mod theme_manager;
 
 pub use context::{Locust, LocustConfig, LocustContext};
-pub use keybindings::{KeyBinding, KeyCodeDef, KeyMap, KeyMapError};
+pub use keybindings::{KeyBinding, KeyCodeDef, KeyMap, KeyMapError, KeySequence};
+pub use modes::{Mode, ModeFrame, ModeStack};
+pub use plugin::PluginOrderError;
 pub use theme::{ColorDef, ColorScheme, StyleDef, StyleScheme, Theme, ThemeError};
 pub use theme_manager::ThemeManager;
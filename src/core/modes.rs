@@ -0,0 +1,276 @@
+//! Modal input-mode stack shared by all plugins.
+//!
+//! Plugins that take over the keyboard (hint mode, the command palette, a
+//! guided tour) push a [`Mode`] when they start and pop it when they finish.
+//! Only the plugin whose mode is on top handles its modal keys, the keymap
+//! can scope bindings to a mode, and the pop binding (Esc by default) always
+//! leaves the top mode.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::core::modes::{Mode, ModeStack};
+//!
+//! let mut modes = ModeStack::default();
+//! assert_eq!(modes.current(), &Mode::Normal);
+//!
+//! modes.push(Mode::Command, "locust.omnibar");
+//! assert!(modes.is_capturing_input());
+//!
+//! modes.pop();
+//! assert_eq!(modes.current(), &Mode::Normal);
+//! ```
+
+use std::fmt;
+
+/// An input mode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// No modal plugin is active; keys go to activations and the app
+    Normal,
+    /// Hint labels are shown and typed characters select targets
+    Hint,
+    /// A command palette is reading text input
+    Command,
+    /// A guided tour is showing
+    Tour,
+    /// An application-defined mode
+    Custom(String),
+}
+
+impl Mode {
+    /// Keymap section name for this mode (`"hint"`, `"command"`, ...).
+    pub fn name(&self) -> &str {
+        match self {
+            Mode::Normal => "normal",
+            Mode::Hint => "hint",
+            Mode::Command => "command",
+            Mode::Tour => "tour",
+            Mode::Custom(name) => name,
+        }
+    }
+
+    /// Whether keys typed in this mode are text rather than shortcuts.
+    ///
+    /// Custom modes default to `false`; use [`ModeFrame::capturing_input`]
+    /// to override.
+    pub fn captures_input(&self) -> bool {
+        matches!(self, Mode::Hint | Mode::Command)
+    }
+}
+
+impl From<&str> for Mode {
+    fn from(name: &str) -> Self {
+        match name {
+            "normal" => Mode::Normal,
+            "hint" => Mode::Hint,
+            "command" => Mode::Command,
+            "tour" => Mode::Tour,
+            other => Mode::Custom(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A mode on the stack together with who pushed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeFrame {
+    /// The mode
+    pub mode: Mode,
+
+    /// ID of the plugin (or app component) that pushed the mode
+    pub owner: String,
+
+    /// Whether keys are text input while this mode is on top
+    pub captures_input: bool,
+
+    /// Whether the pop binding may leave this mode
+    pub escapable: bool,
+}
+
+impl ModeFrame {
+    /// Creates a frame with the mode's default input capture.
+    pub fn new(mode: Mode, owner: impl Into<String>) -> Self {
+        Self {
+            captures_input: mode.captures_input(),
+            mode,
+            owner: owner.into(),
+            escapable: true,
+        }
+    }
+
+    /// Sets whether keys are text input while this frame is on top.
+    pub fn capturing_input(mut self, captures_input: bool) -> Self {
+        self.captures_input = captures_input;
+        self
+    }
+
+    /// Sets whether the pop binding may leave this frame.
+    pub fn escapable(mut self, escapable: bool) -> Self {
+        self.escapable = escapable;
+        self
+    }
+}
+
+/// Stack of active input modes.
+///
+/// An empty stack means [`Mode::Normal`].
+#[derive(Debug, Clone, Default)]
+pub struct ModeStack {
+    frames: Vec<ModeFrame>,
+}
+
+impl ModeStack {
+    /// Pushes `mode` on behalf of `owner`.
+    pub fn push(&mut self, mode: Mode, owner: impl Into<String>) {
+        self.push_frame(ModeFrame::new(mode, owner));
+    }
+
+    /// Pushes a configured frame.
+    pub fn push_frame(&mut self, frame: ModeFrame) {
+        log::debug!("Locust: Entering {} mode ({})", frame.mode, frame.owner);
+        self.frames.push(frame);
+    }
+
+    /// Pops the top frame.
+    pub fn pop(&mut self) -> Option<ModeFrame> {
+        let frame = self.frames.pop()?;
+        log::debug!("Locust: Leaving {} mode ({})", frame.mode, frame.owner);
+        Some(frame)
+    }
+
+    /// Removes the topmost frame `owner` pushed for `mode`, wherever it is.
+    ///
+    /// Plugins call this when they leave their mode on their own. Returns
+    /// `false` if no such frame was on the stack (e.g. it was already popped).
+    pub fn remove(&mut self, mode: &Mode, owner: &str) -> bool {
+        let Some(index) = self
+            .frames
+            .iter()
+            .rposition(|f| &f.mode == mode && f.owner == owner)
+        else {
+            return false;
+        };
+        let frame = self.frames.remove(index);
+        log::debug!("Locust: Leaving {} mode ({})", frame.mode, frame.owner);
+        true
+    }
+
+    /// Removes every frame `owner` pushed, returning them top first.
+    pub fn remove_owned_by(&mut self, owner: &str) -> Vec<ModeFrame> {
+        let mut removed = Vec::new();
+        while let Some(index) = self.frames.iter().rposition(|f| f.owner == owner) {
+            let frame = self.frames.remove(index);
+            log::debug!("Locust: Leaving {} mode ({})", frame.mode, frame.owner);
+            removed.push(frame);
+        }
+        removed
+    }
+
+    /// The top frame, or `None` in normal mode.
+    pub fn top(&self) -> Option<&ModeFrame> {
+        self.frames.last()
+    }
+
+    /// The current mode.
+    pub fn current(&self) -> &Mode {
+        self.top().map_or(&Mode::Normal, |f| &f.mode)
+    }
+
+    /// Whether `mode` is the current mode.
+    pub fn is_current(&self, mode: &Mode) -> bool {
+        self.current() == mode
+    }
+
+    /// Whether `mode` is anywhere on the stack.
+    pub fn contains(&self, mode: &Mode) -> bool {
+        self.frames.iter().any(|f| &f.mode == mode)
+    }
+
+    /// Whether the top mode treats keys as text input.
+    ///
+    /// Host applications should not interpret keys as their own shortcuts
+    /// while this is `true`.
+    pub fn is_capturing_input(&self) -> bool {
+        self.top().is_some_and(|f| f.captures_input)
+    }
+
+    /// Number of modes above normal.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Frames from bottom to top.
+    pub fn iter(&self) -> impl Iterator<Item = &ModeFrame> {
+        self.frames.iter()
+    }
+
+    /// Returns to normal mode.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_stack_is_normal() {
+        let modes = ModeStack::default();
+        assert_eq!(modes.current(), &Mode::Normal);
+        assert!(modes.top().is_none());
+        assert!(!modes.is_capturing_input());
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut modes = ModeStack::default();
+        modes.push(Mode::Tour, "locust.highlight");
+        modes.push(Mode::Hint, "locust.nav");
+
+        assert_eq!(modes.current(), &Mode::Hint);
+        assert!(modes.contains(&Mode::Tour));
+        assert!(modes.is_capturing_input());
+
+        let frame = modes.pop().unwrap();
+        assert_eq!(frame.owner, "locust.nav");
+        assert_eq!(modes.current(), &Mode::Tour);
+        assert!(!modes.is_capturing_input());
+    }
+
+    #[test]
+    fn test_remove_below_top() {
+        let mut modes = ModeStack::default();
+        modes.push(Mode::Hint, "locust.nav");
+        modes.push(Mode::Command, "locust.omnibar");
+
+        assert!(modes.remove(&Mode::Hint, "locust.nav"));
+        assert!(!modes.remove(&Mode::Hint, "locust.nav"));
+        assert_eq!(modes.current(), &Mode::Command);
+        assert_eq!(modes.depth(), 1);
+    }
+
+    #[test]
+    fn test_custom_mode_capture() {
+        let mut modes = ModeStack::default();
+        modes.push(Mode::from("visual"), "app");
+        assert_eq!(modes.current(), &Mode::Custom("visual".into()));
+        assert!(!modes.is_capturing_input());
+
+        modes.push_frame(ModeFrame::new(Mode::from("insert"), "app").capturing_input(true));
+        assert!(modes.is_capturing_input());
+    }
+
+    #[test]
+    fn test_mode_names_round_trip() {
+        for mode in [Mode::Normal, Mode::Hint, Mode::Command, Mode::Tour] {
+            assert_eq!(Mode::from(mode.name()), mode);
+        }
+    }
+}
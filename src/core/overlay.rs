@@ -1,3 +1,5 @@
+use ratatui::layout::{Position, Rect};
+
 /// State management for overlay rendering.
 ///
 /// Tracks which overlays are active, their z-order, and per-frame
@@ -12,29 +14,50 @@
 /// - 100-199: Normal overlays (default)
 /// - 200-299: Modal dialogs
 /// - 300+: Critical notifications
+///
+/// # Compositing
+///
+/// `Locust::render_overlay` draws the visible layers bottom to top. A
+/// plugin may own several named layers (e.g. a tooltip and a modal), and
+/// plugins without any registered layer get an implicit default layer at
+/// [`DEFAULT_Z_INDEX`]. Each layer is drawn with an [`OcclusionMask`] of the
+/// cells that layers above it cover, so it can skip content that would be
+/// hidden anyway.
 #[derive(Debug, Default)]
 pub struct OverlayState {
     /// Whether any plugin has requested overlay rendering this frame.
     pub has_overlay: bool,
 
-    /// Active overlay layers by plugin ID.
+    /// Active overlay layers, sorted by z-index.
     layers: Vec<OverlayLayer>,
 
     /// Total number of frames where overlays were active.
     pub total_overlay_frames: u64,
 }
 
+/// Name of the layer created by [`OverlayLayer::new`].
+pub const DEFAULT_LAYER: &str = "default";
+
+/// Z-index of plugins that have not registered a layer.
+pub const DEFAULT_Z_INDEX: i32 = 100;
+
 /// Represents a single overlay layer from a plugin.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OverlayLayer {
     /// Plugin ID that owns this layer.
     pub plugin_id: String,
 
+    /// Layer name, unique per plugin.
+    pub name: String,
+
     /// Z-index for rendering order (lower = bottom, higher = top).
     pub z_index: i32,
 
     /// Whether this layer is currently visible.
     pub visible: bool,
+
+    /// Area this layer covers opaquely, hidden from the layers below.
+    pub occludes: Option<Rect>,
 }
 
 impl OverlayLayer {
@@ -42,10 +65,94 @@ impl OverlayLayer {
     pub fn new(plugin_id: impl Into<String>, z_index: i32) -> Self {
         Self {
             plugin_id: plugin_id.into(),
+            name: DEFAULT_LAYER.to_string(),
             z_index,
             visible: true,
+            occludes: None,
+        }
+    }
+
+    /// Create a named overlay layer, for plugins that draw several.
+    pub fn named(plugin_id: impl Into<String>, name: impl Into<String>, z_index: i32) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::new(plugin_id, z_index)
         }
     }
+
+    /// Set the area this layer covers.
+    pub fn with_occlusion(mut self, area: Rect) -> Self {
+        self.occludes = Some(area);
+        self
+    }
+
+    /// Returns whether this is the plugin's default layer.
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT_LAYER
+    }
+
+    fn is(&self, plugin_id: &str, name: &str) -> bool {
+        self.plugin_id == plugin_id && self.name == name
+    }
+}
+
+/// Cells covered by the overlay layers above the one being drawn.
+///
+/// # Example
+///
+/// ```rust
+/// use locust::core::overlay::OcclusionMask;
+/// use ratatui::layout::Rect;
+///
+/// let mut mask = OcclusionMask::new();
+/// mask.add(Rect::new(10, 5, 20, 10));
+///
+/// assert!(mask.contains(15, 8));
+/// assert!(mask.is_occluded(Rect::new(25, 14, 10, 1)));
+/// assert!(!mask.is_occluded(Rect::new(0, 0, 10, 1)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OcclusionMask {
+    rects: Vec<Rect>,
+}
+
+impl OcclusionMask {
+    /// Create an empty mask.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark `area` as covered.
+    pub fn add(&mut self, area: Rect) {
+        if !area.is_empty() {
+            self.rects.push(area);
+        }
+    }
+
+    /// Returns whether nothing is covered.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// The covered areas.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Returns whether the cell at `(x, y)` is covered.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        self.rects.iter().any(|r| r.contains(Position::new(x, y)))
+    }
+
+    /// Returns whether any cell of `area` is covered.
+    pub fn is_occluded(&self, area: Rect) -> bool {
+        self.rects.iter().any(|r| r.intersects(area))
+    }
+
+    /// Returns whether every cell of `area` is covered.
+    pub fn covers(&self, area: Rect) -> bool {
+        area.positions().all(|p| self.contains(p.x, p.y))
+    }
 }
 
 impl OverlayState {
@@ -68,32 +175,58 @@ impl OverlayState {
     }
 
     /// Register an overlay layer for a plugin.
+    ///
+    /// Replaces the plugin's existing layer with the same name.
     pub fn add_layer(&mut self, layer: OverlayLayer) {
-        // Remove existing layer from same plugin if present
-        self.layers.retain(|l| l.plugin_id != layer.plugin_id);
+        self.layers.retain(|l| !l.is(&layer.plugin_id, &layer.name));
         self.layers.push(layer);
-        // Keep sorted by z-index
+        // Keep sorted by z-index; the sort is stable so ties keep insertion order
         self.layers.sort_by_key(|l| l.z_index);
     }
 
-    /// Remove an overlay layer by plugin ID.
+    /// Remove all overlay layers of a plugin.
     pub fn remove_layer(&mut self, plugin_id: &str) {
         self.layers.retain(|l| l.plugin_id != plugin_id);
     }
 
+    /// Remove one named overlay layer of a plugin.
+    pub fn remove_named_layer(&mut self, plugin_id: &str, name: &str) {
+        self.layers.retain(|l| !l.is(plugin_id, name));
+    }
+
+    /// Get a plugin's named layer.
+    pub fn layer(&self, plugin_id: &str, name: &str) -> Option<&OverlayLayer> {
+        self.layers.iter().find(|l| l.is(plugin_id, name))
+    }
+
+    /// Get a plugin's named layer mutably, e.g. to update its occlusion.
+    ///
+    /// Changing `z_index` through this reference does not re-sort the
+    /// layers; use [`add_layer`](Self::add_layer) for that.
+    pub fn layer_mut(&mut self, plugin_id: &str, name: &str) -> Option<&mut OverlayLayer> {
+        self.layers.iter_mut().find(|l| l.is(plugin_id, name))
+    }
+
     /// Get all active overlay layers in render order.
     pub fn layers(&self) -> &[OverlayLayer] {
         &self.layers
     }
 
-    /// Set visibility for a specific plugin's overlay.
+    /// Set visibility for all of a plugin's layers.
     pub fn set_layer_visibility(&mut self, plugin_id: &str, visible: bool) {
-        if let Some(layer) = self.layers.iter_mut().find(|l| l.plugin_id == plugin_id) {
+        for layer in self.layers.iter_mut().filter(|l| l.plugin_id == plugin_id) {
             layer.visible = visible;
         }
     }
 
-    /// Check if a plugin has an active overlay layer.
+    /// Set visibility for one named layer of a plugin.
+    pub fn set_named_layer_visibility(&mut self, plugin_id: &str, name: &str, visible: bool) {
+        if let Some(layer) = self.layer_mut(plugin_id, name) {
+            layer.visible = visible;
+        }
+    }
+
+    /// Check if a plugin has a visible overlay layer.
     pub fn has_layer(&self, plugin_id: &str) -> bool {
         self.layers
             .iter()
@@ -104,4 +237,116 @@ impl OverlayState {
     pub fn clear_layers(&mut self) {
         self.layers.clear();
     }
+
+    /// Visible layers in draw order, each with the mask of the layers above.
+    ///
+    /// `plugin_ids` are the registered plugins in priority order. Plugins
+    /// without a registered layer get a default layer at [`DEFAULT_Z_INDEX`],
+    /// and layers of plugins not in `plugin_ids` are left out.
+    pub fn render_plan(&self, plugin_ids: &[&str]) -> Vec<(OverlayLayer, OcclusionMask)> {
+        let mut plan: Vec<OverlayLayer> = plugin_ids
+            .iter()
+            .filter(|id| !self.layers.iter().any(|l| l.plugin_id == **id))
+            .map(|id| OverlayLayer::new(*id, DEFAULT_Z_INDEX))
+            .chain(
+                self.layers
+                    .iter()
+                    .filter(|l| plugin_ids.contains(&l.plugin_id.as_str()))
+                    .cloned(),
+            )
+            .filter(|l| l.visible)
+            .collect();
+        plan.sort_by_key(|l| l.z_index);
+
+        // Walk top-down so each layer sees what is drawn over it
+        let mut above = OcclusionMask::new();
+        let mut masked: Vec<(OverlayLayer, OcclusionMask)> = plan
+            .into_iter()
+            .rev()
+            .map(|layer| {
+                let mask = above.clone();
+                if let Some(area) = layer.occludes {
+                    above.add(area);
+                }
+                (layer, mask)
+            })
+            .collect();
+        masked.reverse();
+        masked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_layers_per_plugin() {
+        let mut state = OverlayState::new();
+        state.add_layer(OverlayLayer::named("app", "tooltip", 100));
+        state.add_layer(OverlayLayer::named("app", "modal", 200));
+        state.add_layer(OverlayLayer::named("app", "tooltip", 150));
+
+        let names: Vec<_> = state.layers().iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, vec!["tooltip", "modal"]);
+        assert_eq!(state.layer("app", "tooltip").unwrap().z_index, 150);
+
+        state.set_named_layer_visibility("app", "modal", false);
+        assert!(state.has_layer("app"));
+        state.remove_named_layer("app", "tooltip");
+        assert!(!state.has_layer("app"));
+    }
+
+    #[test]
+    fn test_render_plan_orders_and_skips_hidden() {
+        let mut state = OverlayState::new();
+        state.add_layer(OverlayLayer::named("b", "modal", 250));
+        state.add_layer(OverlayLayer::named("b", "hidden", 300));
+        state.set_named_layer_visibility("b", "hidden", false);
+        state.add_layer(OverlayLayer::new("c", 10));
+        state.add_layer(OverlayLayer::new("gone", 20));
+
+        let plan: Vec<_> = state
+            .render_plan(&["a", "b", "c"])
+            .into_iter()
+            .map(|(l, _)| (l.plugin_id, l.name))
+            .collect();
+        assert_eq!(
+            plan,
+            vec![
+                ("c".to_string(), DEFAULT_LAYER.to_string()),
+                ("a".to_string(), DEFAULT_LAYER.to_string()),
+                ("b".to_string(), "modal".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_plan_masks_lower_layers() {
+        let modal = Rect::new(10, 5, 20, 10);
+        let mut state = OverlayState::new();
+        state.add_layer(OverlayLayer::named("app", "modal", 200).with_occlusion(modal));
+        state.add_layer(
+            OverlayLayer::named("app", "toast", 300).with_occlusion(Rect::new(0, 0, 5, 1)),
+        );
+
+        let plan = state.render_plan(&["app", "nav"]);
+        let masks: Vec<&[Rect]> = plan.iter().map(|(_, m)| m.rects()).collect();
+        // nav (default z), modal, toast
+        assert_eq!(masks[0].len(), 2);
+        assert_eq!(masks[1], &[Rect::new(0, 0, 5, 1)]);
+        assert!(masks[2].is_empty());
+    }
+
+    #[test]
+    fn test_mask_covers() {
+        let mut mask = OcclusionMask::new();
+        mask.add(Rect::new(0, 0, 5, 1));
+        mask.add(Rect::new(5, 0, 5, 1));
+        mask.add(Rect::new(0, 0, 0, 0));
+
+        assert_eq!(mask.rects().len(), 2);
+        assert!(mask.covers(Rect::new(3, 0, 4, 1)));
+        assert!(!mask.covers(Rect::new(3, 0, 4, 2)));
+    }
 }
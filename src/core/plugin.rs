@@ -1,9 +1,12 @@
 use crate::core::context::LocustContext;
 use crate::core::input::PluginEventResult;
+use crate::core::modes::Mode;
+use crate::core::overlay::{OcclusionMask, OverlayLayer};
 use crossterm::event::Event;
 use ratatui::backend::Backend;
 use ratatui::Frame;
 use std::any::Any;
+use std::time::Instant;
 
 /// Trait implemented by all Locust plugins.
 ///
@@ -17,7 +20,8 @@ use std::any::Any;
 ///
 /// 1. **Construction**: Plugin is created by user code
 /// 2. **Initialization**: `init()` is called when registered with Locust
-/// 3. **Runtime**: `on_event()` and `render_overlay()` called each frame
+/// 3. **Runtime**: `on_event()` and `render_overlay()` called each frame,
+///    `on_tick()` called whenever the host advances time
 /// 4. **Cleanup**: `cleanup()` called when plugin is unregistered or Locust is dropped
 ///
 /// # Plugin Priority
@@ -92,6 +96,25 @@ where
         100
     }
 
+    /// IDs of plugins this one requires.
+    ///
+    /// Dependencies must be registered first; they are initialized and
+    /// see events before this plugin. Registering a plugin whose dependency
+    /// is missing fails with [`PluginOrderError::MissingDependency`].
+    fn depends_on(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// IDs of plugins this one must see events before, if registered.
+    fn before(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// IDs of plugins this one must see events after, if registered.
+    fn after(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// Called once when the plugin is registered with Locust.
     ///
     /// Use this to:
@@ -129,6 +152,71 @@ where
     /// * `ctx` - Read-only context with target registry and overlay state
     fn render_overlay(&self, frame: &mut Frame, ctx: &LocustContext);
 
+    /// Render one of this plugin's overlay layers.
+    ///
+    /// `Locust::render_overlay` calls this once per visible layer, bottom
+    /// to top by z-index. Plugins that never register a layer are drawn
+    /// through an implicit default layer. Override this when the plugin
+    /// registers several named layers, or to skip content hidden by the
+    /// layers above.
+    ///
+    /// # Arguments
+    /// * `frame` - The ratatui Frame to render into
+    /// * `layer` - The layer being drawn
+    /// * `mask` - Cells covered by the visible layers drawn after this one
+    /// * `ctx` - Read-only context with target registry and overlay state
+    ///
+    /// # Default Implementation
+    /// Calls `render_overlay` for the default layer and draws nothing for
+    /// named layers.
+    fn render_layer(
+        &self,
+        frame: &mut Frame,
+        layer: &OverlayLayer,
+        _mask: &OcclusionMask,
+        ctx: &LocustContext,
+    ) {
+        if layer.is_default() {
+            self.render_overlay(frame, ctx);
+        }
+    }
+
+    /// Advance time-based state such as delays, timeouts and animations.
+    ///
+    /// Called by `Locust::tick` independently of input events, so timers
+    /// fire while the user is idle. Plugins should compare against `now`
+    /// instead of reading the clock so behaviour is deterministic in tests.
+    ///
+    /// # Arguments
+    /// * `now` - The current time as seen by the host's event loop
+    /// * `ctx` - Mutable context for updating state
+    ///
+    /// # Returns
+    /// `true` if the plugin's overlay changed and the UI should be redrawn.
+    fn on_tick(&mut self, _now: Instant, _ctx: &mut LocustContext) -> bool {
+        false
+    }
+
+    /// Earliest instant at which `on_tick` has work to do.
+    ///
+    /// Returns `None` if the plugin has no pending timers. The host uses
+    /// this to bound how long it waits for input.
+    fn next_deadline(&self, _ctx: &LocustContext) -> Option<Instant> {
+        None
+    }
+
+    /// Called when Locust pops a mode this plugin pushed.
+    ///
+    /// This happens when the user presses the `pop_mode` binding (Esc by
+    /// default) or the host calls `Locust::pop_mode`. The frame is already
+    /// off `ctx.modes`; plugins should reset whatever state the mode stood
+    /// for, e.g. hide hints or close a prompt.
+    ///
+    /// # Arguments
+    /// * `mode` - The mode that was popped
+    /// * `ctx` - Mutable context for updating state
+    fn on_mode_exit(&mut self, _mode: &Mode, _ctx: &mut LocustContext) {}
+
     /// Called when the plugin is being unregistered or Locust is shutting down.
     ///
     /// Use this to:
@@ -151,4 +239,211 @@ where
     /// # Default Implementation
     /// Does nothing. Override to implement configuration hot-reload support.
     fn reload_config(&mut self, _ctx: &LocustContext) {}
-}
\ No newline at end of file
+}
+
+/// Error resolving the order of registered plugins.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PluginOrderError {
+    #[error("Plugin {plugin} depends on {dependency}, which is not registered")]
+    MissingDependency { plugin: String, dependency: String },
+    #[error("Plugin ordering cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("Plugin {dependent} depends on {plugin}, which cannot be removed")]
+    Required { plugin: String, dependent: String },
+}
+
+/// Ordering declarations of one registered plugin.
+pub(crate) struct OrderNode {
+    id: &'static str,
+    priority: i32,
+    depends_on: &'static [&'static str],
+    before: &'static [&'static str],
+    after: &'static [&'static str],
+}
+
+impl OrderNode {
+    pub(crate) fn of<B: Backend + 'static>(plugin: &dyn LocustPlugin<B>) -> Self {
+        Self {
+            id: plugin.id(),
+            priority: plugin.priority(),
+            depends_on: plugin.depends_on(),
+            before: plugin.before(),
+            after: plugin.after(),
+        }
+    }
+}
+
+/// Resolves the dispatch order of `nodes`, returned as indices into it.
+///
+/// `depends_on`, `before` and `after` declarations are hard constraints.
+/// Among plugins free to go next, the lowest priority wins, then the lower
+/// index, so the order is deterministic and matches a plain priority sort
+/// when nothing is declared.
+pub(crate) fn resolve_order(nodes: &[OrderNode]) -> Result<Vec<usize>, PluginOrderError> {
+    let indices_of = |id: &'static str| {
+        nodes
+            .iter()
+            .enumerate()
+            .filter(move |(_, n)| n.id == id)
+            .map(|(i, _)| i)
+    };
+
+    // successors[i] must come after i
+    let mut successors = vec![Vec::new(); nodes.len()];
+    for (i, node) in nodes.iter().enumerate() {
+        for dependency in node.depends_on {
+            if indices_of(dependency).next().is_none() {
+                return Err(PluginOrderError::MissingDependency {
+                    plugin: node.id.to_string(),
+                    dependency: dependency.to_string(),
+                });
+            }
+        }
+        for id in node.depends_on.iter().chain(node.after) {
+            for j in indices_of(id).filter(|&j| j != i) {
+                successors[j].push(i);
+            }
+        }
+        for id in node.before {
+            successors[i].extend(indices_of(id).filter(|&j| j != i));
+        }
+    }
+
+    let mut in_degree = vec![0usize; nodes.len()];
+    for &j in successors.iter().flatten() {
+        in_degree[j] += 1;
+    }
+
+    let mut order = Vec::with_capacity(nodes.len());
+    let mut placed = vec![false; nodes.len()];
+    while let Some(next) = (0..nodes.len())
+        .filter(|&i| !placed[i] && in_degree[i] == 0)
+        .min_by_key(|&i| (nodes[i].priority, i))
+    {
+        placed[next] = true;
+        order.push(next);
+        for &j in &successors[next] {
+            in_degree[j] -= 1;
+        }
+    }
+
+    if order.len() < nodes.len() {
+        return Err(PluginOrderError::Cycle(find_cycle(
+            nodes,
+            &successors,
+            &placed,
+        )));
+    }
+    Ok(order)
+}
+
+/// IDs along one cycle among the plugins that could not be placed.
+fn find_cycle(nodes: &[OrderNode], successors: &[Vec<usize>], placed: &[bool]) -> Vec<String> {
+    // Every unplaced node has an unplaced predecessor, so walking
+    // predecessors must revisit a node
+    let predecessor =
+        |i: usize| (0..nodes.len()).find(|&p| !placed[p] && successors[p].contains(&i));
+    let mut path = Vec::new();
+    let mut current = placed.iter().position(|&p| !p);
+    while let Some(i) = current {
+        if let Some(start) = path.iter().position(|&p| p == i) {
+            // path runs against the edges; reverse it and close the loop
+            let mut cycle: Vec<String> = path[start..]
+                .iter()
+                .rev()
+                .map(|&p: &usize| nodes[p].id.to_string())
+                .collect();
+            cycle.push(cycle[0].clone());
+            return cycle;
+        }
+        path.push(i);
+        current = predecessor(i);
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &'static str, priority: i32) -> OrderNode {
+        OrderNode {
+            id,
+            priority,
+            depends_on: &[],
+            before: &[],
+            after: &[],
+        }
+    }
+
+    fn ids(nodes: &[OrderNode]) -> Vec<&'static str> {
+        resolve_order(nodes)
+            .unwrap()
+            .into_iter()
+            .map(|i| nodes[i].id)
+            .collect()
+    }
+
+    #[test]
+    fn test_priority_and_index_break_ties() {
+        let nodes = [node("c", 100), node("a", 50), node("b", 100)];
+        assert_eq!(ids(&nodes), vec!["a", "c", "b"]);
+    }
+
+    #[test]
+    fn test_declarations_override_priority() {
+        let nodes = [
+            node("locust.nav", 50),
+            OrderNode {
+                before: &["locust.nav", "absent"],
+                ..node("app.keys", 200)
+            },
+            OrderNode {
+                depends_on: &["app.keys"],
+                ..node("app.status", 10)
+            },
+        ];
+        assert_eq!(ids(&nodes), vec!["app.keys", "app.status", "locust.nav"]);
+    }
+
+    #[test]
+    fn test_missing_dependency() {
+        let nodes = [OrderNode {
+            depends_on: &["absent"],
+            ..node("a", 100)
+        }];
+        assert_eq!(
+            resolve_order(&nodes),
+            Err(PluginOrderError::MissingDependency {
+                plugin: "a".into(),
+                dependency: "absent".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let nodes = [
+            node("free", 0),
+            OrderNode {
+                after: &["c"],
+                ..node("a", 100)
+            },
+            OrderNode {
+                after: &["a"],
+                ..node("b", 100)
+            },
+            OrderNode {
+                before: &["a"],
+                after: &["b"],
+                ..node("c", 100)
+            },
+        ];
+        let Err(PluginOrderError::Cycle(cycle)) = resolve_order(&nodes) else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!(cycle.len(), 4);
+        assert!(!cycle.contains(&"free".to_string()));
+    }
+}
@@ -0,0 +1,342 @@
+//! Recording and deterministic replay of the events Locust handles.
+//!
+//! An [`EventRecorder`] attached with `Locust::start_recording` writes one
+//! JSON line per event passed to `Locust::on_event` and per call to
+//! `Locust::tick`: the time since the first line, the frame number, the
+//! event itself, the outcome and the ID of the plugin that consumed it. A
+//! [`Recording`] loaded from such a file can be replayed into a fresh
+//! `Locust`, failing on the first event whose outcome differs. This turns a
+//! user's bug report into a regression test.
+//!
+//! Replay feeds every event and tick in at its recorded time, so timers
+//! such as key sequence timeouts fire exactly as they did.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::core::recording::{EventRecorder, Recording};
+//! use locust::prelude::*;
+//! use ratatui::backend::TestBackend;
+//! use ratatui::layout::Rect;
+//!
+//! fn draw(locust: &mut Locust<TestBackend>) {
+//!     locust.ctx.targets.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+//! }
+//!
+//! let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+//! locust.register_plugin(NavPlugin::new());
+//! locust.start_recording(EventRecorder::in_memory());
+//!
+//! locust.begin_frame();
+//! draw(&mut locust);
+//! locust.on_event(&Event::Key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE)));
+//!
+//! let recording = locust.stop_recording().unwrap().into_recording().unwrap();
+//! assert_eq!(recording.events[0].consumed_by.as_deref(), Some("locust.nav"));
+//!
+//! let mut fresh = Locust::<TestBackend>::new(LocustConfig::default());
+//! fresh.register_plugin(NavPlugin::new());
+//! recording.replay(&mut fresh, draw).unwrap();
+//! ```
+
+use crate::core::context::Locust;
+use crate::core::input::LocustEventOutcome;
+use crossterm::event::Event;
+use ratatui::backend::Backend;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Errors from recording, loading or replaying events.
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid recording at line {line}: {source}")]
+    Parse {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Failed to write recording: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Replay diverged at event {index}: expected {expected:?}, got {actual:?}")]
+    Mismatch {
+        index: usize,
+        expected: Box<RecordedEvent>,
+        actual: Box<RecordedEvent>,
+    },
+}
+
+/// One event handled by `Locust::on_event`, or one call to `Locust::tick`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Time since the first recorded entry.
+    #[serde(default)]
+    pub at: Duration,
+
+    /// `ctx.frame_count` when the event arrived.
+    pub frame: u64,
+
+    /// The input event, or `None` for a tick.
+    pub event: Option<Event>,
+
+    /// What `on_event` returned. For a tick, `request_redraw` is what
+    /// `tick` returned.
+    pub outcome: LocustEventOutcome,
+
+    /// ID of the plugin that consumed the event. `None` if no plugin did,
+    /// including events Locust consumed itself (focus keys, mode pops and
+    /// pending key sequences).
+    pub consumed_by: Option<String>,
+}
+
+/// Writes recorded events as JSON lines, or keeps them in memory.
+///
+/// A recorder streaming to a writer holds no events, so long sessions do
+/// not grow memory; use [`with_events_kept`](Self::with_events_kept) to
+/// keep them as well.
+pub struct EventRecorder {
+    writer: Option<Box<dyn Write>>,
+    kept: Option<Vec<RecordedEvent>>,
+    count: usize,
+    /// Instant of the first entry, which recorded times count from.
+    origin: Option<Instant>,
+}
+
+impl EventRecorder {
+    /// Records into `writer`.
+    pub fn new(writer: impl Write + 'static) -> Self {
+        Self {
+            writer: Some(Box::new(writer)),
+            kept: None,
+            count: 0,
+            origin: None,
+        }
+    }
+
+    /// Records into a new file at `path`, replacing any existing file.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+
+    /// Keeps events in memory only, for [`into_recording`](Self::into_recording).
+    pub fn in_memory() -> Self {
+        Self {
+            writer: None,
+            kept: Some(Vec::new()),
+            count: 0,
+            origin: None,
+        }
+    }
+
+    /// Also keeps the written events in memory.
+    pub fn with_events_kept(mut self) -> Self {
+        self.kept.get_or_insert_with(Vec::new);
+        self
+    }
+
+    /// Appends one event and flushes, so the file is complete even if the
+    /// application crashes afterwards.
+    pub fn record(&mut self, entry: RecordedEvent) -> Result<(), RecordingError> {
+        if let Some(writer) = self.writer.as_mut() {
+            serde_json::to_writer(&mut *writer, &entry).map_err(RecordingError::Serialize)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+        if let Some(kept) = self.kept.as_mut() {
+            kept.push(entry);
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Time of an entry at `now`, counted from the first entry.
+    pub(crate) fn elapsed(&mut self, now: Instant) -> Duration {
+        now.saturating_duration_since(*self.origin.get_or_insert(now))
+    }
+
+    /// Number of events recorded so far.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns whether nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The events recorded so far, without reading the output back.
+    ///
+    /// `None` unless the recorder keeps events (see
+    /// [`in_memory`](Self::in_memory)); load a streamed recording with
+    /// [`Recording::load`] instead.
+    pub fn into_recording(self) -> Option<Recording> {
+        self.kept.map(|events| Recording { events })
+    }
+}
+
+impl std::fmt::Debug for EventRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventRecorder")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A sequence of recorded events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    pub events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    /// Loads a JSON-lines recording from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Parses a JSON-lines recording. Blank lines are skipped.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, RecordingError> {
+        let mut events = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line).map_err(|source| RecordingError::Parse {
+                line: index + 1,
+                source,
+            })?;
+            events.push(entry);
+        }
+        Ok(Self { events })
+    }
+
+    /// Feeds the recording into `locust` and checks every outcome.
+    ///
+    /// Before each event, frames are advanced to the recorded frame number:
+    /// for every frame, `begin_frame` is called and then `draw`, which
+    /// should register the targets the application had on screen. Plugins
+    /// must be registered the same way as when recording.
+    ///
+    /// Events go through `on_event_at` and ticks through `tick`, at the
+    /// recorded time after the start of the replay.
+    ///
+    /// Returns [`RecordingError::Mismatch`] for the first event whose
+    /// outcome or consuming plugin differs.
+    pub fn replay<B>(
+        &self,
+        locust: &mut Locust<B>,
+        mut draw: impl FnMut(&mut Locust<B>),
+    ) -> Result<(), RecordingError>
+    where
+        B: Backend + 'static,
+    {
+        let start = Instant::now();
+        for (index, expected) in self.events.iter().enumerate() {
+            while locust.ctx.frame_count < expected.frame {
+                locust.begin_frame();
+                draw(locust);
+            }
+
+            let now = start + expected.at;
+            let (outcome, consumed_by) = match &expected.event {
+                Some(event) => (
+                    locust.on_event_at(event, now),
+                    locust.last_consumer().map(str::to_string),
+                ),
+                None => {
+                    let outcome = LocustEventOutcome {
+                        consumed: false,
+                        request_redraw: locust.tick(now),
+                    };
+                    (outcome, None)
+                }
+            };
+            let actual = RecordedEvent {
+                at: expected.at,
+                frame: locust.ctx.frame_count,
+                event: expected.event.clone(),
+                outcome,
+                consumed_by,
+            };
+            if actual != *expected {
+                return Err(RecordingError::Mismatch {
+                    index,
+                    expected: Box::new(expected.clone()),
+                    actual: Box::new(actual),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn entry(c: char, frame: u64) -> RecordedEvent {
+        RecordedEvent {
+            at: Duration::from_millis(frame * 40),
+            frame,
+            event: Some(Event::Key(KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::CONTROL,
+            ))),
+            outcome: LocustEventOutcome::CONSUMED_REDRAW,
+            consumed_by: Some("locust.omnibar".into()),
+        }
+    }
+
+    #[test]
+    fn test_json_lines_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("recording.jsonl");
+        let mut recorder = EventRecorder::create(&path).unwrap();
+        recorder.record(entry('p', 1)).unwrap();
+        recorder.record(entry('x', 3)).unwrap();
+        drop(recorder);
+
+        let loaded = Recording::load(&path).unwrap();
+        assert_eq!(loaded.events, vec![entry('p', 1), entry('x', 3)]);
+    }
+
+    #[test]
+    fn test_streaming_recorder_keeps_no_events() {
+        let mut recorder = EventRecorder::new(Vec::new());
+        recorder.record(entry('p', 1)).unwrap();
+        recorder.record(entry('x', 3)).unwrap();
+        assert_eq!(recorder.len(), 2);
+        assert!(recorder.into_recording().is_none());
+
+        let mut recorder = EventRecorder::new(Vec::new()).with_events_kept();
+        recorder.record(entry('p', 1)).unwrap();
+        assert_eq!(
+            recorder.into_recording().unwrap().events,
+            vec![entry('p', 1)]
+        );
+    }
+
+    #[test]
+    fn test_times_count_from_the_first_entry() {
+        let mut recorder = EventRecorder::in_memory();
+        let start = Instant::now() + Duration::from_secs(5);
+        assert_eq!(recorder.elapsed(start), Duration::ZERO);
+        assert_eq!(
+            recorder.elapsed(start + Duration::from_millis(1500)),
+            Duration::from_millis(1500)
+        );
+    }
+
+    #[test]
+    fn test_parse_error_reports_line() {
+        let input = "\n{\"frame\": 1}\n";
+        let err = Recording::from_reader(input.as_bytes()).unwrap_err();
+        assert!(matches!(err, RecordingError::Parse { line: 2, .. }));
+    }
+}
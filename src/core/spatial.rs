@@ -0,0 +1,759 @@
+//! Spatial queries over targets: directional navigation and nearest
+//! neighbours.
+//!
+//! Finds the target nearest to another in a direction, the way browsers move
+//! focus with spatial navigation. Candidates must lie past the source's edge
+//! in that direction. Candidates that overlap the source's row or column band
+//! (the "beam") win over the rest, and the others are only considered within
+//! a 45° cone. Among equals, distance along the direction counts less than
+//! drift away from it.
+//!
+//! `TargetRegistry` keeps its targets in a uniform grid, filled as targets
+//! are registered, so these queries and the point and area lookups only
+//! visit targets near the searched area instead of every registered one.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::core::spatial::{Direction, SpatialConfig};
+//! use locust::core::targets::{NavTarget, TargetRegistry};
+//! use ratatui::layout::Rect;
+//!
+//! let mut targets = TargetRegistry::new();
+//! targets.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+//! targets.register(NavTarget::new(2, Rect::new(20, 0, 10, 1)));
+//! targets.register(NavTarget::new(3, Rect::new(0, 5, 10, 1)));
+//!
+//! let config = SpatialConfig::default();
+//! assert_eq!(targets.nearest_in_direction(1, Direction::Right, &config).map(|t| t.id), Some(2));
+//! assert_eq!(targets.nearest_in_direction(1, Direction::Down, &config).map(|t| t.id), Some(3));
+//! ```
+
+use crate::core::targets::{NavTarget, TargetRegistry, TargetState};
+use ratatui::layout::Rect;
+use std::collections::HashMap;
+
+/// Direction of a spatial move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Keymap action that moves focus in this direction.
+    pub fn focus_action(self) -> &'static str {
+        match self {
+            Direction::Up => "focus_up",
+            Direction::Down => "focus_down",
+            Direction::Left => "focus_left",
+            Direction::Right => "focus_right",
+        }
+    }
+
+    /// Parses a `focus_*` keymap action.
+    pub fn from_focus_action(action: &str) -> Option<Self> {
+        match action {
+            "focus_up" => Some(Direction::Up),
+            "focus_down" => Some(Direction::Down),
+            "focus_left" => Some(Direction::Left),
+            "focus_right" => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Options for directional navigation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpatialConfig {
+    /// Only move between targets in the source target's group.
+    pub restrict_to_group: bool,
+
+    /// When nothing lies in the direction, continue from the opposite edge.
+    pub wrap: bool,
+}
+
+impl SpatialConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether moves stay within the source's group.
+    pub fn with_restrict_to_group(mut self, restrict: bool) -> Self {
+        self.restrict_to_group = restrict;
+        self
+    }
+
+    /// Sets whether moves wrap around at the edges.
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+}
+
+/// Cell bounds in signed coordinates, so wrapping can start off-screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Bounds {
+    pub(crate) left: i32,
+    pub(crate) top: i32,
+    pub(crate) right: i32,
+    pub(crate) bottom: i32,
+}
+
+impl Bounds {
+    /// Bounds of the single cell at (x, y).
+    pub(crate) fn point(x: u16, y: u16) -> Self {
+        Self::from(Rect::new(x, y, 1, 1))
+    }
+
+    /// Bounds moved by (dx, dy).
+    pub(crate) fn translate(self, dx: i32, dy: i32) -> Bounds {
+        Bounds {
+            left: self.left + dx,
+            top: self.top + dy,
+            right: self.right + dx,
+            bottom: self.bottom + dy,
+        }
+    }
+
+    /// Overlap of both, empty (`right <= left` or `bottom <= top`) if they
+    /// are apart.
+    pub(crate) fn intersect(self, other: Bounds) -> Bounds {
+        Bounds {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+
+    /// The rect covering these bounds, clamped to the `u16` range.
+    pub(crate) fn to_rect(self) -> Rect {
+        let clamp = |n: i32| n.clamp(0, u16::MAX as i32) as u16;
+        let (left, top) = (clamp(self.left), clamp(self.top));
+        Rect::new(
+            left,
+            top,
+            clamp(self.right).saturating_sub(left),
+            clamp(self.bottom).saturating_sub(top),
+        )
+    }
+
+    /// Smallest bounds holding both.
+    fn union(self, other: Bounds) -> Bounds {
+        Bounds {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    fn contains(self, other: Bounds) -> bool {
+        self.left <= other.left
+            && self.top <= other.top
+            && self.right >= other.right
+            && self.bottom >= other.bottom
+    }
+
+    /// Grid cells touched, counting empty bounds as their origin cell.
+    fn cells(self) -> impl Iterator<Item = (i32, i32)> {
+        let columns = self.left.div_euclid(CELL_WIDTH)
+            ..=(self.right - 1).max(self.left).div_euclid(CELL_WIDTH);
+        let rows = self.top.div_euclid(CELL_HEIGHT)
+            ..=(self.bottom - 1).max(self.top).div_euclid(CELL_HEIGHT);
+        rows.flat_map(move |row| columns.clone().map(move |column| (column, row)))
+    }
+}
+
+impl From<Rect> for Bounds {
+    fn from(rect: Rect) -> Self {
+        Self {
+            left: rect.x as i32,
+            top: rect.y as i32,
+            right: rect.x as i32 + rect.width as i32,
+            bottom: rect.y as i32 + rect.height as i32,
+        }
+    }
+}
+
+/// Width of a spatial grid cell, in terminal columns.
+const CELL_WIDTH: i32 = 16;
+
+/// Height of a spatial grid cell, in terminal rows.
+const CELL_HEIGHT: i32 = 4;
+
+/// Uniform grid of target IDs, bucketed by the cells their rects touch.
+///
+/// Lookups return candidates, a superset of the matches that callers check
+/// against the exact rects.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SpatialGrid {
+    cells: HashMap<(i32, i32), Vec<u64>>,
+    /// Targets whose rect may have changed since they were indexed; they
+    /// are candidates of every lookup.
+    loose: Vec<u64>,
+    /// Union of all indexed rects.
+    extent: Option<Bounds>,
+}
+
+impl SpatialGrid {
+    pub(crate) fn insert(&mut self, id: u64, rect: Rect) {
+        let bounds = Bounds::from(rect);
+        for cell in bounds.cells() {
+            self.cells.entry(cell).or_default().push(id);
+        }
+        self.extent = Some(self.extent.map_or(bounds, |extent| extent.union(bounds)));
+    }
+
+    pub(crate) fn remove(&mut self, id: u64, rect: Rect) {
+        for cell in Bounds::from(rect).cells() {
+            if let Some(ids) = self.cells.get_mut(&cell) {
+                ids.retain(|&other| other != id);
+                if ids.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        self.loose.retain(|&other| other != id);
+    }
+
+    /// Stops indexing `id` by `rect`, keeping it a candidate of all lookups.
+    pub(crate) fn unindex(&mut self, id: u64, rect: Rect) {
+        if !self.loose.contains(&id) {
+            self.remove(id, rect);
+            self.loose.push(id);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.cells.clear();
+        self.loose.clear();
+        self.extent = None;
+    }
+
+    /// Union of all indexed rects.
+    pub(crate) fn extent(&self) -> Option<Bounds> {
+        self.extent
+    }
+
+    /// IDs of the targets that may overlap `area`, possibly repeated.
+    pub(crate) fn candidates(&self, area: Bounds) -> Vec<u64> {
+        let mut ids = self.loose.clone();
+        let Some(extent) = self.extent else {
+            return ids;
+        };
+        let area = area.intersect(extent);
+        if area.left >= area.right || area.top >= area.bottom {
+            return ids;
+        }
+        for cell in area.cells() {
+            if let Some(cell_ids) = self.cells.get(&cell) {
+                ids.extend_from_slice(cell_ids);
+            }
+        }
+        ids
+    }
+}
+
+/// The area past `from`'s edge in `direction`, `reach` deep and widened by
+/// `widen` on both sides.
+fn ahead(from: Bounds, direction: Direction, reach: i32, widen: i32) -> Bounds {
+    let across = |bounds: Bounds| match direction {
+        Direction::Left | Direction::Right => Bounds {
+            top: from.top - widen,
+            bottom: from.bottom + widen,
+            ..bounds
+        },
+        Direction::Up | Direction::Down => Bounds {
+            left: from.left - widen,
+            right: from.right + widen,
+            ..bounds
+        },
+    };
+    across(match direction {
+        Direction::Right => Bounds {
+            left: from.right,
+            right: from.right + reach,
+            ..from
+        },
+        Direction::Left => Bounds {
+            left: from.left - reach,
+            right: from.left,
+            ..from
+        },
+        Direction::Down => Bounds {
+            top: from.bottom,
+            bottom: from.bottom + reach,
+            ..from
+        },
+        Direction::Up => Bounds {
+            top: from.top - reach,
+            bottom: from.top,
+            ..from
+        },
+    })
+}
+
+/// Depth from `from`'s edge in `direction` to the far side of `extent`.
+fn reach_to(extent: Bounds, from: Bounds, direction: Direction) -> i32 {
+    let reach = match direction {
+        Direction::Right => extent.right - from.right,
+        Direction::Left => from.left - extent.left,
+        Direction::Down => extent.bottom - from.bottom,
+        Direction::Up => from.top - extent.top,
+    };
+    reach.max(1)
+}
+
+/// Orthogonal drift is weighted more heavily than forward distance.
+const ORTHOGONAL_WEIGHT: i32 = 2;
+
+/// Scores `candidate` as a move from `from` in `direction`.
+///
+/// Returns `None` if the candidate is not in that direction, otherwise
+/// `(outside_beam, score)`, where lower sorts first.
+fn score(from: Bounds, candidate: Bounds, direction: Direction) -> Option<(bool, i32)> {
+    // Distance along the direction, between facing edges
+    let (forward, leading_ok) = match direction {
+        Direction::Right => (candidate.left - from.right, candidate.left >= from.right),
+        Direction::Left => (from.left - candidate.right, candidate.right <= from.left),
+        Direction::Down => (candidate.top - from.bottom, candidate.top >= from.bottom),
+        Direction::Up => (from.top - candidate.bottom, candidate.bottom <= from.top),
+    };
+    if !leading_ok {
+        return None;
+    }
+
+    // Gap across the direction; zero when the bands overlap (in the beam)
+    let (near, far, c_near, c_far) = match direction {
+        Direction::Left | Direction::Right => {
+            (from.top, from.bottom, candidate.top, candidate.bottom)
+        }
+        Direction::Up | Direction::Down => (from.left, from.right, candidate.left, candidate.right),
+    };
+    let sideways = if c_far <= near {
+        near - c_far + 1
+    } else if c_near >= far {
+        c_near - far + 1
+    } else {
+        0
+    };
+
+    let in_beam = sideways == 0;
+    if !in_beam && sideways > forward + 1 {
+        // Outside the 45° cone
+        return None;
+    }
+
+    Some((!in_beam, forward + ORTHOGONAL_WEIGHT * sideways))
+}
+
+/// Candidates in `direction` from `from`, best first, with their scores.
+fn ranked<'a>(
+    candidates: impl IntoIterator<Item = &'a NavTarget>,
+    from: Bounds,
+    direction: Direction,
+) -> Vec<((bool, i32), &'a NavTarget)> {
+    let mut ranked: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|t| score(from, Bounds::from(t.rect), direction).map(|s| (s, t)))
+        .collect();
+    ranked.sort_by_key(|((outside_beam, score), t)| (*outside_beam, *score, t.rect.y, t.rect.x));
+    ranked
+}
+
+impl TargetRegistry {
+    /// Finds the nearest target from `from_id` in `direction`.
+    ///
+    /// Disabled targets are skipped. Returns `None` if `from_id` is not
+    /// registered or no target lies in that direction (and `config.wrap`
+    /// found nothing either).
+    pub fn nearest_in_direction(
+        &self,
+        from_id: u64,
+        direction: Direction,
+        config: &SpatialConfig,
+    ) -> Option<&NavTarget> {
+        let source = self.by_id(from_id)?;
+        let eligible = |t: &NavTarget| {
+            t.id != from_id
+                && t.state != TargetState::Disabled
+                && (!config.restrict_to_group || t.group == source.group)
+        };
+
+        let from = Bounds::from(source.rect);
+        if let Some(target) = self.best_in_direction(from, direction, &eligible) {
+            return Some(target);
+        }
+        if !config.wrap {
+            return None;
+        }
+
+        // Continue from just beyond the opposite edge, in the same band
+        let all = || {
+            self.all()
+                .iter()
+                .filter(|t| eligible(t))
+                .map(|t| Bounds::from(t.rect))
+        };
+        let wrapped = match direction {
+            Direction::Right => {
+                let edge = all().map(|b| b.left).min()?;
+                Bounds {
+                    left: edge - 1,
+                    right: edge,
+                    ..from
+                }
+            }
+            Direction::Left => {
+                let edge = all().map(|b| b.right).max()?;
+                Bounds {
+                    left: edge,
+                    right: edge + 1,
+                    ..from
+                }
+            }
+            Direction::Down => {
+                let edge = all().map(|b| b.top).min()?;
+                Bounds {
+                    top: edge - 1,
+                    bottom: edge,
+                    ..from
+                }
+            }
+            Direction::Up => {
+                let edge = all().map(|b| b.bottom).max()?;
+                Bounds {
+                    top: edge,
+                    bottom: edge + 1,
+                    ..from
+                }
+            }
+        };
+        self.best_in_direction(wrapped, direction, &eligible)
+    }
+
+    /// Returns every target in `direction` from `from_id`, best move first.
+    ///
+    /// This ranks targets the way
+    /// [`nearest_in_direction`](Self::nearest_in_direction) picks them,
+    /// without wrapping. Disabled targets are skipped.
+    pub fn in_direction(
+        &self,
+        from_id: u64,
+        direction: Direction,
+        config: &SpatialConfig,
+    ) -> Vec<&NavTarget> {
+        let Some(source) = self.by_id(from_id) else {
+            return Vec::new();
+        };
+        let Some(extent) = self.spatial_extent() else {
+            return Vec::new();
+        };
+
+        let from = Bounds::from(source.rect);
+        let reach = reach_to(extent, from, direction);
+        let candidates = self
+            .spatial_candidates(ahead(from, direction, reach, reach + 1))
+            .into_iter()
+            .filter(|t| {
+                t.id != from_id
+                    && t.state != TargetState::Disabled
+                    && (!config.restrict_to_group || t.group == source.group)
+            });
+        ranked(candidates, from, direction)
+            .into_iter()
+            .map(|(_, t)| t)
+            .collect()
+    }
+
+    /// Returns the `k` targets whose centers are closest to (x, y),
+    /// nearest first.
+    ///
+    /// Ties keep registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use locust::core::targets::{NavTarget, TargetRegistry};
+    /// use ratatui::layout::Rect;
+    ///
+    /// let mut targets = TargetRegistry::new();
+    /// for id in 0..10u16 {
+    ///     targets.register(NavTarget::new(id as u64, Rect::new(id * 10, 0, 4, 1)));
+    /// }
+    ///
+    /// let ids: Vec<u64> = targets.k_nearest(33, 0, 3).iter().map(|t| t.id).collect();
+    /// assert_eq!(ids, vec![3, 4, 2]);
+    /// ```
+    pub fn k_nearest(&self, x: u16, y: u16, k: usize) -> Vec<&NavTarget> {
+        if k == 0 || self.is_empty() {
+            return Vec::new();
+        }
+        let distance = |t: &NavTarget| {
+            let (cx, cy) = t.center();
+            let dx = cx as i64 - x as i64;
+            let dy = cy as i64 - y as i64;
+            dx * dx + dy * dy
+        };
+
+        // Grow a square around the point until the k-th nearest center
+        // found lies inside it; centers outside are further away
+        let point = Bounds::point(x, y);
+        let mut reach = CELL_WIDTH.max(CELL_HEIGHT);
+        loop {
+            let area = Bounds {
+                left: point.left - reach,
+                top: point.top - reach,
+                right: point.right + reach,
+                bottom: point.bottom + reach,
+            };
+            let mut found = self.spatial_candidates(area);
+            found.sort_by_key(|t| distance(t));
+
+            let settled = found
+                .get(k - 1)
+                .is_some_and(|t| distance(t) <= (reach as i64).pow(2));
+            let covers_all = self
+                .spatial_extent()
+                .is_none_or(|extent| area.contains(extent));
+            if settled || covers_all {
+                found.truncate(k);
+                return found;
+            }
+            reach *= 2;
+        }
+    }
+
+    /// Best eligible target in `direction` from `from`.
+    fn best_in_direction(
+        &self,
+        from: Bounds,
+        direction: Direction,
+        eligible: &dyn Fn(&NavTarget) -> bool,
+    ) -> Option<&NavTarget> {
+        let extent = self.spatial_extent()?;
+        let full_reach = reach_to(extent, from, direction);
+        let best = |area: Bounds| {
+            let candidates = self
+                .spatial_candidates(area)
+                .into_iter()
+                .filter(|t| eligible(t));
+            ranked(candidates, from, direction).into_iter().next()
+        };
+
+        // Targets in the beam win, however far away
+        if let Some(((false, _), target)) = best(ahead(from, direction, full_reach, 0)) {
+            return Some(target);
+        }
+
+        // Otherwise search a growing cone; anything further than `reach`
+        // scores at least `reach`
+        let mut reach = CELL_WIDTH.max(CELL_HEIGHT);
+        loop {
+            let found = best(ahead(from, direction, reach, reach + 1));
+            match found {
+                Some(((_, score), target)) if score < reach => return Some(target),
+                _ if reach >= full_reach => return found.map(|(_, target)| target),
+                _ => reach *= 2,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(id: u64, x: u16, y: u16) -> NavTarget {
+        NavTarget::new(id, Rect::new(x, y, 4, 1))
+    }
+
+    fn nearest(targets: &TargetRegistry, from: u64, direction: Direction) -> Option<u64> {
+        targets
+            .nearest_in_direction(from, direction, &SpatialConfig::default())
+            .map(|t| t.id)
+    }
+
+    /// 3x3 grid, IDs 1-9 in reading order.
+    fn grid() -> TargetRegistry {
+        let mut targets = TargetRegistry::new();
+        for row in 0..3u16 {
+            for col in 0..3u16 {
+                targets.register(at((row * 3 + col + 1) as u64, col * 10, row * 3));
+            }
+        }
+        targets
+    }
+
+    #[test]
+    fn test_grid_moves() {
+        let targets = grid();
+        assert_eq!(nearest(&targets, 5, Direction::Up), Some(2));
+        assert_eq!(nearest(&targets, 5, Direction::Down), Some(8));
+        assert_eq!(nearest(&targets, 5, Direction::Left), Some(4));
+        assert_eq!(nearest(&targets, 5, Direction::Right), Some(6));
+        assert_eq!(nearest(&targets, 3, Direction::Right), None);
+    }
+
+    #[test]
+    fn test_beam_beats_closer_diagonal() {
+        let mut targets = TargetRegistry::new();
+        targets.register(at(1, 0, 5));
+        // Slightly off-axis but close
+        targets.register(at(2, 6, 6));
+        // Far away but in the same row
+        targets.register(at(3, 30, 5));
+
+        assert_eq!(nearest(&targets, 1, Direction::Right), Some(3));
+    }
+
+    #[test]
+    fn test_outside_cone_is_ignored() {
+        let mut targets = TargetRegistry::new();
+        targets.register(at(1, 0, 0));
+        targets.register(at(2, 5, 10));
+
+        assert_eq!(nearest(&targets, 1, Direction::Right), None);
+        assert_eq!(nearest(&targets, 1, Direction::Down), Some(2));
+    }
+
+    #[test]
+    fn test_restrict_to_group() {
+        let mut targets = TargetRegistry::new();
+        targets.register(at(1, 0, 0).with_group("left"));
+        targets.register(at(2, 10, 0).with_group("right"));
+        targets.register(at(3, 0, 3).with_group("left"));
+
+        let config = SpatialConfig::new().with_restrict_to_group(true);
+        let next = |direction| {
+            targets
+                .nearest_in_direction(1, direction, &config)
+                .map(|t| t.id)
+        };
+        assert_eq!(next(Direction::Right), None);
+        assert_eq!(next(Direction::Down), Some(3));
+    }
+
+    #[test]
+    fn test_wrap_at_edges() {
+        let targets = grid();
+        let config = SpatialConfig::new().with_wrap(true);
+        let next = |from, direction| {
+            targets
+                .nearest_in_direction(from, direction, &config)
+                .map(|t| t.id)
+        };
+
+        assert_eq!(next(3, Direction::Right), Some(1));
+        assert_eq!(next(4, Direction::Left), Some(6));
+        assert_eq!(next(8, Direction::Down), Some(2));
+        assert_eq!(next(2, Direction::Up), Some(8));
+    }
+
+    #[test]
+    fn test_disabled_targets_are_skipped() {
+        let mut targets = grid();
+        targets.by_id_mut(5).unwrap().state = TargetState::Disabled;
+        assert_eq!(nearest(&targets, 4, Direction::Right), Some(6));
+    }
+
+    /// Targets of mixed sizes scattered by a fixed LCG, so rects span
+    /// several grid cells and overlap.
+    fn scattered(count: u64) -> TargetRegistry {
+        let mut seed = 7u64;
+        let mut next = |modulo: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((seed >> 33) % modulo) as u16
+        };
+        let mut targets = TargetRegistry::new();
+        for id in 0..count {
+            let rect = Rect::new(next(300), next(120), 1 + next(40), 1 + next(9));
+            targets.register(NavTarget::new(id, rect));
+        }
+        targets
+    }
+
+    fn ids(targets: Vec<&NavTarget>) -> Vec<u64> {
+        targets.iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn test_grid_queries_match_linear_scan() {
+        let targets = scattered(400);
+        for (x, y) in [(0, 0), (17, 33), (150, 60), (299, 119), (500, 500)] {
+            let linear: Vec<u64> = targets
+                .all()
+                .iter()
+                .filter(|t| t.contains_point(x, y))
+                .map(|t| t.id)
+                .collect();
+            assert_eq!(ids(targets.at_point(x, y)), linear);
+
+            let area = Rect::new(x, y, 37, 11);
+            let linear: Vec<u64> = targets
+                .all()
+                .iter()
+                .filter(|t| t.overlaps_rect(&area))
+                .map(|t| t.id)
+                .collect();
+            assert_eq!(ids(targets.in_area(area)), linear);
+
+            let mut by_distance: Vec<&NavTarget> = targets.all().iter().collect();
+            by_distance.sort_by_key(|t| {
+                let (cx, cy) = t.center();
+                (cx as i64 - x as i64).pow(2) + (cy as i64 - y as i64).pow(2)
+            });
+            assert_eq!(
+                ids(targets.k_nearest(x, y, 5)),
+                ids(by_distance[..5].to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn test_directional_queries_match_linear_scan() {
+        let targets = scattered(400);
+        let config = SpatialConfig::default();
+        for from in [0, 57, 123, 399] {
+            let source = Bounds::from(targets.by_id(from).unwrap().rect);
+            for direction in [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ] {
+                let others = targets.all().iter().filter(|t| t.id != from);
+                let linear = ids(ranked(others, source, direction)
+                    .into_iter()
+                    .map(|(_, t)| t)
+                    .collect());
+                assert_eq!(ids(targets.in_direction(from, direction, &config)), linear);
+                assert_eq!(nearest(&targets, from, direction), linear.first().copied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_moved_and_removed_targets_stay_consistent() {
+        let mut targets = grid();
+        targets.by_id_mut(1).unwrap().rect = Rect::new(100, 40, 4, 1);
+        assert_eq!(ids(targets.at_point(100, 40)), vec![1]);
+        assert!(targets.at_point(0, 0).is_empty());
+        assert_eq!(targets.closest_to(90, 40).map(|t| t.id), Some(1));
+
+        targets.register(at(5, 60, 20));
+        assert_eq!(ids(targets.at_point(60, 20)), vec![5]);
+        assert!(targets.at_point(10, 3).is_empty());
+
+        assert!(targets.remove(2));
+        assert_eq!(ids(targets.in_area(Rect::new(0, 0, 30, 1))), vec![3]);
+        assert_eq!(targets.by_id(9).unwrap().id, 9);
+        assert_eq!(nearest(&targets, 8, Direction::Right), Some(9));
+    }
+}
@@ -1,5 +1,12 @@
+use crate::core::actions::ScrollDirection;
+use crate::core::spatial::{Bounds, SpatialGrid};
 use ratatui::layout::Rect;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// Metadata key holding a target's role (see [`NavTarget::with_role`]).
+pub const ROLE_METADATA_KEY: &str = "role";
 
 /// Action to perform when a navigation target is activated.
 ///
@@ -88,10 +95,41 @@ impl Default for TargetPriority {
     }
 }
 
+/// Identity of a target that survives ID changes between frames.
+///
+/// Applications that regenerate IDs every frame (such as adapters using a
+/// fresh `TargetBuilder`) can still be followed across frames by key. See
+/// [`NavTarget::stable_key`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TargetKey {
+    /// Set with [`NavTarget::with_key`].
+    Explicit(String),
+    /// Hash of the target's group and label.
+    Derived(u64),
+    /// The target ID, for targets with neither a key nor a label.
+    Id(u64),
+}
+
+impl TargetKey {
+    /// Key of the `n`-th target in a frame with this key, counting from 0.
+    ///
+    /// The first keeps the key itself so it matches `stable_key`; later
+    /// duplicates get derived keys of their own.
+    fn occurrence(&self, n: usize) -> Self {
+        if n == 0 {
+            return self.clone();
+        }
+        let mut hasher = DefaultHasher::new();
+        (self, n).hash(&mut hasher);
+        TargetKey::Derived(hasher.finish())
+    }
+}
+
 /// Callback function type for target activation.
 ///
 /// When a target is activated, this callback is invoked with the target ID.
-/// Returns `true` if the action was handled, `false` otherwise.
+/// Returns `true` if the action was handled, `false` otherwise. Unhandled
+/// activations fall through to the context's action queue.
 pub type TargetCallback = Box<dyn Fn(u64) -> bool + Send + Sync>;
 
 /// A navigable region in the UI, such as a list row, table cell, tab, or button.
@@ -143,6 +181,14 @@ pub struct NavTarget {
 
     /// Optional metadata for application-specific data.
     pub metadata: HashMap<String, String>,
+
+    /// Optional stable key identifying the target across frames.
+    pub key: Option<String>,
+
+    /// ID of the enclosing target, such as the pane holding a list row.
+    /// Parents nest into a tree queried with [`TargetRegistry::children_of`],
+    /// [`TargetRegistry::ancestors`] and [`TargetRegistry::scope`].
+    pub parent: Option<u64>,
 }
 
 impl NavTarget {
@@ -169,6 +215,8 @@ impl NavTarget {
             priority: TargetPriority::default(),
             group: None,
             metadata: HashMap::new(),
+            key: None,
+            parent: None,
         }
     }
 
@@ -210,6 +258,60 @@ impl NavTarget {
         self
     }
 
+    /// Sets a key that identifies this target across frames, even if its
+    /// ID or label changes.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// The key this target is followed by across frames.
+    ///
+    /// This is the explicit key if one is set, otherwise a hash of the
+    /// group and label, and the ID for targets without a label.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use locust::core::targets::NavTarget;
+    /// use ratatui::layout::Rect;
+    ///
+    /// let a = NavTarget::new(1, Rect::new(0, 0, 5, 1)).with_label("Save");
+    /// let b = NavTarget::new(7, Rect::new(0, 3, 5, 1)).with_label("Save");
+    /// assert_eq!(a.stable_key(), b.stable_key());
+    /// ```
+    pub fn stable_key(&self) -> TargetKey {
+        if let Some(key) = &self.key {
+            return TargetKey::Explicit(key.clone());
+        }
+        match &self.label {
+            Some(label) => {
+                let mut hasher = DefaultHasher::new();
+                (&self.group, label).hash(&mut hasher);
+                TargetKey::Derived(hasher.finish())
+            }
+            None => TargetKey::Id(self.id),
+        }
+    }
+
+    /// Nests this target inside the target with ID `parent`.
+    pub fn with_parent(mut self, parent: u64) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Sets the kind of widget this target is, such as `"button"` or `"tab"`.
+    ///
+    /// The role is stored in `metadata` under [`ROLE_METADATA_KEY`].
+    pub fn with_role(self, role: impl Into<String>) -> Self {
+        self.with_metadata(ROLE_METADATA_KEY, role)
+    }
+
+    /// The role set with [`with_role`](Self::with_role).
+    pub fn role(&self) -> Option<&str> {
+        self.metadata.get(ROLE_METADATA_KEY).map(String::as_str)
+    }
+
     /// Checks if a point (x, y) is inside this target's rectangle.
     ///
     /// # Examples
@@ -251,6 +353,124 @@ impl NavTarget {
     }
 }
 
+/// A target whose rectangle changed since the previous frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetMove {
+    /// Key of the target in both frames.
+    pub key: TargetKey,
+    /// Rectangle in the previous frame.
+    pub from: Rect,
+    /// Rectangle in the current frame.
+    pub to: Rect,
+}
+
+/// A target whose label changed since the previous frame.
+///
+/// Only targets with an explicit key (or no label before or after) can be
+/// relabelled; for others the label is part of the key, so a new label
+/// shows up as one target removed and another added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetRelabel {
+    /// Key of the target in both frames.
+    pub key: TargetKey,
+    /// Label in the previous frame.
+    pub from: Option<String>,
+    /// Label in the current frame.
+    pub to: Option<String>,
+}
+
+/// Changes between the previous frame's targets and the current ones,
+/// matched by key. See [`TargetRegistry::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameDiff {
+    /// Keys new in this frame, in registration order.
+    pub added: Vec<TargetKey>,
+    /// Keys of the previous frame that are gone, in registration order.
+    pub removed: Vec<TargetKey>,
+    /// Targets whose rectangle changed, in registration order.
+    pub moved: Vec<TargetMove>,
+    /// Targets whose label changed, in registration order.
+    pub relabelled: Vec<TargetRelabel>,
+}
+
+impl FrameDiff {
+    /// Returns whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.relabelled.is_empty()
+    }
+}
+
+/// A target registered entirely outside the current clip region.
+///
+/// Such targets are not registered; the registry lists them in
+/// [`TargetRegistry::offscreen`] instead, e.g. for "more below" indicators.
+#[derive(Debug, Clone)]
+pub struct OffscreenTarget {
+    /// The target as it was passed to `register`.
+    pub target: NavTarget,
+
+    /// Direction to scroll the clip region to bring the target into view.
+    pub direction: ScrollDirection,
+}
+
+/// Offset and clip applied to registered rects, see
+/// [`TargetRegistry::push_offset`] and [`TargetRegistry::push_clip`].
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    dx: i32,
+    dy: i32,
+    /// Clip in screen coordinates.
+    clip: Bounds,
+}
+
+impl Default for Region {
+    fn default() -> Self {
+        Self {
+            dx: 0,
+            dy: 0,
+            clip: Bounds::from(Rect::new(0, 0, u16::MAX, u16::MAX)),
+        }
+    }
+}
+
+impl Region {
+    /// Places `rect` in screen coordinates, or returns the direction it
+    /// lies in if it is clipped away entirely.
+    fn place(&self, rect: Rect) -> Result<Rect, ScrollDirection> {
+        let bounds = Bounds::from(rect).translate(self.dx, self.dy);
+        let visible = bounds.intersect(self.clip);
+        // Empty rects stay visible as long as they are inside the clip
+        let apart = |from: i32, to: i32, size: i32| to < from || (to == from && size > 0);
+        let clipped = apart(visible.left, visible.right, bounds.right - bounds.left)
+            || apart(visible.top, visible.bottom, bounds.bottom - bounds.top);
+        if !clipped {
+            return Ok(visible.to_rect());
+        }
+
+        Err(if bounds.bottom <= self.clip.top {
+            ScrollDirection::Up
+        } else if bounds.top >= self.clip.bottom {
+            ScrollDirection::Down
+        } else if bounds.right <= self.clip.left {
+            ScrollDirection::Left
+        } else {
+            ScrollDirection::Right
+        })
+    }
+}
+
+/// What is kept of a target of the previous frame.
+#[derive(Debug, Clone)]
+struct PreviousTarget {
+    key: TargetKey,
+    id: u64,
+    rect: Rect,
+    label: Option<String>,
+}
+
 /// Registry of navigation targets discovered during a frame.
 ///
 /// The registry maintains a collection of targets and provides efficient
@@ -258,11 +478,36 @@ impl NavTarget {
 /// Targets are typically registered during the rendering phase and cleared
 /// at the start of each frame.
 ///
+/// Point, area and nearest-target queries go through a grid index built as
+/// targets are registered, so they stay cheap with thousands of targets.
+/// Results are in registration order unless noted otherwise.
+///
+/// # Offsets and Clipping
+///
+/// Widgets drawn inside a scrolled viewport or a nested area can register
+/// rects relative to it: [`push_offset`](Self::push_offset) translates and
+/// [`push_clip`](Self::push_clip) clips the rects of targets registered
+/// until the matching [`pop`](Self::pop). Targets clipped away entirely
+/// are listed in [`offscreen`](Self::offscreen) instead of being
+/// registered.
+///
 /// # Thread Safety
 ///
 /// The registry is not thread-safe by default. If you need to share it
 /// across threads, wrap it in `Arc<Mutex<TargetRegistry>>`.
 ///
+/// # State Overrides
+///
+/// Interaction state such as mouse hover is tracked across frames as a
+/// state override per target ID. Overrides survive `clear()` and are
+/// applied to targets registered with `TargetState::Normal`.
+///
+/// # Callbacks
+///
+/// Activation callbacks can be attached per target ID or per group. Target
+/// callbacks are cleared along with the targets each frame; group callbacks
+/// persist until removed.
+///
 /// # Examples
 ///
 /// ```rust
@@ -288,10 +533,36 @@ impl NavTarget {
 /// let high_priority = registry.by_priority(TargetPriority::High);
 /// assert_eq!(high_priority.len(), 1);
 /// ```
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct TargetRegistry {
     targets: Vec<NavTarget>,
     targets_by_id: HashMap<u64, usize>,
+    callbacks: HashMap<u64, TargetCallback>,
+    group_callbacks: HashMap<String, TargetCallback>,
+    /// Overrides by target key, so they follow targets whose IDs change.
+    state_overrides: HashMap<TargetKey, TargetState>,
+    /// Stable key and registry key per target ID.
+    keys_by_id: HashMap<u64, (TargetKey, TargetKey)>,
+    ids_by_key: HashMap<TargetKey, u64>,
+    key_counts: HashMap<TargetKey, usize>,
+    previous: Vec<PreviousTarget>,
+    previous_by_key: HashMap<TargetKey, usize>,
+    previous_by_id: HashMap<u64, usize>,
+    grid: SpatialGrid,
+    regions: Vec<Region>,
+    offscreen: Vec<OffscreenTarget>,
+}
+
+impl std::fmt::Debug for TargetRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TargetRegistry")
+            .field("targets", &self.targets)
+            .field("callbacks", &self.callbacks.len())
+            .field("group_callbacks", &self.group_callbacks.len())
+            .field("state_overrides", &self.state_overrides)
+            .field("previous", &self.previous.len())
+            .finish()
+    }
 }
 
 impl TargetRegistry {
@@ -300,28 +571,372 @@ impl TargetRegistry {
         Self::default()
     }
 
-    /// Clears all registered targets.
+    /// Clears all registered targets and their per-target callbacks.
     ///
     /// This should be called at the beginning of each frame to remove
-    /// targets from the previous frame.
+    /// targets from the previous frame. Group callbacks are kept, and the
+    /// cleared targets become the previous frame for [`diff`](Self::diff).
     pub fn clear(&mut self) {
-        self.targets.clear();
+        self.previous = self
+            .targets
+            .drain(..)
+            .filter_map(|target| {
+                let (_, key) = self.keys_by_id.get(&target.id)?;
+                Some(PreviousTarget {
+                    key: key.clone(),
+                    id: target.id,
+                    rect: target.rect,
+                    label: target.label,
+                })
+            })
+            .collect();
+        self.previous_by_key.clear();
+        self.previous_by_id.clear();
+        for (idx, previous) in self.previous.iter().enumerate() {
+            self.previous_by_key.insert(previous.key.clone(), idx);
+            self.previous_by_id.insert(previous.id, idx);
+        }
+
         self.targets_by_id.clear();
+        self.callbacks.clear();
+        self.keys_by_id.clear();
+        self.ids_by_key.clear();
+        self.key_counts.clear();
+        self.grid.clear();
+        self.regions.clear();
+        self.offscreen.clear();
     }
 
     /// Registers a new navigation target.
     ///
     /// If a target with the same ID already exists, it will be replaced.
-    pub fn register(&mut self, target: NavTarget) {
+    /// The rect is translated and clipped by the pushed offsets and clips;
+    /// a target clipped away entirely goes to [`offscreen`](Self::offscreen).
+    pub fn register(&mut self, mut target: NavTarget) {
+        if let Some(region) = self.regions.last() {
+            match region.place(target.rect) {
+                Ok(rect) => target.rect = rect,
+                Err(direction) => {
+                    self.offscreen.push(OffscreenTarget { target, direction });
+                    return;
+                }
+            }
+        }
+
+        self.assign_key(&target);
+        if target.state == TargetState::Normal {
+            if let Some(&state) = self
+                .key_of(target.id)
+                .and_then(|key| self.state_overrides.get(key))
+            {
+                target.state = state;
+            }
+        }
+
         if let Some(&idx) = self.targets_by_id.get(&target.id) {
+            self.grid.remove(target.id, self.targets[idx].rect);
+            self.grid.insert(target.id, target.rect);
             self.targets[idx] = target;
         } else {
+            self.grid.insert(target.id, target.rect);
             let idx = self.targets.len();
             self.targets_by_id.insert(target.id, idx);
             self.targets.push(target);
         }
     }
 
+    /// Translates the rects of targets registered until the matching
+    /// [`pop`](Self::pop) by (dx, dy).
+    ///
+    /// Offsets add up with the ones already pushed. A viewport scrolled down
+    /// by `n` rows pushes an offset of `-n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use locust::core::actions::ScrollDirection;
+    /// use locust::core::targets::{NavTarget, TargetRegistry};
+    /// use ratatui::layout::Rect;
+    ///
+    /// let mut registry = TargetRegistry::new();
+    /// // A list drawn at (5, 2), 3 rows high, scrolled down by 4 rows
+    /// registry.push_clip(Rect::new(5, 2, 20, 3));
+    /// registry.push_offset(5, 2 - 4);
+    /// for row in 0..10 {
+    ///     registry.register(NavTarget::new(row as u64, Rect::new(0, row, 20, 1)));
+    /// }
+    /// registry.pop();
+    /// registry.pop();
+    ///
+    /// assert_eq!(registry.len(), 3);
+    /// assert_eq!(registry.by_id(4).unwrap().rect, Rect::new(5, 2, 20, 1));
+    /// assert_eq!(registry.offscreen()[0].direction, ScrollDirection::Up);
+    /// ```
+    pub fn push_offset(&mut self, dx: i32, dy: i32) {
+        let current = self.regions.last().copied().unwrap_or_default();
+        self.regions.push(Region {
+            dx: current.dx + dx,
+            dy: current.dy + dy,
+            ..current
+        });
+    }
+
+    /// Clips the rects of targets registered until the matching
+    /// [`pop`](Self::pop) to `area`.
+    ///
+    /// `area` is translated by the pushed offsets and narrows the clips
+    /// already pushed.
+    pub fn push_clip(&mut self, area: Rect) {
+        let current = self.regions.last().copied().unwrap_or_default();
+        let clip = Bounds::from(area).translate(current.dx, current.dy);
+        self.regions.push(Region {
+            clip: current.clip.intersect(clip),
+            ..current
+        });
+    }
+
+    /// Undoes the last [`push_offset`](Self::push_offset) or
+    /// [`push_clip`](Self::push_clip).
+    ///
+    /// Returns `false` if nothing was pushed. [`clear`](Self::clear) drops
+    /// whatever is still pushed.
+    pub fn pop(&mut self) -> bool {
+        self.regions.pop().is_some()
+    }
+
+    /// Targets clipped away entirely this frame, in registration order.
+    pub fn offscreen(&self) -> &[OffscreenTarget] {
+        &self.offscreen
+    }
+
+    /// Gives a newly registered target its key, keeping the key of a
+    /// replaced target with the same stable key.
+    fn assign_key(&mut self, target: &NavTarget) {
+        let stable = target.stable_key();
+        if let Some((old_stable, _)) = self.keys_by_id.get(&target.id) {
+            if *old_stable == stable {
+                return;
+            }
+            self.release_key(target.id);
+        }
+
+        let count = self.key_counts.entry(stable.clone()).or_insert(0);
+        // Skip occurrences still held by targets registered before a removal
+        let key = (*count..)
+            .map(|n| stable.occurrence(n))
+            .find(|key| !self.ids_by_key.contains_key(key))
+            .expect("occurrences are unbounded");
+        *count += 1;
+        self.ids_by_key.insert(key.clone(), target.id);
+        self.keys_by_id.insert(target.id, (stable, key));
+    }
+
+    /// Frees the key of a removed or replaced target.
+    fn release_key(&mut self, id: u64) {
+        let Some((stable, key)) = self.keys_by_id.remove(&id) else {
+            return;
+        };
+        self.ids_by_key.remove(&key);
+        if let Some(count) = self.key_counts.get_mut(&stable) {
+            *count -= 1;
+            if *count == 0 {
+                self.key_counts.remove(&stable);
+            }
+        }
+    }
+
+    /// The key of a registered target.
+    pub fn key_of(&self, id: u64) -> Option<&TargetKey> {
+        self.keys_by_id.get(&id).map(|(_, key)| key)
+    }
+
+    /// Finds a target by its key.
+    pub fn by_key(&self, key: &TargetKey) -> Option<&NavTarget> {
+        self.by_id(*self.ids_by_key.get(key)?)
+    }
+
+    /// Follows a target ID of the previous frame to the current frame.
+    ///
+    /// Returns the ID of the current target with the same key, or `None`
+    /// if the target is gone. Use this to carry a plugin's own state keyed
+    /// by ID over when the application regenerates IDs.
+    pub fn remap_id(&self, previous_id: u64) -> Option<u64> {
+        let previous = &self.previous[*self.previous_by_id.get(&previous_id)?];
+        self.ids_by_key.get(&previous.key).copied()
+    }
+
+    /// Compares the targets registered so far with the previous frame.
+    ///
+    /// Call this once the frame's targets are registered, e.g. from a
+    /// plugin's `render_overlay` or `on_event`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use locust::core::targets::{NavTarget, TargetRegistry};
+    /// use ratatui::layout::Rect;
+    ///
+    /// let mut registry = TargetRegistry::new();
+    /// registry.register(NavTarget::new(1, Rect::new(0, 0, 5, 1)).with_label("Save"));
+    /// registry.register(NavTarget::new(2, Rect::new(0, 1, 5, 1)).with_label("Undo"));
+    ///
+    /// registry.clear();
+    /// // IDs restart, "Save" moves down, "Undo" is gone
+    /// registry.register(NavTarget::new(1, Rect::new(0, 2, 5, 1)).with_label("Save"));
+    ///
+    /// let diff = registry.diff();
+    /// assert_eq!(diff.moved.len(), 1);
+    /// assert_eq!(diff.removed.len(), 1);
+    /// assert!(diff.added.is_empty());
+    /// ```
+    pub fn diff(&self) -> FrameDiff {
+        let mut diff = FrameDiff::default();
+        for target in &self.targets {
+            let Some((_, key)) = self.keys_by_id.get(&target.id) else {
+                continue;
+            };
+            let Some(&idx) = self.previous_by_key.get(key) else {
+                diff.added.push(key.clone());
+                continue;
+            };
+
+            let previous = &self.previous[idx];
+            if previous.rect != target.rect {
+                diff.moved.push(TargetMove {
+                    key: key.clone(),
+                    from: previous.rect,
+                    to: target.rect,
+                });
+            }
+            if previous.label != target.label {
+                diff.relabelled.push(TargetRelabel {
+                    key: key.clone(),
+                    from: previous.label.clone(),
+                    to: target.label.clone(),
+                });
+            }
+        }
+
+        diff.removed = self
+            .previous
+            .iter()
+            .filter(|previous| !self.ids_by_key.contains_key(&previous.key))
+            .map(|previous| previous.key.clone())
+            .collect();
+        diff
+    }
+
+    /// Registers a target together with an activation callback.
+    pub fn register_with_callback<F>(&mut self, target: NavTarget, callback: F)
+    where
+        F: Fn(u64) -> bool + Send + Sync + 'static,
+    {
+        let id = target.id;
+        self.register(target);
+        self.on_activate(id, callback);
+    }
+
+    /// Attaches an activation callback to the target with the given ID.
+    ///
+    /// Replaces any callback previously attached to this ID.
+    pub fn on_activate<F>(&mut self, id: u64, callback: F)
+    where
+        F: Fn(u64) -> bool + Send + Sync + 'static,
+    {
+        self.callbacks.insert(id, Box::new(callback));
+    }
+
+    /// Attaches an activation callback to every target in a group.
+    ///
+    /// Group callbacks are consulted when a target has no callback of its own.
+    pub fn on_group_activate<F>(&mut self, group: impl Into<String>, callback: F)
+    where
+        F: Fn(u64) -> bool + Send + Sync + 'static,
+    {
+        self.group_callbacks
+            .insert(group.into(), Box::new(callback));
+    }
+
+    /// Removes the callback attached to a target ID.
+    pub fn remove_callback(&mut self, id: u64) -> bool {
+        self.callbacks.remove(&id).is_some()
+    }
+
+    /// Removes the callback attached to a group.
+    pub fn remove_group_callback(&mut self, group: &str) -> bool {
+        self.group_callbacks.remove(group).is_some()
+    }
+
+    /// Invokes the activation callback for a target.
+    ///
+    /// The target's own callback takes precedence over its group's callback.
+    /// Returns `None` if no callback applies, otherwise whether the callback
+    /// handled the activation.
+    pub fn invoke_callback(&self, id: u64) -> Option<bool> {
+        if let Some(callback) = self.callbacks.get(&id) {
+            return Some(callback(id));
+        }
+
+        let group = self.by_id(id)?.group.as_deref()?;
+        self.group_callbacks.get(group).map(|callback| callback(id))
+    }
+
+    /// Overrides the state of a target in this and subsequent frames.
+    ///
+    /// The override is stored under the target's key, so it stays with the
+    /// target when its ID changes and does not carry over to another target
+    /// reusing the ID. Disabled targets keep their state.
+    pub fn set_state_override(&mut self, id: u64, state: TargetState) {
+        let key = self.override_key(id);
+        self.set_key_state_override(key, state);
+    }
+
+    /// Removes the state override of a target.
+    ///
+    /// If the target currently shows the overridden state, it is reset
+    /// to `TargetState::Normal`.
+    pub fn clear_state_override(&mut self, id: u64) {
+        let key = self.override_key(id);
+        self.clear_key_state_override(&key);
+    }
+
+    /// Returns the state override of a target, if any.
+    pub fn state_override(&self, id: u64) -> Option<TargetState> {
+        self.state_overrides.get(&self.override_key(id)).copied()
+    }
+
+    /// Overrides the state of the target with `key`, registered or not.
+    pub(crate) fn set_key_state_override(&mut self, key: TargetKey, state: TargetState) {
+        if let Some(&id) = self.ids_by_key.get(&key) {
+            if let Some(target) = self.target_mut(id) {
+                if target.state != TargetState::Disabled {
+                    target.state = state;
+                }
+            }
+        }
+        self.state_overrides.insert(key, state);
+    }
+
+    /// Removes the state override of the target with `key`.
+    pub(crate) fn clear_key_state_override(&mut self, key: &TargetKey) {
+        let Some(state) = self.state_overrides.remove(key) else {
+            return;
+        };
+        if let Some(&id) = self.ids_by_key.get(key) {
+            if let Some(target) = self.target_mut(id) {
+                if target.state == state {
+                    target.state = TargetState::Normal;
+                }
+            }
+        }
+    }
+
+    /// Key of a registered target, or the ID itself for one not
+    /// registered this frame.
+    fn override_key(&self, id: u64) -> TargetKey {
+        self.key_of(id).cloned().unwrap_or(TargetKey::Id(id))
+    }
+
     /// Returns all registered targets as a slice.
     pub fn all(&self) -> &[NavTarget] {
         &self.targets
@@ -343,26 +958,66 @@ impl TargetRegistry {
     }
 
     /// Finds a mutable reference to a target by its ID.
+    ///
+    /// As the rect may be changed through it, the target leaves the grid
+    /// index and is checked by every spatial query until the next
+    /// [`clear`](Self::clear).
     pub fn by_id_mut(&mut self, id: u64) -> Option<&mut NavTarget> {
+        let &idx = self.targets_by_id.get(&id)?;
+        let target = &mut self.targets[idx];
+        self.grid.unindex(id, target.rect);
+        Some(target)
+    }
+
+    /// Mutable access for changes that keep the rect.
+    fn target_mut(&mut self, id: u64) -> Option<&mut NavTarget> {
         self.targets_by_id
             .get(&id)
             .map(|&idx| &mut self.targets[idx])
     }
 
+    /// Targets the grid index places near `area`, in registration order.
+    ///
+    /// This is a superset of the targets overlapping `area`.
+    pub(crate) fn spatial_candidates(&self, area: Bounds) -> Vec<&NavTarget> {
+        let mut indices: Vec<usize> = self
+            .grid
+            .candidates(area)
+            .into_iter()
+            .filter_map(|id| self.targets_by_id.get(&id).copied())
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|idx| &self.targets[idx]).collect()
+    }
+
+    /// Bounds of all indexed targets.
+    pub(crate) fn spatial_extent(&self) -> Option<Bounds> {
+        self.grid.extent()
+    }
+
     /// Returns all targets at the given point (x, y).
     ///
     /// If multiple targets overlap at this point, all are returned.
     pub fn at_point(&self, x: u16, y: u16) -> Vec<&NavTarget> {
-        self.targets
-            .iter()
+        self.spatial_candidates(Bounds::point(x, y))
+            .into_iter()
             .filter(|t| t.contains_point(x, y))
             .collect()
     }
 
+    /// Returns the topmost target at the given point (x, y).
+    ///
+    /// Targets registered later are assumed to be drawn on top of
+    /// earlier ones.
+    pub fn topmost_at(&self, x: u16, y: u16) -> Option<&NavTarget> {
+        self.at_point(x, y).pop()
+    }
+
     /// Returns all targets that overlap with the given rectangle.
     pub fn in_area(&self, area: Rect) -> Vec<&NavTarget> {
-        self.targets
-            .iter()
+        self.spatial_candidates(Bounds::from(area))
+            .into_iter()
             .filter(|t| t.overlaps_rect(&area))
             .collect()
     }
@@ -404,37 +1059,113 @@ impl TargetRegistry {
         targets
     }
 
+    /// Returns the targets whose parent is `id`, in registration order.
+    pub fn children_of(&self, id: u64) -> Vec<&NavTarget> {
+        self.targets
+            .iter()
+            .filter(|t| t.parent == Some(id))
+            .collect()
+    }
+
+    /// Returns the registered ancestors of `id`, nearest first.
+    ///
+    /// The walk stops at the first parent that is not registered, and at a
+    /// cycle.
+    pub fn ancestors(&self, id: u64) -> Vec<&NavTarget> {
+        let mut ancestors = Vec::new();
+        let mut current = self.by_id(id).and_then(|t| t.parent);
+        while let Some(parent_id) = current {
+            let Some(parent) = self.by_id(parent_id) else {
+                break;
+            };
+            if parent.id == id || ancestors.len() >= self.targets.len() {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent.parent;
+        }
+        ancestors
+    }
+
+    /// Returns every descendant of `id`, in registration order.
+    ///
+    /// The target itself is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use locust::core::targets::{NavTarget, TargetRegistry};
+    /// use ratatui::layout::Rect;
+    ///
+    /// let mut registry = TargetRegistry::new();
+    /// registry.register(NavTarget::new(1, Rect::new(0, 0, 40, 10)).with_label("Pane"));
+    /// registry.register(NavTarget::new(2, Rect::new(1, 1, 38, 1)).with_parent(1));
+    /// registry.register(NavTarget::new(3, Rect::new(2, 1, 5, 1)).with_parent(2));
+    /// registry.register(NavTarget::new(4, Rect::new(0, 12, 10, 1)));
+    ///
+    /// let ids: Vec<u64> = registry.scope(1).iter().map(|t| t.id).collect();
+    /// assert_eq!(ids, vec![2, 3]);
+    /// ```
+    pub fn scope(&self, id: u64) -> Vec<&NavTarget> {
+        let mut children: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (idx, target) in self.targets.iter().enumerate() {
+            if let Some(parent) = target.parent {
+                children.entry(parent).or_default().push(idx);
+            }
+        }
+
+        // Walk down from `id`; `visited` stops at cycles
+        let mut visited = HashSet::from([id]);
+        let mut found = Vec::new();
+        let mut stack = vec![id];
+        while let Some(parent) = stack.pop() {
+            for &idx in children.get(&parent).into_iter().flatten() {
+                let child = self.targets[idx].id;
+                if visited.insert(child) {
+                    found.push(idx);
+                    stack.push(child);
+                }
+            }
+        }
+
+        found.sort_unstable();
+        found.into_iter().map(|idx| &self.targets[idx]).collect()
+    }
+
+    /// Returns the targets without a registered parent.
+    pub fn roots(&self) -> Vec<&NavTarget> {
+        self.targets
+            .iter()
+            .filter(|t| {
+                t.parent
+                    .is_none_or(|p| !self.targets_by_id.contains_key(&p))
+            })
+            .collect()
+    }
+
     /// Returns the target closest to the given point.
+    ///
+    /// See [`k_nearest`](Self::k_nearest) for more than one.
     pub fn closest_to(&self, x: u16, y: u16) -> Option<&NavTarget> {
-        self.targets.iter().min_by_key(|t| {
-            let (cx, cy) = t.center();
-            let dx = (cx as i32 - x as i32).abs();
-            let dy = (cy as i32 - y as i32).abs();
-            dx * dx + dy * dy // squared distance (no need for sqrt)
-        })
+        self.k_nearest(x, y, 1).pop()
     }
 
     /// Removes a target by its ID.
     ///
     /// Returns `true` if the target was removed, `false` if it didn't exist.
     pub fn remove(&mut self, id: u64) -> bool {
-        if let Some(&idx) = self.targets_by_id.get(&id) {
-            self.targets.remove(idx);
-            self.targets_by_id.remove(&id);
-            // Rebuild index since indices shifted
-            self.rebuild_index();
-            true
-        } else {
-            false
-        }
-    }
-
-    /// Rebuilds the internal index after removal operations.
-    fn rebuild_index(&mut self) {
-        self.targets_by_id.clear();
-        for (idx, target) in self.targets.iter().enumerate() {
-            self.targets_by_id.insert(target.id, idx);
+        let Some(idx) = self.targets_by_id.remove(&id) else {
+            return false;
+        };
+        let target = self.targets.remove(idx);
+        self.grid.remove(id, target.rect);
+        self.callbacks.remove(&id);
+        self.release_key(id);
+        // Only the targets after the removed one moved
+        for (offset, target) in self.targets[idx..].iter().enumerate() {
+            self.targets_by_id.insert(target.id, idx + offset);
         }
+        true
     }
 }
 
@@ -464,12 +1195,44 @@ impl TargetBuilder {
         id
     }
 
+    /// Registers a built target in `registry` with an activation callback.
+    ///
+    /// Returns the target's ID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use locust::core::targets::{TargetBuilder, TargetRegistry};
+    /// use ratatui::layout::Rect;
+    ///
+    /// let mut builder = TargetBuilder::new();
+    /// let mut registry = TargetRegistry::new();
+    ///
+    /// let button = builder.button(Rect::new(0, 0, 10, 1), "Save");
+    /// let id = builder.register_with_callback(&mut registry, button, |_id| true);
+    /// assert_eq!(registry.invoke_callback(id), Some(true));
+    /// ```
+    pub fn register_with_callback<F>(
+        &mut self,
+        registry: &mut TargetRegistry,
+        target: NavTarget,
+        callback: F,
+    ) -> u64
+    where
+        F: Fn(u64) -> bool + Send + Sync + 'static,
+    {
+        let id = target.id;
+        registry.register_with_callback(target, callback);
+        id
+    }
+
     /// Creates a button target with high priority.
     pub fn button(&mut self, rect: Rect, label: impl Into<String>) -> NavTarget {
         NavTarget::new(self.next_id(), rect)
             .with_label(label)
             .with_action(TargetAction::Activate)
             .with_priority(TargetPriority::High)
+            .with_role("button")
     }
 
     /// Creates a list item target.
@@ -478,6 +1241,7 @@ impl TargetBuilder {
             .with_label(label)
             .with_action(TargetAction::Select)
             .with_priority(TargetPriority::Normal)
+            .with_role("list_item")
     }
 
     /// Creates a tab target with high priority.
@@ -487,6 +1251,7 @@ impl TargetBuilder {
             .with_action(TargetAction::Activate)
             .with_priority(TargetPriority::High)
             .with_group("tabs")
+            .with_role("tab")
     }
 
     /// Creates a tree node target that can be expanded/collapsed.
@@ -494,7 +1259,8 @@ impl TargetBuilder {
         let mut target = NavTarget::new(self.next_id(), rect)
             .with_label(label)
             .with_action(TargetAction::Activate)
-            .with_priority(TargetPriority::Normal);
+            .with_priority(TargetPriority::Normal)
+            .with_role("tree_node");
 
         target
             .metadata
@@ -513,6 +1279,7 @@ impl TargetBuilder {
             .with_label(label)
             .with_action(TargetAction::Navigate(route.into()))
             .with_priority(TargetPriority::Normal)
+            .with_role("link")
     }
 
     /// Creates a custom target with specified action and priority.
@@ -578,4 +1345,260 @@ mod tests {
         registry.clear();
         assert!(registry.is_empty());
     }
+
+    #[test]
+    fn test_topmost_at_prefers_later_targets() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 20, 10)));
+        registry.register(NavTarget::new(2, Rect::new(2, 2, 5, 1)));
+
+        assert_eq!(registry.topmost_at(3, 2).map(|t| t.id), Some(2));
+        assert_eq!(registry.topmost_at(0, 0).map(|t| t.id), Some(1));
+        assert!(registry.topmost_at(30, 30).is_none());
+    }
+
+    #[test]
+    fn test_state_override_survives_clear() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+        registry.set_state_override(1, TargetState::Highlighted);
+        assert_eq!(registry.by_id(1).unwrap().state, TargetState::Highlighted);
+
+        registry.clear();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+        assert_eq!(registry.by_id(1).unwrap().state, TargetState::Highlighted);
+
+        registry.clear_state_override(1);
+        assert_eq!(registry.by_id(1).unwrap().state, TargetState::Normal);
+    }
+
+    #[test]
+    fn test_state_override_follows_key_not_id() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_label("Save"));
+        registry.set_state_override(1, TargetState::Selected);
+
+        // IDs are regenerated: "Quit" now has the ID "Save" had
+        registry.clear();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_label("Quit"));
+        registry.register(NavTarget::new(2, Rect::new(0, 1, 10, 1)).with_label("Save"));
+        assert_eq!(registry.by_id(1).unwrap().state, TargetState::Normal);
+        assert_eq!(registry.by_id(2).unwrap().state, TargetState::Selected);
+        assert_eq!(registry.state_override(2), Some(TargetState::Selected));
+    }
+
+    #[test]
+    fn test_target_callback_precedes_group_callback() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_group("sidebar"));
+        registry.register(NavTarget::new(2, Rect::new(0, 1, 10, 1)).with_group("sidebar"));
+        registry.register(NavTarget::new(3, Rect::new(0, 2, 10, 1)));
+
+        registry.on_group_activate("sidebar", |_| false);
+        registry.on_activate(1, |_| true);
+
+        assert_eq!(registry.invoke_callback(1), Some(true));
+        assert_eq!(registry.invoke_callback(2), Some(false));
+        assert_eq!(registry.invoke_callback(3), None);
+    }
+
+    #[test]
+    fn test_clear_keeps_group_callbacks() {
+        let mut registry = TargetRegistry::new();
+        registry.register_with_callback(
+            NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_group("tabs"),
+            |_| true,
+        );
+        registry.on_group_activate("tabs", |_| true);
+
+        registry.clear();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+        assert_eq!(registry.invoke_callback(1), None);
+
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_group("tabs"));
+        assert_eq!(registry.invoke_callback(1), Some(true));
+    }
+
+    #[test]
+    fn test_builder_sets_roles() {
+        let mut builder = TargetBuilder::new();
+        let rect = Rect::new(0, 0, 10, 1);
+        assert_eq!(builder.button(rect, "Save").role(), Some("button"));
+        assert_eq!(builder.tab(rect, "Logs").role(), Some("tab"));
+        assert_eq!(builder.link(rect, "Help", "/help").role(), Some("link"));
+        assert_eq!(NavTarget::new(9, rect).role(), None);
+    }
+
+    #[test]
+    fn test_diff_matches_targets_by_key() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_label("Inbox"));
+        registry.register(
+            NavTarget::new(2, Rect::new(0, 1, 10, 1))
+                .with_key("unread")
+                .with_label("Unread (3)"),
+        );
+        registry.register(NavTarget::new(3, Rect::new(0, 2, 10, 1)).with_label("Spam"));
+
+        registry.clear();
+        registry.register(NavTarget::new(1, Rect::new(0, 1, 10, 1)).with_label("Inbox"));
+        registry.register(
+            NavTarget::new(2, Rect::new(0, 2, 10, 1))
+                .with_key("unread")
+                .with_label("Unread (4)"),
+        );
+        registry.register(NavTarget::new(3, Rect::new(0, 3, 10, 1)).with_label("Trash"));
+
+        let diff = registry.diff();
+        let inbox = NavTarget::new(9, Rect::default())
+            .with_label("Inbox")
+            .stable_key();
+        let unread = TargetKey::Explicit("unread".into());
+        assert_eq!(diff.added, vec![registry.key_of(3).unwrap().clone()]);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(
+            diff.moved.iter().map(|m| &m.key).collect::<Vec<_>>(),
+            vec![&inbox, &unread]
+        );
+        assert_eq!(
+            diff.relabelled,
+            vec![TargetRelabel {
+                key: unread,
+                from: Some("Unread (3)".into()),
+                to: Some("Unread (4)".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_remap_id_follows_regenerated_ids() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_label("Open"));
+        registry.register(NavTarget::new(2, Rect::new(0, 1, 10, 1)).with_label("Save"));
+
+        registry.clear();
+        registry.register(NavTarget::new(7, Rect::new(0, 0, 10, 1)).with_label("Save"));
+
+        assert_eq!(registry.remap_id(2), Some(7));
+        assert_eq!(registry.remap_id(1), None);
+        assert_eq!(registry.by_key(registry.key_of(7).unwrap()).unwrap().id, 7);
+        assert_eq!(registry.diff().moved.len(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_keys_pair_in_order() {
+        let mut registry = TargetRegistry::new();
+        for id in 1..=2 {
+            registry.register(NavTarget::new(id, Rect::new(0, id as u16, 4, 1)).with_label("OK"));
+        }
+        assert_ne!(registry.key_of(1), registry.key_of(2));
+
+        registry.clear();
+        for id in 11..=12 {
+            registry
+                .register(NavTarget::new(id, Rect::new(0, id as u16 - 10, 4, 1)).with_label("OK"));
+        }
+
+        assert!(registry.diff().is_empty());
+        assert_eq!(registry.remap_id(2), Some(12));
+
+        // A removed duplicate frees its key for the next target
+        let second = registry.key_of(12).cloned();
+        registry.remove(12);
+        registry.register(NavTarget::new(13, Rect::new(0, 3, 4, 1)).with_label("OK"));
+        assert_eq!(registry.key_of(13), second.as_ref());
+        registry.remove(11);
+        registry.remove(13);
+        registry.register(NavTarget::new(14, Rect::new(0, 4, 4, 1)).with_label("OK"));
+        assert_eq!(
+            registry.key_of(14),
+            Some(&registry.by_id(14).unwrap().stable_key())
+        );
+    }
+
+    #[test]
+    fn test_hierarchy_queries() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 40, 10)));
+        registry.register(NavTarget::new(2, Rect::new(1, 1, 38, 1)).with_parent(1));
+        registry.register(NavTarget::new(3, Rect::new(1, 2, 38, 1)).with_parent(1));
+        registry.register(NavTarget::new(4, Rect::new(2, 2, 4, 1)).with_parent(3));
+        registry.register(NavTarget::new(5, Rect::new(0, 12, 10, 1)).with_parent(99));
+
+        let ids = |targets: Vec<&NavTarget>| targets.iter().map(|t| t.id).collect::<Vec<_>>();
+        assert_eq!(ids(registry.children_of(1)), vec![2, 3]);
+        assert_eq!(ids(registry.ancestors(4)), vec![3, 1]);
+        assert_eq!(ids(registry.scope(1)), vec![2, 3, 4]);
+        assert_eq!(ids(registry.roots()), vec![1, 5]);
+        assert!(registry.ancestors(5).is_empty());
+    }
+
+    #[test]
+    fn test_ancestors_stop_at_cycles() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 4, 1)).with_parent(2));
+        registry.register(NavTarget::new(2, Rect::new(0, 1, 4, 1)).with_parent(1));
+
+        assert_eq!(registry.ancestors(1).len(), 1);
+        assert_eq!(registry.scope(1).len(), 1);
+    }
+
+    #[test]
+    fn test_offset_and_clip_stack() {
+        let mut registry = TargetRegistry::new();
+        registry.push_offset(10, 5);
+        registry.push_clip(Rect::new(0, 0, 20, 4));
+        registry.push_offset(0, -2);
+
+        // Partly scrolled out at the top
+        registry.register(NavTarget::new(1, Rect::new(2, 1, 5, 2)));
+        assert_eq!(registry.by_id(1).unwrap().rect, Rect::new(12, 5, 5, 1));
+        // Cut at the right edge of the clip
+        registry.register(NavTarget::new(2, Rect::new(15, 3, 10, 1)));
+        assert_eq!(registry.by_id(2).unwrap().rect, Rect::new(25, 6, 5, 1));
+
+        assert!(registry.pop());
+        registry.register(NavTarget::new(3, Rect::new(0, 0, 4, 1)));
+        assert_eq!(registry.by_id(3).unwrap().rect, Rect::new(10, 5, 4, 1));
+        assert!(registry.pop());
+        registry.register(NavTarget::new(4, Rect::new(40, 0, 4, 1)));
+        assert_eq!(registry.by_id(4).unwrap().rect, Rect::new(50, 5, 4, 1));
+        assert!(registry.pop());
+        assert!(!registry.pop());
+
+        registry.register(NavTarget::new(5, Rect::new(40, 0, 4, 1)));
+        assert_eq!(registry.by_id(5).unwrap().rect, Rect::new(40, 0, 4, 1));
+        assert_eq!(registry.at_point(12, 5)[0].id, 1);
+    }
+
+    #[test]
+    fn test_clipped_targets_go_offscreen() {
+        let mut registry = TargetRegistry::new();
+        registry.push_clip(Rect::new(10, 10, 10, 10));
+        registry.register(NavTarget::new(1, Rect::new(12, 2, 4, 1)));
+        registry.register(NavTarget::new(2, Rect::new(12, 20, 4, 1)));
+        registry.register(NavTarget::new(3, Rect::new(0, 12, 10, 1)));
+        registry.register(NavTarget::new(4, Rect::new(20, 12, 4, 1)));
+        registry.register(NavTarget::new(5, Rect::new(12, 12, 4, 1)));
+
+        let offscreen: Vec<(u64, ScrollDirection)> = registry
+            .offscreen()
+            .iter()
+            .map(|o| (o.target.id, o.direction))
+            .collect();
+        assert_eq!(
+            offscreen,
+            vec![
+                (1, ScrollDirection::Up),
+                (2, ScrollDirection::Down),
+                (3, ScrollDirection::Left),
+                (4, ScrollDirection::Right),
+            ]
+        );
+        assert_eq!(registry.len(), 1);
+
+        registry.clear();
+        assert!(registry.offscreen().is_empty());
+        assert!(!registry.pop());
+    }
 }
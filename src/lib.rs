@@ -39,5 +39,6 @@ pub mod core;
 pub mod plugins;
 pub mod prelude;
 pub mod ratatui_ext;
+pub mod testing;
 
 pub use core::{Locust, LocustConfig, LocustContext};
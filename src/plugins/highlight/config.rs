@@ -45,8 +45,10 @@ impl Default for HighlightAnimation {
 /// Configuration for the highlight plugin.
 #[derive(Debug, Clone)]
 pub struct HighlightConfig {
-    /// Activation key to start a tour (default: '?')
-    pub activation_key: char,
+    /// Activation key to start a tour, replacing the `highlight.start`
+    /// keymap binding ('?') when the plugin is registered
+    /// (default: None, use the keymap)
+    pub activation_key: Option<char>,
 
     /// Opacity of the dim overlay (0.0 = transparent, 1.0 = opaque)
     /// Represented as a u8 from 0-255 for simplicity
@@ -107,7 +109,7 @@ pub struct HighlightConfig {
 impl Default for HighlightConfig {
     fn default() -> Self {
         Self {
-            activation_key: '?',
+            activation_key: None,
             dim_opacity: 180, // ~70% opacity
             dim_color: Color::Black,
             border_style: HighlightBorderStyle::default(),
@@ -142,7 +144,7 @@ impl HighlightConfig {
 
     /// Sets the activation key.
     pub fn with_activation_key(mut self, key: char) -> Self {
-        self.activation_key = key;
+        self.activation_key = Some(key);
         self
     }
 
@@ -226,7 +228,7 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = HighlightConfig::default();
-        assert_eq!(config.activation_key, '?');
+        assert_eq!(config.activation_key, None);
         assert_eq!(config.dim_opacity, 180);
         assert_eq!(config.border_style, HighlightBorderStyle::Rounded);
         assert!(config.show_navigation_hints);
@@ -242,7 +244,7 @@ mod tests {
             .with_navigation_hints(false)
             .with_z_index(300);
 
-        assert_eq!(config.activation_key, 'h');
+        assert_eq!(config.activation_key, Some('h'));
         assert_eq!(config.dim_opacity, 200);
         assert_eq!(config.border_style, HighlightBorderStyle::Double);
         assert!(!config.show_navigation_hints);
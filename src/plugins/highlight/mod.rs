@@ -50,15 +50,20 @@ pub use tour::{MessagePosition, Tour, TourState, TourStep};
 
 use crate::core::context::LocustContext;
 use crate::core::input::PluginEventResult;
+use crate::core::modes::{Mode, ModeFrame};
 use crate::core::overlay::OverlayLayer;
 use crate::core::plugin::LocustPlugin;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crate::plugins::BUILTIN_PRIORITY;
+use crossterm::event::Event;
 use ratatui::backend::Backend;
 use ratatui::Frame;
 use render::HighlightRenderer;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+/// Plugin ID, also the owner of the tour mode on the mode stack.
+const PLUGIN_ID: &str = "locust.highlight";
+
 /// Highlight plugin for guided tours and spotlights.
 ///
 /// This plugin manages multiple tours and displays them with visual
@@ -96,6 +101,9 @@ pub struct HighlightPlugin {
 
     /// Last animation tick time
     last_tick: Option<Instant>,
+
+    /// Index of the current tour step and when it was shown
+    step_timer: Option<(usize, Instant)>,
 }
 
 impl Default for HighlightPlugin {
@@ -119,6 +127,7 @@ impl HighlightPlugin {
             renderer: HighlightRenderer::new(),
             completed_tours: Vec::new(),
             last_tick: None,
+            step_timer: None,
         }
     }
 
@@ -146,6 +155,17 @@ impl HighlightPlugin {
             }
 
             tour.start();
+            for step in &mut tour.steps {
+                step.bind_target_key(&ctx.targets);
+            }
+            // Unskippable tours cannot be left with the pop binding
+            ctx.modes.remove(&Mode::Tour, PLUGIN_ID);
+            ctx.modes
+                .push_frame(ModeFrame::new(Mode::Tour, PLUGIN_ID).escapable(tour.skippable));
+
+            let now = ctx.now();
+            self.step_timer = Some((tour.current_index(), now));
+            self.last_tick = Some(now);
             self.active_tour_id = Some(tour_id.to_string());
             ctx.overlay.mark_has_overlay();
 
@@ -176,6 +196,8 @@ impl HighlightPlugin {
         }
 
         self.active_tour_id = None;
+        self.step_timer = None;
+        ctx.modes.remove(&Mode::Tour, PLUGIN_ID);
         ctx.overlay.remove_layer("locust.highlight");
     }
 
@@ -203,38 +225,87 @@ impl HighlightPlugin {
         self.tours.keys().cloned().collect()
     }
 
-    /// Advances to the next step in the active tour.
-    fn next_tour_step(&mut self, ctx: &mut LocustContext) {
+    /// Advances to the next step in the active tour at `now`.
+    fn next_tour_step(&mut self, now: Instant, ctx: &mut LocustContext) {
         if let Some(tour) = self.active_tour_mut() {
             if !tour.next_step() {
                 // Tour ended
                 self.stop_tour(ctx);
+                return;
             }
         }
+        self.restart_step_timer(now);
     }
 
-    /// Goes back to the previous step in the active tour.
-    fn previous_tour_step(&mut self) {
+    /// Goes back to the previous step in the active tour at `now`.
+    fn previous_tour_step(&mut self, now: Instant) {
         if let Some(tour) = self.active_tour_mut() {
             tour.previous_step();
         }
+        self.restart_step_timer(now);
     }
 
-    /// Updates animation state.
-    fn update_animation(&mut self) {
-        let now = Instant::now();
+    /// Starts timing the current step of the active tour from `now`.
+    fn restart_step_timer(&mut self, now: Instant) {
+        self.step_timer = self.active_tour().map(|t| (t.current_index(), now));
+    }
+
+    /// Whether the active tour has an animated highlight.
+    fn is_animating(&self) -> bool {
+        self.config.animation != HighlightAnimation::None
+            && self.active_tour().is_some_and(|t| t.is_active())
+    }
 
+    /// Updates animation state.
+    ///
+    /// Returns `true` if the animation advanced a frame.
+    fn update_animation(&mut self, now: Instant) -> bool {
         if let Some(last_tick) = self.last_tick {
-            let elapsed = now.duration_since(last_tick);
+            let elapsed = now.saturating_duration_since(last_tick);
             let tick_duration = Duration::from_millis(self.config.animation_speed_ms);
 
             if elapsed >= tick_duration {
                 self.renderer.tick();
                 self.last_tick = Some(now);
+                return true;
             }
         } else {
             self.last_tick = Some(now);
         }
+
+        false
+    }
+
+    /// When the current tour step should auto-advance, if it has a timeout.
+    fn auto_advance_deadline(&self) -> Option<Instant> {
+        let tour = self.active_tour().filter(|t| t.is_active())?;
+        let ms = tour.current_step()?.auto_advance_ms;
+        match self.step_timer {
+            Some((index, started_at)) if ms > 0 && index == tour.current_index() => {
+                Some(started_at + Duration::from_millis(ms))
+            }
+            _ => None,
+        }
+    }
+
+    /// Advances the active tour if the current step's timeout has elapsed.
+    fn check_auto_advance(&mut self, now: Instant, ctx: &mut LocustContext) -> bool {
+        let Some(index) = self.active_tour().map(|t| t.current_index()) else {
+            return false;
+        };
+
+        // Restart the step timer whenever the step changed since the last tick
+        if self.step_timer.is_none_or(|(i, _)| i != index) {
+            self.step_timer = Some((index, now));
+        }
+
+        match self.auto_advance_deadline() {
+            Some(deadline) if now >= deadline => {
+                self.next_tour_step(now, ctx);
+                true
+            }
+            _ => false,
+        }
     }
 }
 
@@ -243,64 +314,62 @@ where
     B: Backend + 'static,
 {
     fn id(&self) -> &'static str {
-        "locust.highlight"
+        PLUGIN_ID
     }
 
     fn priority(&self) -> i32 {
-        30 // Higher priority than nav and omnibar
+        BUILTIN_PRIORITY
     }
 
-    fn init(&mut self, _ctx: &mut LocustContext) {
-        // Plugin is initialized
+    fn before(&self) -> &'static [&'static str] {
+        // A running tour takes keys before every other built-in plugin
+        &["locust.omnibar", "locust.tooltip", "locust.nav"]
+    }
+
+    fn init(&mut self, ctx: &mut LocustContext) {
+        ctx.bind_configured_key("highlight.start", self.config.activation_key);
     }
 
     fn on_event(&mut self, event: &Event, ctx: &mut LocustContext) -> PluginEventResult {
-        // Update animation
-        self.update_animation();
+        if let Event::Key(key) = event {
+            let action = ctx.plugin_action("highlight", key);
 
-        if let Event::Key(KeyEvent {
-            code, modifiers, ..
-        }) = event
-        {
             // If no active tour, check for activation
             if self.active_tour_id.is_none() {
-                if let KeyCode::Char(c) = code {
-                    if *c == self.config.activation_key && *modifiers == KeyModifiers::NONE {
-                        // Start first available tour (or could show tour selection)
-                        if let Some(tour_id) = self.tours.keys().next().cloned() {
-                            self.start_tour(&tour_id, ctx);
-                            return PluginEventResult::ConsumedRequestRedraw;
-                        }
+                if action.as_deref() == Some("highlight.start") && !ctx.is_capturing_input() {
+                    // Start first available tour (or could show tour selection)
+                    if let Some(tour_id) = self.tours.keys().next().cloned() {
+                        self.start_tour(&tour_id, ctx);
+                        return PluginEventResult::ConsumedRequestRedraw;
                     }
                 }
                 return PluginEventResult::NotHandled;
             }
 
+            // Leave keys alone while another plugin's mode is on top
+            if !ctx.modes.is_current(&Mode::Tour) {
+                return PluginEventResult::NotHandled;
+            }
+
             // Handle tour navigation
             let tour_skippable = self.active_tour().map(|t| t.skippable).unwrap_or(true);
 
-            match code {
+            match action.as_deref() {
                 // Skip tour
-                KeyCode::Esc if tour_skippable => {
+                Some("highlight.skip_tour") if tour_skippable => {
                     self.stop_tour(ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
-                // Next step
-                KeyCode::Right | KeyCode::Char('n') if *modifiers == KeyModifiers::NONE => {
-                    self.next_tour_step(ctx);
+                // Next step, or finish on the last one
+                Some("highlight.next_step" | "highlight.advance") => {
+                    self.next_tour_step(ctx.now(), ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Previous step
-                KeyCode::Left | KeyCode::Char('p') if *modifiers == KeyModifiers::NONE => {
-                    self.previous_tour_step();
-                    return PluginEventResult::ConsumedRequestRedraw;
-                }
-
-                // Enter to advance or finish
-                KeyCode::Enter => {
-                    self.next_tour_step(ctx);
+                Some("highlight.previous_step") => {
+                    self.previous_tour_step(ctx.now());
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
@@ -311,6 +380,40 @@ where
         PluginEventResult::NotHandled
     }
 
+    fn on_tick(&mut self, now: Instant, ctx: &mut LocustContext) -> bool {
+        // Steps whose target appeared after the tour started follow it from now on
+        if let Some(tour) = self
+            .active_tour_id
+            .as_ref()
+            .and_then(|id| self.tours.get_mut(id))
+        {
+            for step in &mut tour.steps {
+                step.bind_target_key(&ctx.targets);
+            }
+        }
+        let advanced = self.check_auto_advance(now, ctx);
+        let animated = self.is_animating() && self.update_animation(now);
+        advanced || animated
+    }
+
+    fn next_deadline(&self, _ctx: &LocustContext) -> Option<Instant> {
+        let animation = self
+            .last_tick
+            .filter(|_| self.is_animating())
+            .map(|t| t + Duration::from_millis(self.config.animation_speed_ms));
+
+        match (self.auto_advance_deadline(), animation) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn on_mode_exit(&mut self, mode: &Mode, ctx: &mut LocustContext) {
+        if *mode == Mode::Tour {
+            self.stop_tour(ctx);
+        }
+    }
+
     fn render_overlay(&self, frame: &mut Frame, ctx: &LocustContext) {
         if let Some(tour) = self.active_tour() {
             if tour.is_active() {
@@ -341,7 +444,7 @@ mod tests {
             .with_activation_key('h')
             .with_dim_opacity(200);
         let plugin = HighlightPlugin::with_config(config);
-        assert_eq!(plugin.config().activation_key, 'h');
+        assert_eq!(plugin.config().activation_key, Some('h'));
         assert_eq!(plugin.config().dim_opacity, 200);
     }
 
@@ -387,11 +490,11 @@ mod tests {
         plugin.start_tour("test", &mut ctx);
 
         // Navigate forward
-        plugin.next_tour_step(&mut ctx);
+        plugin.next_tour_step(Instant::now(), &mut ctx);
         assert_eq!(plugin.active_tour().unwrap().current_step, 1);
 
         // Navigate backward
-        plugin.previous_tour_step();
+        plugin.previous_tour_step(Instant::now());
         assert_eq!(plugin.active_tour().unwrap().current_step, 0);
     }
 
@@ -406,18 +509,148 @@ mod tests {
         plugin.start_tour("test", &mut ctx);
 
         // Complete the tour
-        plugin.next_tour_step(&mut ctx);
+        plugin.next_tour_step(Instant::now(), &mut ctx);
 
         // Should be marked as completed
         assert!(plugin.is_tour_completed("test"));
         assert!(plugin.active_tour_id.is_none());
     }
 
+    #[test]
+    fn test_tick_auto_advances_tour_step() {
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = HighlightPlugin::with_config(
+            HighlightConfig::new().with_animation(HighlightAnimation::None),
+        );
+        let mut ctx = LocustContext::default();
+
+        let tour = Tour::new("test")
+            .add_step(TourStep::new("Step 1", "First").with_auto_advance(1000))
+            .add_step(TourStep::new("Step 2", "Second"));
+        plugin.register_tour(tour);
+        plugin.start_tour("test", &mut ctx);
+
+        let (_, started_at) = plugin.step_timer.unwrap();
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            Some(started_at + Duration::from_millis(1000))
+        );
+
+        let before = started_at + Duration::from_millis(999);
+        assert!(!LocustPlugin::<TestBackend>::on_tick(
+            &mut plugin,
+            before,
+            &mut ctx
+        ));
+        assert_eq!(plugin.active_tour().unwrap().current_index(), 0);
+
+        let after = started_at + Duration::from_millis(1000);
+        assert!(LocustPlugin::<TestBackend>::on_tick(
+            &mut plugin,
+            after,
+            &mut ctx
+        ));
+        assert_eq!(plugin.active_tour().unwrap().current_index(), 1);
+
+        // The second step has no timeout and animation is off
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tick_advances_animation() {
+        use ratatui::backend::TestBackend;
+
+        let mut plugin =
+            HighlightPlugin::with_config(HighlightConfig::new().with_animation_speed(500));
+        let mut ctx = LocustContext::default();
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            None
+        );
+
+        plugin.register_tour(Tour::new("test").add_step(TourStep::new("Step 1", "First")));
+        plugin.start_tour("test", &mut ctx);
+
+        let last_tick = plugin.last_tick.unwrap();
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            Some(last_tick + Duration::from_millis(500))
+        );
+        assert!(LocustPlugin::<TestBackend>::on_tick(
+            &mut plugin,
+            last_tick + Duration::from_millis(500),
+            &mut ctx
+        ));
+    }
+
+    #[test]
+    fn test_skip_key_from_keymap_stops_tour() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = HighlightPlugin::new();
+        let mut ctx = LocustContext::default();
+        plugin.register_tour(Tour::new("test").add_step(TourStep::new("Step 1", "First")));
+
+        let key = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &key('?'), &mut ctx);
+        assert_eq!(plugin.active_tour_id.as_deref(), Some("test"));
+
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &key('s'), &mut ctx);
+        assert!(plugin.active_tour_id.is_none());
+    }
+
+    #[test]
+    fn test_tour_arrow_keys_follow_keymap() {
+        use crate::core::keybindings::{KeyBinding, KeyCodeDef};
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = HighlightPlugin::new();
+        let mut ctx = LocustContext::default();
+        plugin.register_tour(
+            Tour::new("test")
+                .add_step(TourStep::new("Step 1", "First"))
+                .add_step(TourStep::new("Step 2", "Second"))
+                .add_step(TourStep::new("Step 3", "Third")),
+        );
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        plugin.start_tour("test", &mut ctx);
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &key(KeyCode::Right), &mut ctx);
+        assert_eq!(plugin.active_tour().unwrap().current_step, 1);
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &key(KeyCode::Left), &mut ctx);
+        assert_eq!(plugin.active_tour().unwrap().current_step, 0);
+
+        // Remapped in the tour mode's section, Right no longer advances
+        ctx.keymap
+            .bind_in_mode(
+                "tour",
+                "highlight.next_step",
+                KeyBinding::new(KeyCodeDef::Char('l')),
+            )
+            .unwrap();
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &key(KeyCode::Right), &mut ctx);
+        assert_eq!(plugin.active_tour().unwrap().current_step, 0);
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &key(KeyCode::Char('l')), &mut ctx);
+        assert_eq!(plugin.active_tour().unwrap().current_step, 1);
+
+        ctx.keymap.unbind("highlight.skip_tour");
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &key(KeyCode::Esc), &mut ctx);
+        assert_eq!(plugin.active_tour_id.as_deref(), Some("test"));
+    }
+
     #[test]
     fn test_plugin_priority() {
         let plugin = HighlightPlugin::new();
         use ratatui::backend::TestBackend;
-        // Highlight should have higher priority than nav (50) and omnibar (40)
-        assert!(<HighlightPlugin as LocustPlugin<TestBackend>>::priority(&plugin) < 40);
+        // Highlight should see events before nav and omnibar
+        let before = <HighlightPlugin as LocustPlugin<TestBackend>>::before(&plugin);
+        assert!(before.contains(&"locust.nav"));
+        assert!(before.contains(&"locust.omnibar"));
     }
 }
@@ -1,5 +1,6 @@
 //! Tour system for multi-step guided highlights.
 
+use crate::core::targets::{TargetKey, TargetRegistry};
 use ratatui::layout::Rect;
 use std::collections::HashMap;
 
@@ -30,6 +31,10 @@ pub struct TourStep {
     /// Optional target ID to highlight (from NavTarget registry)
     pub target_id: Option<u64>,
 
+    /// Key of the target to highlight; takes precedence over `target_id`
+    /// and survives the application regenerating IDs
+    pub target_key: Option<TargetKey>,
+
     /// Optional explicit highlight area (if target_id is None)
     pub highlight_area: Option<Rect>,
 
@@ -54,6 +59,7 @@ impl TourStep {
     pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
             target_id: None,
+            target_key: None,
             highlight_area: None,
             title: title.into(),
             message: message.into(),
@@ -64,8 +70,22 @@ impl TourStep {
     }
 
     /// Sets the target ID to highlight (from NavTarget registry).
+    ///
+    /// The highlight plugin follows the target by key from the frame the
+    /// tour starts in, or the first frame the target appears in after that.
     pub fn with_target(mut self, target_id: u64) -> Self {
         self.target_id = Some(target_id);
+        self.target_key = None;
+        self.highlight_area = None; // Clear explicit area
+        self
+    }
+
+    /// Sets the key of the target to highlight, such as
+    /// `TargetKey::Explicit("save".into())` for a target registered with
+    /// `with_key("save")`.
+    pub fn with_target_key(mut self, key: TargetKey) -> Self {
+        self.target_key = Some(key);
+        self.target_id = None;
         self.highlight_area = None; // Clear explicit area
         self
     }
@@ -74,6 +94,7 @@ impl TourStep {
     pub fn with_area(mut self, area: Rect) -> Self {
         self.highlight_area = Some(area);
         self.target_id = None; // Clear target ID
+        self.target_key = None;
         self
     }
 
@@ -96,17 +117,28 @@ impl TourStep {
     }
 
     /// Gets the highlight rect for this step, either from target or explicit area.
-    pub fn highlight_rect(
-        &self,
-        registry: Option<&crate::core::targets::TargetRegistry>,
-    ) -> Option<Rect> {
-        if let Some(target_id) = self.target_id {
-            if let Some(registry) = registry {
+    pub fn highlight_rect(&self, registry: Option<&TargetRegistry>) -> Option<Rect> {
+        if let Some(registry) = registry {
+            if let Some(key) = &self.target_key {
+                return registry.by_key(key).map(|t| t.rect);
+            }
+            if let Some(target_id) = self.target_id {
                 return registry.by_id(target_id).map(|t| t.rect);
             }
         }
         self.highlight_area
     }
+
+    /// Remembers the key of the step's target if it is registered, so the
+    /// step keeps following it when its ID changes.
+    pub(crate) fn bind_target_key(&mut self, registry: &TargetRegistry) {
+        if self.target_key.is_some() {
+            return;
+        }
+        if let Some(target_id) = self.target_id {
+            self.target_key = registry.key_of(target_id).cloned();
+        }
+    }
 }
 
 /// State of a tour.
@@ -455,4 +487,22 @@ mod tests {
         assert_eq!(step.highlight_area, Some(rect));
         assert_eq!(step.target_id, None);
     }
+
+    #[test]
+    fn test_bound_step_follows_regenerated_ids() {
+        use crate::core::targets::NavTarget;
+
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(1, Rect::new(0, 0, 5, 1)).with_label("Save"));
+        let mut step = TourStep::new("Save", "Saves the file").with_target(1);
+        step.bind_target_key(&registry);
+
+        registry.clear();
+        registry.register(NavTarget::new(1, Rect::new(0, 4, 5, 1)).with_label("Quit"));
+        registry.register(NavTarget::new(2, Rect::new(0, 2, 5, 1)).with_label("Save"));
+        assert_eq!(
+            step.highlight_rect(Some(&registry)),
+            Some(Rect::new(0, 2, 5, 1))
+        );
+    }
 }
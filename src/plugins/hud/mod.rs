@@ -0,0 +1,142 @@
+//! Locust metrics HUD plugin - Live plugin timings on screen.
+//!
+//! The HUD shows the rolling `on_event` and render percentiles of every
+//! plugin from `ctx.metrics` (see [`metrics`](crate::core::metrics)),
+//! along with the frame count, the targets of the last frame and
+//! `total_overlay_frames`. It is toggled with the `hud.toggle` keymap
+//! action (F12 by default) and turns metrics collection on when shown.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::prelude::*;
+//! use ratatui::backend::TestBackend;
+//!
+//! let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+//! locust.register_plugin(NavPlugin::new());
+//! locust.register_plugin(MetricsHudPlugin::new());
+//!
+//! locust.on_event(&Event::Key(KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE)));
+//! assert!(locust.ctx.metrics.enabled);
+//! ```
+
+pub mod render;
+
+use crate::core::context::LocustContext;
+use crate::core::input::PluginEventResult;
+use crate::core::overlay::OverlayLayer;
+use crate::core::plugin::LocustPlugin;
+use crate::plugins::BUILTIN_PRIORITY;
+use crossterm::event::Event;
+use ratatui::backend::Backend;
+use ratatui::Frame;
+
+/// Plugin ID.
+const PLUGIN_ID: &str = "locust.hud";
+
+/// Z-index of the HUD, above modal dialogs.
+const HUD_Z_INDEX: i32 = 300;
+
+/// Overlay showing per-plugin timings.
+#[derive(Debug, Default)]
+pub struct MetricsHudPlugin {
+    visible: bool,
+}
+
+impl MetricsHudPlugin {
+    /// Creates a hidden HUD.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the HUD starts visible.
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Returns whether the HUD is shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Shows or hides the HUD. Showing it enables metrics collection;
+    /// hiding it leaves collection as it is.
+    pub fn set_visible(&mut self, visible: bool, ctx: &mut LocustContext) {
+        self.visible = visible;
+        if visible {
+            ctx.metrics.enabled = true;
+        }
+    }
+}
+
+impl<B> LocustPlugin<B> for MetricsHudPlugin
+where
+    B: Backend + 'static,
+{
+    fn id(&self) -> &'static str {
+        PLUGIN_ID
+    }
+
+    fn priority(&self) -> i32 {
+        BUILTIN_PRIORITY
+    }
+
+    fn init(&mut self, ctx: &mut LocustContext) {
+        ctx.overlay
+            .add_layer(OverlayLayer::new(PLUGIN_ID, HUD_Z_INDEX));
+        let visible = self.visible;
+        self.set_visible(visible, ctx);
+    }
+
+    fn on_event(&mut self, event: &Event, ctx: &mut LocustContext) -> PluginEventResult {
+        let Event::Key(key) = event else {
+            return PluginEventResult::NotHandled;
+        };
+        if ctx.plugin_action("hud", key).as_deref() != Some("hud.toggle") {
+            return PluginEventResult::NotHandled;
+        }
+
+        let visible = !self.visible;
+        self.set_visible(visible, ctx);
+        PluginEventResult::ConsumedRequestRedraw
+    }
+
+    fn render_overlay(&self, frame: &mut Frame, ctx: &LocustContext) {
+        if self.visible {
+            render::render_hud(frame, ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_toggle_action() {
+        let mut hud = MetricsHudPlugin::new();
+        let mut ctx = LocustContext::default();
+        LocustPlugin::<TestBackend>::init(&mut hud, &mut ctx);
+        assert!(!ctx.metrics.enabled);
+
+        let result =
+            LocustPlugin::<TestBackend>::on_event(&mut hud, &key(KeyCode::F(12)), &mut ctx);
+        assert!(result.is_consumed());
+        assert!(hud.is_visible());
+        assert!(ctx.metrics.enabled);
+
+        LocustPlugin::<TestBackend>::on_event(&mut hud, &key(KeyCode::F(12)), &mut ctx);
+        assert!(!hud.is_visible());
+
+        let result =
+            LocustPlugin::<TestBackend>::on_event(&mut hud, &key(KeyCode::Char('x')), &mut ctx);
+        assert!(!result.is_consumed());
+    }
+}
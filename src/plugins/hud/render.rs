@@ -0,0 +1,122 @@
+//! Metrics HUD rendering.
+//!
+//! The HUD is a bordered table in the top-right corner of the frame: one
+//! row per plugin with its `on_event` and render p50/p99, then a line of
+//! frame counters.
+
+use crate::core::context::LocustContext;
+use crate::core::metrics::PluginPhase;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+use std::time::Duration;
+
+/// Formats a duration compactly: `850µs`, `12.5ms`, `1.20s`.
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+/// The HUD's text, one string per row.
+pub fn hud_lines(ctx: &LocustContext) -> Vec<String> {
+    let metrics = &ctx.metrics;
+    let ids = metrics.plugin_ids();
+    let id_width = ids.iter().map(|id| id.len()).max().unwrap_or(0).max(6);
+
+    let column = |id: &str, phase| match metrics.percentiles(id, phase) {
+        Some(p) => format!("{}/{}", format_duration(p.p50), format_duration(p.p99)),
+        None => "-".to_string(),
+    };
+
+    let mut lines = vec![format!(
+        "{:<id_width$}  {:<15}  {:<15}",
+        "plugin", "event p50/p99", "render p50/p99"
+    )];
+    for id in ids {
+        lines.push(format!(
+            "{:<id_width$}  {:<15}  {:<15}",
+            id,
+            column(id, PluginPhase::Event),
+            column(id, PluginPhase::Render)
+        ));
+    }
+
+    let targets = metrics
+        .frame_targets()
+        .map_or_else(|| "-".to_string(), |n| n.to_string());
+    lines.push(format!(
+        "frame {}  targets {}  overlay frames {}",
+        ctx.frame_count, targets, ctx.overlay.total_overlay_frames
+    ));
+    lines
+        .into_iter()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+/// Draws the HUD in the top-right corner, clipped to the frame.
+pub fn render_hud(frame: &mut Frame, ctx: &LocustContext) {
+    let lines = hud_lines(ctx);
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16 + 2;
+    let height = lines.len() as u16 + 2;
+
+    let screen = frame.area();
+    let width = width.min(screen.width);
+    let area = Rect::new(
+        screen.right().saturating_sub(width),
+        screen.y,
+        width,
+        height.min(screen.height),
+    );
+
+    let text: Vec<Line> = lines.into_iter().map(Line::from).collect();
+    let block = Block::default()
+        .title(" Metrics ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(block)
+            .style(Style::default().fg(Color::Gray).add_modifier(Modifier::DIM)),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(850)), "850µs");
+        assert_eq!(format_duration(Duration::from_micros(12_500)), "12.5ms");
+        assert_eq!(format_duration(Duration::from_millis(1_200)), "1.20s");
+    }
+
+    #[test]
+    fn test_hud_lines() {
+        let mut ctx = LocustContext::default();
+        ctx.metrics
+            .record("locust.nav", PluginPhase::Event, Duration::from_micros(40));
+        ctx.metrics.record_frame_targets(7);
+        ctx.frame_count = 3;
+
+        assert_eq!(
+            hud_lines(&ctx),
+            vec![
+                "plugin      event p50/p99    render p50/p99",
+                "locust.nav  40µs/40µs        -",
+                "frame 3  targets 7  overlay frames 0",
+            ]
+        );
+    }
+}
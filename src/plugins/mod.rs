@@ -1,4 +1,13 @@
 pub mod highlight;
+pub mod hud;
 pub mod nav;
 pub mod omnibar;
+pub mod patterns;
 pub mod tooltip;
+
+/// Priority shared by the built-in plugins.
+///
+/// Built-in plugins order themselves among each other with `before`
+/// declarations, and all see events before user plugins at the default
+/// priority of 100.
+pub const BUILTIN_PRIORITY: i32 = 50;
@@ -3,22 +3,75 @@
 //! This module defines configuration options for hint generation,
 //! rendering styles, and keybindings.
 
+use super::hints::{HintOrder, HintStrategy};
 use ratatui::style::{Color, Modifier, Style};
 
+/// Which targets hint mode labels.
+///
+/// Scopes follow the target tree built with [`NavTarget::with_parent`].
+///
+/// [`NavTarget::with_parent`]: crate::core::targets::NavTarget::with_parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HintScope {
+    /// Every registered target gets a hint.
+    #[default]
+    All,
+
+    /// Hints start at the top-level targets. Selecting a target with
+    /// children hints its children instead of activating it, and backspace
+    /// with no typed input goes back up a level.
+    Nested,
+
+    /// Only the targets inside the focused container get hints: the focused
+    /// target's descendants, or everything under its parent for a leaf.
+    /// Falls back to all targets when nothing focused is nested.
+    Focused,
+}
+
+/// What yank hint mode copies from a picked target.
+///
+/// A target without the chosen text falls back to its label, then to the
+/// text drawn inside it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum YankSource {
+    /// The target's label.
+    #[default]
+    Label,
+
+    /// A metadata field, such as `"path"` or `"url"`.
+    Metadata(String),
+
+    /// The text drawn in the target's cells, one line per row.
+    Text,
+}
+
 /// Configuration for the navigation plugin.
 ///
 /// Controls hint generation algorithm, visual styling, and keybindings.
 #[derive(Debug, Clone)]
 pub struct NavConfig {
-    /// The key that activates hint mode.
-    /// Default: 'f' (like Vimium)
-    pub hint_key: char,
+    /// The key that activates hint mode, replacing the `nav.activate`
+    /// keymap binding ('f', like Vimium) when the plugin is registered.
+    /// Default: None (use the keymap)
+    pub hint_key: Option<char>,
 
     /// Character set used for generating hints.
     /// Should be ordered from most convenient to least convenient.
     /// Default: "asdfghjkl" (home row keys)
     pub hint_charset: String,
 
+    /// How hint strings are drawn from the charset.
+    /// Default: [`HintStrategy::PrefixFree`]
+    pub hint_strategy: HintStrategy,
+
+    /// Which targets of the same priority get the shortest hints.
+    /// Default: [`HintOrder::Reading`]
+    pub hint_order: HintOrder,
+
+    /// How strongly earlier activations shorten a target's hint.
+    /// Default: 0.0 (activations are ignored)
+    pub activation_weight: f64,
+
     /// Style for hint overlays (background box).
     pub hint_background_style: Style,
 
@@ -44,13 +97,24 @@ pub struct NavConfig {
     /// Maximum number of hints to generate.
     /// Set to 0 for unlimited.
     pub max_hints: usize,
+
+    /// Which targets get hints.
+    /// Default: [`HintScope::All`]
+    pub hint_scope: HintScope,
+
+    /// What yank hint mode copies.
+    /// Default: [`YankSource::Label`]
+    pub yank_source: YankSource,
 }
 
 impl Default for NavConfig {
     fn default() -> Self {
         Self {
-            hint_key: 'f',
+            hint_key: None,
             hint_charset: "asdfghjkl".to_string(),
+            hint_strategy: HintStrategy::PrefixFree,
+            hint_order: HintOrder::Reading,
+            activation_weight: 0.0,
             hint_background_style: Style::default()
                 .bg(Color::Yellow)
                 .fg(Color::Black)
@@ -74,6 +138,8 @@ impl Default for NavConfig {
             show_empty_hints: false,
             min_target_area: 1,
             max_hints: 0,
+            hint_scope: HintScope::All,
+            yank_source: YankSource::Label,
         }
     }
 }
@@ -86,7 +152,7 @@ impl NavConfig {
 
     /// Sets the hint activation key.
     pub fn with_hint_key(mut self, key: char) -> Self {
-        self.hint_key = key;
+        self.hint_key = Some(key);
         self
     }
 
@@ -102,6 +168,28 @@ impl NavConfig {
         self
     }
 
+    /// Sets how hint strings are drawn from the charset.
+    pub fn with_hint_strategy(mut self, strategy: HintStrategy) -> Self {
+        self.hint_strategy = strategy;
+        self
+    }
+
+    /// Sets which targets of the same priority get the shortest hints.
+    pub fn with_hint_order(mut self, order: HintOrder) -> Self {
+        self.hint_order = order;
+        self
+    }
+
+    /// Sets how strongly earlier activations shorten a target's hint.
+    ///
+    /// See [`HintGenerator::with_activation_weight`].
+    ///
+    /// [`HintGenerator::with_activation_weight`]: super::hints::HintGenerator::with_activation_weight
+    pub fn with_activation_weight(mut self, weight: f64) -> Self {
+        self.activation_weight = weight;
+        self
+    }
+
     /// Sets the hint background style.
     pub fn with_background_style(mut self, style: Style) -> Self {
         self.hint_background_style = style;
@@ -149,6 +237,18 @@ impl NavConfig {
         self.max_hints = max;
         self
     }
+
+    /// Sets which targets get hints.
+    pub fn with_hint_scope(mut self, scope: HintScope) -> Self {
+        self.hint_scope = scope;
+        self
+    }
+
+    /// Sets what yank hint mode copies.
+    pub fn with_yank_source(mut self, source: YankSource) -> Self {
+        self.yank_source = source;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -158,11 +258,16 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = NavConfig::default();
-        assert_eq!(config.hint_key, 'f');
+        assert_eq!(config.hint_key, None);
         assert_eq!(config.hint_charset, "asdfghjkl");
         assert_eq!(config.min_target_area, 1);
         assert_eq!(config.max_hints, 0);
         assert!(!config.show_empty_hints);
+        assert_eq!(config.hint_scope, HintScope::All);
+        assert_eq!(config.yank_source, YankSource::Label);
+        assert_eq!(config.hint_strategy, HintStrategy::PrefixFree);
+        assert_eq!(config.hint_order, HintOrder::Reading);
+        assert_eq!(config.activation_weight, 0.0);
     }
 
     #[test]
@@ -172,13 +277,17 @@ mod tests {
             .with_charset("abcdef")
             .with_min_target_area(10)
             .with_max_hints(50)
-            .with_show_empty_hints(true);
+            .with_show_empty_hints(true)
+            .with_hint_scope(HintScope::Nested)
+            .with_yank_source(YankSource::Metadata("path".into()));
 
-        assert_eq!(config.hint_key, 'g');
+        assert_eq!(config.hint_key, Some('g'));
         assert_eq!(config.hint_charset, "abcdef");
         assert_eq!(config.min_target_area, 10);
         assert_eq!(config.max_hints, 50);
         assert!(config.show_empty_hints);
+        assert_eq!(config.hint_scope, HintScope::Nested);
+        assert_eq!(config.yank_source, YankSource::Metadata("path".into()));
     }
 
     #[test]
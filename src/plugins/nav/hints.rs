@@ -3,9 +3,15 @@
 //! This module implements the core hint generation algorithm that assigns
 //! unique, short character sequences to navigation targets.
 
-use crate::core::targets::NavTarget;
+use crate::core::targets::{NavTarget, TargetKey};
 use std::collections::HashMap;
 
+/// Most targets whose activations are counted at once.
+///
+/// Unlabeled targets are keyed by ID, so apps that renumber them every frame
+/// would otherwise grow the counts without bound.
+const MAX_COUNTED_TARGETS: usize = 256;
+
 /// A hint assigned to a navigation target.
 ///
 /// Contains the hint string (e.g., "as", "df") and tracks which
@@ -63,6 +69,43 @@ impl Hint {
     }
 }
 
+/// How hint strings are drawn from the charset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HintStrategy {
+    /// No hint is a prefix of another, as in Vimium. With more targets than
+    /// charset characters, all hints get the same length or two adjacent
+    /// lengths, with as many short hints as the count allows.
+    ///
+    /// With 5 targets over `"as"` this gives `aa`, `as`, `sa`, `ssa` and
+    /// `sss`.
+    #[default]
+    PrefixFree,
+
+    /// Bijective base-N: `a`, `s`, ..., `aa`, `as`, ... The shortest hints
+    /// come first, but `a` and `aa` can both be assigned, so typing `a`
+    /// picks its target before `aa` can be typed.
+    Bijective,
+}
+
+/// Which targets of the same priority get the shortest hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HintOrder {
+    /// Top-left first, in reading order.
+    #[default]
+    Reading,
+
+    /// Nearest the focused target first. Without a focused target, nearest
+    /// the mouse, then nearest the screen centre.
+    NearFocus,
+
+    /// Nearest the mouse first. Before the mouse has moved, nearest the
+    /// screen centre.
+    NearMouse,
+
+    /// Nearest the screen centre first.
+    NearCenter,
+}
+
 /// Generates hints for a list of navigation targets.
 ///
 /// This struct implements the Vimium-style hint generation algorithm:
@@ -70,10 +113,11 @@ impl Hint {
 /// 2. Generate shortest unique hints from charset
 /// 3. Assign hints to targets in order
 ///
-/// The algorithm generates hints in this order:
-/// - 1 character: a, s, d, f, ...
-/// - 2 characters: aa, as, ad, af, sa, ss, sd, ...
-/// - 3 characters: aaa, aas, aad, ...
+/// By default hints are prefix-free (see [`HintStrategy::PrefixFree`]):
+/// - up to N targets (N = charset length): a, s, d, f, ...
+/// - up to N² targets: two characters each, except for as many single
+///   characters as can be kept, such as a, s, da, ds, dd, ...
+/// - and so on for longer hints
 ///
 /// # Examples
 ///
@@ -101,6 +145,21 @@ pub struct HintGenerator {
 
     /// Cached charset length for performance
     charset_len: usize,
+
+    /// How hint strings are drawn from the charset
+    strategy: HintStrategy,
+
+    /// Which targets get the shortest hints
+    order: HintOrder,
+
+    /// Point distances are measured from, for orders other than reading
+    anchor: Option<(u16, u16)>,
+
+    /// How much each earlier activation shortens a target's hint
+    activation_weight: f64,
+
+    /// Activations per target, kept across frames by stable key
+    activations: HashMap<TargetKey, u32>,
 }
 
 impl HintGenerator {
@@ -115,13 +174,79 @@ impl HintGenerator {
         Self {
             charset,
             charset_len,
+            strategy: HintStrategy::default(),
+            order: HintOrder::default(),
+            anchor: None,
+            activation_weight: 0.0,
+            activations: HashMap::new(),
         }
     }
 
+    /// Sets which targets of the same priority get the shortest hints.
+    pub fn with_order(mut self, order: HintOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets how strongly earlier activations shorten a target's hint.
+    ///
+    /// A target activated `n` times ranks as if it were `1 + weight * n`
+    /// times nearer (or earlier in reading order). `0.0`, the default,
+    /// ignores activations.
+    pub fn with_activation_weight(mut self, weight: f64) -> Self {
+        self.activation_weight = weight.max(0.0);
+        self
+    }
+
+    /// Sets the point the [`HintOrder`] measures distances from, such as
+    /// the centre of the focused target.
+    ///
+    /// With no anchor, targets stay in reading order.
+    pub fn set_anchor(&mut self, anchor: Option<(u16, u16)>) {
+        self.anchor = anchor;
+    }
+
+    /// Counts an activation of `target`, for [`with_activation_weight`].
+    ///
+    /// Once too many targets are counted, all counts are halved and those
+    /// reaching zero are forgotten, so rarely activated targets age out.
+    ///
+    /// [`with_activation_weight`]: Self::with_activation_weight
+    pub fn record_activation(&mut self, target: &NavTarget) {
+        let key = target.stable_key();
+        if !self.activations.contains_key(&key) {
+            while self.activations.len() >= MAX_COUNTED_TARGETS {
+                self.activations.retain(|_, count| {
+                    *count /= 2;
+                    *count > 0
+                });
+            }
+        }
+        *self.activations.entry(key).or_insert(0) += 1;
+    }
+
+    /// Returns how often the target with `key` was activated.
+    pub fn activation_count(&self, key: &TargetKey) -> u32 {
+        self.activations.get(key).copied().unwrap_or(0)
+    }
+
+    /// Sets how hint strings are drawn from the charset.
+    pub fn with_strategy(mut self, strategy: HintStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns how hint strings are drawn from the charset.
+    pub fn strategy(&self) -> HintStrategy {
+        self.strategy
+    }
+
     /// Generates hints for the given targets.
     ///
     /// Targets are sorted by priority (highest first) and then by
-    /// vertical then horizontal position. The most prominent targets
+    /// vertical then horizontal position, or by distance from the anchor
+    /// for the other [`HintOrder`]s. Earlier activations move targets
+    /// forward if an activation weight is set. The most prominent targets
     /// receive the shortest hints.
     pub fn generate(&mut self, targets: &[NavTarget]) -> Vec<Hint> {
         if targets.is_empty() {
@@ -147,18 +272,90 @@ impl HintGenerator {
             }
         });
 
+        let anchor = self.anchor.filter(|_| self.order != HintOrder::Reading);
+        if anchor.is_some() || self.activation_weight > 0.0 {
+            let costs: HashMap<u64, f64> = sorted_targets
+                .iter()
+                .enumerate()
+                .map(|(rank, target)| (target.id, self.cost(target, rank, anchor)))
+                .collect();
+            // Stable, so equal costs keep reading order
+            sorted_targets.sort_by(|a, b| {
+                b.priority
+                    .cmp(&a.priority)
+                    .then(costs[&a.id].total_cmp(&costs[&b.id]))
+            });
+        }
+
         // Generate hints in order
-        sorted_targets
-            .iter()
-            .enumerate()
-            .map(|(index, target)| {
-                let hint_text = self.generate_hint_string(index);
-                Hint::new(hint_text, target.id)
-            })
+        self.generate_hint_strings(sorted_targets.len())
+            .into_iter()
+            .zip(sorted_targets)
+            .map(|(hint_text, target)| Hint::new(hint_text, target.id))
+            .collect()
+    }
+
+    /// Ranking cost of a target at `rank` in reading order; lower is better.
+    fn cost(&self, target: &NavTarget, rank: usize, anchor: Option<(u16, u16)>) -> f64 {
+        let base = match anchor {
+            Some((x, y)) => {
+                let (cx, cy) = target.center();
+                // Cells are about twice as tall as they are wide
+                let dx = cx as f64 - x as f64;
+                let dy = (cy as f64 - y as f64) * 2.0;
+                dx.hypot(dy)
+            }
+            None => rank as f64,
+        };
+        let activations = self.activation_count(&target.stable_key()) as f64;
+        (base + 1.0) / (1.0 + self.activation_weight * activations)
+    }
+
+    /// Generates `count` hint strings, shortest first.
+    ///
+    /// A single-character charset cannot be prefix-free beyond one hint and
+    /// always uses [`HintStrategy::Bijective`].
+    pub fn generate_hint_strings(&self, count: usize) -> Vec<String> {
+        if self.strategy == HintStrategy::Bijective || self.charset_len == 1 {
+            return (0..count)
+                .map(|index| self.generate_hint_string(index))
+                .collect();
+        }
+
+        // Shortest length fitting every hint: k^(len-1) < count <= k^len
+        let k = self.charset_len;
+        let mut len = 1;
+        let mut capacity = k;
+        while capacity < count {
+            len += 1;
+            capacity = capacity.saturating_mul(k);
+        }
+        if len == 1 {
+            return (0..count).map(|index| self.fixed_width(index, 1)).collect();
+        }
+
+        // Keep the first `short` strings of length len-1 as hints and split
+        // the following ones into k hints each, as many as are needed
+        let short = (capacity - count) / (k - 1);
+        (0..short)
+            .map(|index| self.fixed_width(index, len - 1))
+            .chain((0..count - short).map(|index| self.fixed_width(short * k + index, len)))
             .collect()
     }
 
-    /// Generates the hint string for the given index.
+    /// Writes `index` in base N with exactly `width` digits.
+    fn fixed_width(&self, mut index: usize, width: usize) -> String {
+        let chars: Vec<char> = self.charset.chars().collect();
+        let mut result = vec![chars[0]; width];
+        for slot in result.iter_mut().rev() {
+            *slot = chars[index % self.charset_len];
+            index /= self.charset_len;
+        }
+        result.into_iter().collect()
+    }
+
+    /// Generates the [`HintStrategy::Bijective`] hint string for the given
+    /// index.
     ///
     /// Uses a base-N encoding where N is the charset length.
     /// Index 0 -> "a", 1 -> "s", ..., N -> "aa", N+1 -> "as", etc.
@@ -352,7 +549,8 @@ mod tests {
 
     #[test]
     fn test_hint_generation_two_char() {
-        let mut generator = HintGenerator::new("as".to_string());
+        let mut generator =
+            HintGenerator::new("as".to_string()).with_strategy(HintStrategy::Bijective);
         let targets = vec![
             NavTarget::new(1, Rect::new(0, 0, 10, 1)),
             NavTarget::new(2, Rect::new(0, 2, 10, 1)),
@@ -366,6 +564,96 @@ mod tests {
         assert_eq!(hints[2].text, "aa");
     }
 
+    #[test]
+    fn test_prefix_free_hints_are_balanced() {
+        let generator = HintGenerator::new("as".to_string());
+        assert_eq!(
+            generator.generate_hint_strings(5),
+            vec!["aa", "as", "sa", "ssa", "sss"]
+        );
+
+        let generator = HintGenerator::new("asd".to_string());
+        assert_eq!(generator.generate_hint_strings(2), vec!["a", "s"]);
+        assert_eq!(
+            generator.generate_hint_strings(5),
+            vec!["a", "s", "da", "ds", "dd"]
+        );
+        assert_eq!(
+            generator.generate_hint_strings(9),
+            vec!["aa", "as", "ad", "sa", "ss", "sd", "da", "ds", "dd"]
+        );
+    }
+
+    #[test]
+    fn test_hint_order_near_anchor() {
+        let targets = vec![
+            NavTarget::new(1, Rect::new(0, 0, 4, 1)),
+            NavTarget::new(2, Rect::new(20, 0, 4, 1)),
+            NavTarget::new(3, Rect::new(38, 0, 4, 1)),
+            NavTarget::new(4, Rect::new(36, 10, 4, 1)).with_priority(TargetPriority::High),
+        ];
+        let order = |generator: &mut HintGenerator| -> Vec<u64> {
+            generator
+                .generate(&targets)
+                .iter()
+                .map(|h| h.target_id)
+                .collect()
+        };
+
+        let mut generator = HintGenerator::new("asdf".to_string()).with_order(HintOrder::NearMouse);
+        assert_eq!(order(&mut generator), vec![4, 1, 2, 3]);
+
+        // Nearest first, with priority still first
+        generator.set_anchor(Some((39, 4)));
+        assert_eq!(order(&mut generator), vec![4, 3, 2, 1]);
+
+        // Reading order ignores the anchor
+        let mut generator = HintGenerator::new("asdf".to_string());
+        generator.set_anchor(Some((39, 4)));
+        assert_eq!(order(&mut generator), vec![4, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_frequent_targets_move_forward() {
+        let targets = vec![
+            NavTarget::new(1, Rect::new(0, 0, 4, 1)).with_label("Inbox"),
+            NavTarget::new(2, Rect::new(0, 1, 4, 1)).with_label("Sent"),
+            NavTarget::new(3, Rect::new(0, 2, 4, 1)).with_label("Trash"),
+        ];
+        let mut generator = HintGenerator::new("asdf".to_string()).with_activation_weight(1.0);
+        generator.record_activation(&targets[2]);
+        generator.record_activation(&targets[2]);
+
+        // Trash ranks (2 + 1) / 3 = 1, level with Inbox, which stays first
+        let hints = generator.generate(&targets);
+        let ids: Vec<u64> = hints.iter().map(|h| h.target_id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+
+        // Activations follow the label, not the ID
+        let renumbered = NavTarget::new(9, Rect::new(0, 5, 4, 1)).with_label("Trash");
+        assert_eq!(generator.activation_count(&renumbered.stable_key()), 2);
+    }
+
+    #[test]
+    fn test_activation_counts_are_bounded() {
+        let mut generator = HintGenerator::new("asdf".to_string()).with_activation_weight(1.0);
+        let inbox = NavTarget::new(1, Rect::new(0, 0, 4, 1)).with_label("Inbox");
+        for _ in 0..4 {
+            generator.record_activation(&inbox);
+        }
+
+        // Unlabeled targets renumbered every frame
+        for id in 0..MAX_COUNTED_TARGETS as u64 {
+            generator.record_activation(&NavTarget::new(100 + id, Rect::new(0, 1, 4, 1)));
+        }
+
+        // Filling up halved every count once, forgetting the single ones
+        assert_eq!(generator.activations.len(), 2);
+        assert_eq!(generator.activation_count(&inbox.stable_key()), 2);
+        let last = NavTarget::new(100 + MAX_COUNTED_TARGETS as u64 - 1, Rect::default());
+        assert_eq!(generator.activation_count(&last.stable_key()), 1);
+    }
+
     #[test]
     fn test_hint_generation_priority() {
         let mut generator = HintGenerator::new("asdf".to_string());
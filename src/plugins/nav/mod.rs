@@ -1,7 +1,9 @@
 //! Locust navigation plugin.
 //!
 //! This plugin provides Vimium-style hint-based navigation for terminal UIs.
-//! Press 'f' to activate hint mode, then type hint characters to navigate to targets.
+//! Press 'f' (the `nav.activate` keymap binding) to activate hint mode, then type
+//! hint characters to navigate to targets. Bind `nav.yank` to pick a target
+//! and copy its text to the clipboard instead (see [`YankSource`]).
 //!
 //! # Features
 //!
@@ -10,6 +12,8 @@
 //! - Priority-based hint assignment
 //! - Customizable styling and configuration
 //! - Automatic target discovery from TargetRegistry
+//! - Scoped hints over nested targets (see [`HintScope`])
+//! - Yank hints copying to the system clipboard over OSC 52
 //!
 //! # Example
 //!
@@ -29,17 +33,36 @@ pub mod hints;
 pub mod render;
 
 // Re-export for easier access
-pub use config::NavConfig;
+pub use config::{HintScope, NavConfig, YankSource};
+pub use hints::{HintOrder, HintStrategy};
 
+use crate::core::clipboard::{Clipboard, Osc52Clipboard};
 use crate::core::context::LocustContext;
 use crate::core::input::PluginEventResult;
+use crate::core::modes::Mode;
+use crate::core::overlay::{OcclusionMask, OverlayLayer};
 use crate::core::plugin::LocustPlugin;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crate::core::targets::NavTarget;
+use crate::plugins::BUILTIN_PRIORITY;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use hints::{HintGenerator, HintMatcher};
+use log::{info, warn};
 use ratatui::backend::Backend;
+use ratatui::layout::Rect;
 use ratatui::Frame;
 use render::HintRenderer;
-use log::info;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Plugin ID, also the owner of the hint mode on the mode stack.
+const PLUGIN_ID: &str = "locust.nav";
+
+/// How long the result of a yank is shown.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest copied text quoted in full in the yank message.
+const MESSAGE_PREVIEW_LEN: usize = 40;
 
 /// Current navigation mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +71,8 @@ pub enum NavMode {
     Normal,
     /// Hint mode - hints are visible and accepting input
     Hint,
+    /// Yank mode - hints are visible and picking a target copies its text
+    Yank,
     // Future: Find, Command, Visual, etc.
 }
 
@@ -59,6 +84,9 @@ pub enum NavMode {
 /// 3. User types hint characters to narrow matches
 /// 4. When a unique match is found, the target is activated
 ///
+/// In yank mode the picked target's text is copied to the clipboard
+/// instead, and the result is shown in a message for a moment.
+///
 /// # Configuration
 ///
 /// The plugin can be customized using `NavConfig`:
@@ -88,6 +116,21 @@ pub struct NavPlugin {
 
     /// Hint renderer
     renderer: HintRenderer,
+
+    /// Containers entered in [`HintScope::Nested`] hint mode, outermost first
+    scope_path: Vec<u64>,
+
+    /// Where yank mode copies to
+    clipboard: Box<dyn Clipboard>,
+
+    /// Text drawn in the hinted targets, read while rendering yank mode
+    drawn_text: RefCell<HashMap<u64, String>>,
+
+    /// Result of the last yank and when it was shown
+    message: Option<(String, Instant)>,
+
+    /// Area of the last frame drawn, for [`HintOrder::NearCenter`]
+    screen: Cell<Rect>,
 }
 
 impl Default for NavPlugin {
@@ -104,7 +147,10 @@ impl NavPlugin {
 
     /// Creates a new navigation plugin with custom configuration.
     pub fn with_config(config: NavConfig) -> Self {
-        let generator = HintGenerator::new(config.hint_charset.clone());
+        let generator = HintGenerator::new(config.hint_charset.clone())
+            .with_strategy(config.hint_strategy)
+            .with_order(config.hint_order)
+            .with_activation_weight(config.activation_weight);
         let matcher = HintMatcher::new();
         let renderer = HintRenderer::new();
 
@@ -114,9 +160,24 @@ impl NavPlugin {
             generator,
             matcher,
             renderer,
+            scope_path: Vec::new(),
+            clipboard: Box::new(Osc52Clipboard::stdout()),
+            drawn_text: RefCell::new(HashMap::new()),
+            message: None,
+            screen: Cell::new(Rect::default()),
         }
     }
 
+    /// Sets the clipboard yank mode copies to.
+    ///
+    /// Defaults to an [`Osc52Clipboard`] on stdout. Tests can pass a
+    /// [`MemoryClipboard`](crate::core::clipboard::MemoryClipboard) to check
+    /// what was copied without writing escape sequences.
+    pub fn with_clipboard(mut self, clipboard: impl Clipboard + 'static) -> Self {
+        self.clipboard = Box::new(clipboard);
+        self
+    }
+
     /// Returns the current configuration.
     pub fn config(&self) -> &NavConfig {
         &self.config
@@ -127,15 +188,64 @@ impl NavPlugin {
         self.mode
     }
 
+    /// Returns the message reporting the last yank, while it is shown.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    /// Returns the containers entered in nested hint mode, outermost first.
+    pub fn scope_path(&self) -> &[u64] {
+        &self.scope_path
+    }
+
     /// Enters hint mode and generates hints for visible targets.
     fn enter_hint_mode(&mut self, ctx: &mut LocustContext) {
-        self.mode = NavMode::Hint;
+        self.enter_mode(NavMode::Hint, ctx);
+    }
+
+    /// Enters hint mode, with `NavMode::Yank` copying picked targets.
+    fn enter_mode(&mut self, mode: NavMode, ctx: &mut LocustContext) {
+        self.mode = mode;
+        self.scope_path.clear();
+        self.message = None;
+        ctx.modes.push(Mode::Hint, PLUGIN_ID);
 
-        // Get all visible targets from registry
+        self.refresh_hints(ctx);
+    }
+
+    /// Returns the targets the configured [`HintScope`] puts hints on.
+    fn scoped_targets<'a>(&self, ctx: &'a LocustContext) -> Vec<&'a NavTarget> {
         let registry = &ctx.targets;
-        let mut targets: Vec<_> = registry
-            .all()
-            .iter()
+        match self.config.hint_scope {
+            HintScope::All => registry.all().iter().collect(),
+            HintScope::Nested => match self.scope_path.last() {
+                Some(&container) => registry.children_of(container),
+                None => registry.roots(),
+            },
+            HintScope::Focused => {
+                let focused = ctx.focus.focused().and_then(|id| registry.by_id(id));
+                let container = focused.and_then(|target| {
+                    if registry.children_of(target.id).is_empty() {
+                        target.parent
+                    } else {
+                        Some(target.id)
+                    }
+                });
+                match container {
+                    Some(container) if registry.by_id(container).is_some() => {
+                        registry.scope(container)
+                    }
+                    _ => registry.all().iter().collect(),
+                }
+            }
+        }
+    }
+
+    /// Generates hints for the targets in the current scope.
+    fn refresh_hints(&mut self, ctx: &mut LocustContext) {
+        let mut targets: Vec<NavTarget> = self
+            .scoped_targets(ctx)
+            .into_iter()
             .filter(|t| {
                 // Filter by minimum area if configured
                 if self.config.min_target_area > 1 {
@@ -155,16 +265,68 @@ impl NavPlugin {
         }
 
         // Generate hints
+        self.generator.set_anchor(self.anchor(ctx, &targets));
         let hints = self.generator.generate(&targets);
         self.matcher.set_hints(hints);
 
         ctx.overlay.mark_has_overlay();
     }
 
+    /// The point the configured [`HintOrder`] puts the shortest hints
+    /// around.
+    fn anchor(&self, ctx: &LocustContext, targets: &[NavTarget]) -> Option<(u16, u16)> {
+        let focus = || ctx.focused_target().map(NavTarget::center);
+        let mouse = || ctx.mouse.position;
+        let center = || {
+            let mut area = self.screen.get();
+            if area.is_empty() {
+                area = targets.iter().map(|t| t.rect).reduce(|a, b| a.union(b))?;
+            }
+            Some((area.x + area.width / 2, area.y + area.height / 2))
+        };
+
+        match self.config.hint_order {
+            HintOrder::Reading => None,
+            HintOrder::NearFocus => focus().or_else(mouse).or_else(center),
+            HintOrder::NearMouse => mouse().or_else(center),
+            HintOrder::NearCenter => center(),
+        }
+    }
+
+    /// Selects a hinted target.
+    ///
+    /// In nested hint mode a target with children is entered rather than
+    /// activated.
+    fn select_target(&mut self, target_id: u64, ctx: &mut LocustContext) {
+        if self.config.hint_scope == HintScope::Nested
+            && !ctx.targets.children_of(target_id).is_empty()
+        {
+            self.scope_path.push(target_id);
+            self.refresh_hints(ctx);
+        } else if self.mode == NavMode::Yank {
+            self.yank_target(target_id, ctx);
+        } else {
+            self.activate_target(target_id, ctx);
+        }
+    }
+
+    /// Removes the last typed character, or leaves the innermost scope if
+    /// nothing is typed.
+    fn handle_backspace(&mut self, ctx: &mut LocustContext) {
+        if self.matcher.input().is_empty() && self.scope_path.pop().is_some() {
+            self.refresh_hints(ctx);
+        } else {
+            self.matcher.pop_char();
+        }
+    }
+
     /// Exits hint mode and clears all hints.
-    fn exit_hint_mode(&mut self) {
+    fn exit_hint_mode(&mut self, ctx: &mut LocustContext) {
         self.mode = NavMode::Normal;
         self.matcher.clear();
+        self.scope_path.clear();
+        self.drawn_text.borrow_mut().clear();
+        ctx.modes.remove(&Mode::Hint, PLUGIN_ID);
     }
 
     /// Handles a character input in hint mode.
@@ -180,75 +342,158 @@ impl NavPlugin {
     }
 
     /// Activates the target with the given ID.
+    ///
+    /// The target's action is emitted into `ctx.actions` for the host
+    /// application to handle.
     fn activate_target(&mut self, target_id: u64, ctx: &mut LocustContext) {
         if let Some(target) = ctx.targets.by_id(target_id) {
-            // Log the activation for debugging
-            // In a real application, this would trigger the target's action
-            info!(
-                "Locust: Activated target {} ({:?})",
-                target_id,
-                target.label.as_deref().unwrap_or("unlabeled")
-            );
-
-            // TODO: Execute target action
-            // match &target.action {
-            //     TargetAction::Activate => { /* ... */ }
-            //     TargetAction::Navigate(route) => { /* ... */ }
-            //     _ => {}
-            // }
+            self.generator.record_activation(target);
+        }
+        if ctx.activate_target(target_id) {
+            info!("Locust: Activated target {}", target_id);
         }
 
         // Exit hint mode after activation
-        self.exit_hint_mode();
+        self.exit_hint_mode(ctx);
+    }
+
+    /// Copies the text of the target with the given ID and exits hint mode.
+    ///
+    /// The text is chosen by [`NavConfig::yank_source`], falling back to
+    /// the label and then to the text drawn in the target.
+    fn yank_target(&mut self, target_id: u64, ctx: &mut LocustContext) {
+        let drawn = self.drawn_text.borrow_mut().remove(&target_id);
+        let text = ctx.targets.by_id(target_id).and_then(|target| {
+            let label = target.label.clone();
+            let chosen = match &self.config.yank_source {
+                YankSource::Metadata(key) => target.metadata.get(key).cloned().or(label),
+                YankSource::Label => label,
+                YankSource::Text => None,
+            };
+            chosen.or(drawn).filter(|text| !text.trim().is_empty())
+        });
+
+        let message = match text {
+            Some(text) => match self.clipboard.set_text(&text) {
+                Ok(()) => {
+                    info!("Locust: Copied target {} to the clipboard", target_id);
+                    format!("Copied: {}", preview(&text))
+                }
+                Err(err) => {
+                    warn!("Failed to copy target {}: {}", target_id, err);
+                    format!("Copy failed: {}", err)
+                }
+            },
+            None => "Nothing to copy".to_string(),
+        };
+        self.exit_hint_mode(ctx);
+        self.message = Some((message, ctx.now()));
+        ctx.overlay.mark_has_overlay();
+    }
+
+    /// Draws the hint banner and the hints not covered by `mask`, or the
+    /// yank message.
+    fn render_hints(&self, frame: &mut Frame, mask: &OcclusionMask, ctx: &LocustContext) {
+        self.screen.set(frame.area());
+        let title = match self.mode {
+            NavMode::Normal => {
+                if let Some((message, _)) = &self.message {
+                    render::render_message(frame, message, self.config.banner_style);
+                }
+                return;
+            }
+            NavMode::Hint => "Hint mode",
+            NavMode::Yank => {
+                // Read the targets' text before the hints cover it
+                let buffer = frame.buffer_mut();
+                let mut drawn = self.drawn_text.borrow_mut();
+                for hint in self.matcher.hints() {
+                    if let Some(target) = ctx.targets.by_id(hint.target_id) {
+                        drawn.insert(target.id, render::cell_text(buffer, target.rect));
+                    }
+                }
+                "Yank mode"
+            }
+        };
+
+        // Render hint banner at top
+        render::render_banner(frame, title, &self.matcher, self.config.banner_style);
+
+        // Render hints on targets, leaving out those under higher layers
+        self.renderer
+            .render(frame, &self.matcher, &ctx.targets, &self.config, mask);
     }
 }
 
+/// `text` on one line, shortened to [`MESSAGE_PREVIEW_LEN`] characters.
+fn preview(text: &str) -> String {
+    let line = text.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= MESSAGE_PREVIEW_LEN {
+        return line;
+    }
+    let mut short: String = line.chars().take(MESSAGE_PREVIEW_LEN - 1).collect();
+    short.push('…');
+    short
+}
+
 impl<B> LocustPlugin<B> for NavPlugin
 where
     B: Backend + 'static,
 {
     fn id(&self) -> &'static str {
-        "locust.nav"
+        PLUGIN_ID
     }
 
     fn priority(&self) -> i32 {
-        50 // Built-in plugin, higher priority than user plugins
+        BUILTIN_PRIORITY
     }
 
-    fn init(&mut self, _ctx: &mut LocustContext) {
-        // Plugin is initialized
+    fn init(&mut self, ctx: &mut LocustContext) {
+        ctx.bind_configured_key("nav.activate", self.config.hint_key);
     }
 
     fn on_event(&mut self, event: &Event, ctx: &mut LocustContext) -> PluginEventResult {
-        if let Event::Key(KeyEvent {
-            code, modifiers, ..
-        }) = event
-        {
-            match (self.mode, code, modifiers) {
-                // Normal mode: activate hint mode on configured key
-                (NavMode::Normal, KeyCode::Char(c), m)
-                    if *c == self.config.hint_key && *m == KeyModifiers::NONE =>
-                {
+        if let Event::Key(key) = event {
+            // Leave keys alone while another plugin's mode is on top
+            let blocked = match self.mode {
+                NavMode::Normal => ctx.is_capturing_input(),
+                NavMode::Hint | NavMode::Yank => !ctx.modes.is_current(&Mode::Hint),
+            };
+            if blocked {
+                return PluginEventResult::NotHandled;
+            }
+
+            let action = ctx.plugin_action("nav", key);
+
+            match (self.mode, action.as_deref(), &key.code, &key.modifiers) {
+                // Normal mode: activate hint mode on the bound key
+                (NavMode::Normal, Some("nav.activate"), _, _) => {
                     self.enter_hint_mode(ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
+                (NavMode::Normal, Some("nav.yank"), _, _) => {
+                    self.enter_mode(NavMode::Yank, ctx);
+                    return PluginEventResult::ConsumedRequestRedraw;
+                }
 
-                // Hint mode: handle escape to exit
-                (NavMode::Hint, KeyCode::Esc, _) => {
-                    self.exit_hint_mode();
+                // Hint mode: exit on the cancel key
+                (NavMode::Hint | NavMode::Yank, Some("nav.cancel"), _, _) => {
+                    self.exit_hint_mode(ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Hint mode: handle backspace to remove last character
-                (NavMode::Hint, KeyCode::Backspace, _) => {
-                    self.matcher.pop_char();
+                (NavMode::Hint | NavMode::Yank, _, KeyCode::Backspace, _) => {
+                    self.handle_backspace(ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Hint mode: handle character input
-                (NavMode::Hint, KeyCode::Char(c), m) if *m == KeyModifiers::NONE => {
+                (NavMode::Hint | NavMode::Yank, _, KeyCode::Char(c), m)
+                    if *m == KeyModifiers::NONE =>
+                {
                     if let Some(target_id) = self.handle_hint_char(*c) {
-                        self.activate_target(target_id, ctx);
+                        self.select_target(target_id, ctx);
                     }
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
@@ -260,24 +505,49 @@ where
         PluginEventResult::NotHandled
     }
 
-    fn render_overlay(&self, frame: &mut Frame, ctx: &LocustContext) {
-        if self.mode != NavMode::Hint {
-            return;
+    fn on_mode_exit(&mut self, mode: &Mode, ctx: &mut LocustContext) {
+        if *mode == Mode::Hint {
+            self.exit_hint_mode(ctx);
         }
+    }
 
-        // Render hint banner at top
-        render::render_hint_banner(frame, &self.matcher, self.config.banner_style);
+    fn on_tick(&mut self, now: Instant, _ctx: &mut LocustContext) -> bool {
+        // Dismiss the yank message once it expired
+        match &self.message {
+            Some((_, shown_at)) if now >= *shown_at + MESSAGE_TIMEOUT => {
+                self.message = None;
+                true
+            }
+            _ => false,
+        }
+    }
 
-        // Render hints on targets
-        self.renderer
-            .render(frame, &self.matcher, &ctx.targets, &self.config);
+    fn next_deadline(&self, _ctx: &LocustContext) -> Option<Instant> {
+        self.message
+            .as_ref()
+            .map(|(_, shown_at)| *shown_at + MESSAGE_TIMEOUT)
+    }
+
+    fn render_overlay(&self, frame: &mut Frame, ctx: &LocustContext) {
+        self.render_hints(frame, &OcclusionMask::new(), ctx);
+    }
+
+    fn render_layer(
+        &self,
+        frame: &mut Frame,
+        _layer: &OverlayLayer,
+        mask: &OcclusionMask,
+        ctx: &LocustContext,
+    ) {
+        self.render_hints(frame, mask, ctx);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::targets::{NavTarget, TargetPriority};
+    use crate::core::keybindings::{KeyBinding, KeyCodeDef};
+    use crate::core::targets::{TargetAction, TargetPriority};
     use ratatui::layout::Rect;
 
     #[test]
@@ -290,7 +560,7 @@ mod tests {
     fn test_plugin_custom_config() {
         let config = NavConfig::new().with_hint_key('g').with_charset("abc");
         let plugin = NavPlugin::with_config(config);
-        assert_eq!(plugin.config().hint_key, 'g');
+        assert_eq!(plugin.config().hint_key, Some('g'));
         assert_eq!(plugin.config().hint_charset, "abc");
     }
 
@@ -311,9 +581,110 @@ mod tests {
         assert_eq!(plugin.mode, NavMode::Hint);
         assert_eq!(plugin.matcher.hints().len(), 2);
 
-        plugin.exit_hint_mode();
+        assert_eq!(ctx.modes.current(), &Mode::Hint);
+
+        plugin.exit_hint_mode(&mut ctx);
         assert_eq!(plugin.mode, NavMode::Normal);
         assert_eq!(plugin.matcher.hints().len(), 0);
+        assert_eq!(ctx.modes.current(), &Mode::Normal);
+    }
+
+    #[test]
+    fn test_hint_mode_follows_keymap() {
+        use crossterm::event::KeyEvent;
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = NavPlugin::new();
+        let mut ctx = LocustContext::default();
+        LocustPlugin::<TestBackend>::init(&mut plugin, &mut ctx);
+        ctx.bind_key(
+            "nav.activate",
+            KeyBinding::with_modifiers(KeyCodeDef::Char('g'), KeyModifiers::CONTROL),
+        )
+        .unwrap();
+
+        let press = |plugin: &mut NavPlugin, ctx: &mut LocustContext, code, modifiers| {
+            let event = Event::Key(KeyEvent::new(code, modifiers));
+            LocustPlugin::<TestBackend>::on_event(plugin, &event, ctx)
+        };
+
+        assert_eq!(
+            press(
+                &mut plugin,
+                &mut ctx,
+                KeyCode::Char('f'),
+                KeyModifiers::NONE
+            ),
+            PluginEventResult::NotHandled
+        );
+        press(
+            &mut plugin,
+            &mut ctx,
+            KeyCode::Char('g'),
+            KeyModifiers::CONTROL,
+        );
+        assert_eq!(plugin.mode, NavMode::Hint);
+        press(&mut plugin, &mut ctx, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(plugin.mode, NavMode::Normal);
+    }
+
+    #[test]
+    fn test_configured_hint_key_overrides_keymap() {
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = NavPlugin::with_config(NavConfig::new().with_hint_key('g'));
+        let mut ctx = LocustContext::default();
+        LocustPlugin::<TestBackend>::init(&mut plugin, &mut ctx);
+
+        assert_eq!(
+            ctx.keymap.get_binding("nav.activate"),
+            Some(&KeyBinding::new(KeyCodeDef::Char('g')))
+        );
+    }
+
+    #[test]
+    fn test_hint_selection_emits_action() {
+        let mut plugin = NavPlugin::new();
+        let mut ctx = LocustContext::default();
+
+        ctx.targets.register(
+            NavTarget::new(1, Rect::new(0, 0, 10, 1))
+                .with_label("Open")
+                .with_action(TargetAction::Navigate("/open".into())),
+        );
+
+        plugin.enter_hint_mode(&mut ctx);
+        let hint = plugin.matcher.hints()[0].text.clone();
+        for c in hint.chars() {
+            if let Some(target_id) = plugin.handle_hint_char(c) {
+                plugin.activate_target(target_id, &mut ctx);
+            }
+        }
+
+        assert_eq!(plugin.mode, NavMode::Normal);
+        let actions = ctx.actions.drain();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].target_id, 1);
+        assert_eq!(actions[0].action, TargetAction::Navigate("/open".into()));
+        assert_eq!(actions[0].label.as_deref(), Some("Open"));
+    }
+
+    #[test]
+    fn test_unhandled_callback_falls_through_to_queue() {
+        let mut plugin = NavPlugin::new();
+        let mut ctx = LocustContext::default();
+
+        ctx.targets
+            .register_with_callback(NavTarget::new(1, Rect::new(0, 0, 10, 1)), |_| false);
+        ctx.targets
+            .register_with_callback(NavTarget::new(2, Rect::new(0, 2, 10, 1)), |_| true);
+
+        plugin.activate_target(1, &mut ctx);
+        plugin.activate_target(2, &mut ctx);
+
+        let actions = ctx.actions.drain();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].target_id, 1);
     }
 
     #[test]
@@ -361,4 +732,204 @@ mod tests {
         assert!(hint_targets.contains(&3)); // Normal priority
         assert!(!hint_targets.contains(&1)); // Low priority filtered out
     }
+
+    /// Pane 1 holds rows 2 and 3, row 3 holds cell 4; 5 is a loose button.
+    fn nested_targets(ctx: &mut LocustContext) {
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(0, 0, 40, 10)).with_label("Pane"));
+        ctx.targets
+            .register(NavTarget::new(2, Rect::new(1, 1, 38, 1)).with_parent(1));
+        ctx.targets
+            .register(NavTarget::new(3, Rect::new(1, 2, 38, 1)).with_parent(1));
+        ctx.targets
+            .register(NavTarget::new(4, Rect::new(2, 2, 4, 1)).with_parent(3));
+        ctx.targets
+            .register(NavTarget::new(5, Rect::new(0, 12, 10, 1)));
+    }
+
+    fn hinted_ids(plugin: &NavPlugin) -> Vec<u64> {
+        let mut ids: Vec<u64> = plugin.matcher.hints().iter().map(|h| h.target_id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    fn type_hint(plugin: &mut NavPlugin, ctx: &mut LocustContext, target_id: u64) {
+        let hint = plugin
+            .matcher
+            .hint_for_target(target_id)
+            .unwrap()
+            .text
+            .clone();
+        for c in hint.chars() {
+            if let Some(selected) = plugin.handle_hint_char(c) {
+                plugin.select_target(selected, ctx);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nested_scope_descends_into_containers() {
+        let mut plugin =
+            NavPlugin::with_config(NavConfig::new().with_hint_scope(HintScope::Nested));
+        let mut ctx = LocustContext::default();
+        nested_targets(&mut ctx);
+
+        plugin.enter_hint_mode(&mut ctx);
+        assert_eq!(hinted_ids(&plugin), vec![1, 5]);
+
+        type_hint(&mut plugin, &mut ctx, 1);
+        assert_eq!(plugin.mode, NavMode::Hint);
+        assert_eq!(plugin.scope_path(), &[1]);
+        assert_eq!(hinted_ids(&plugin), vec![2, 3]);
+        assert!(ctx.actions.drain().is_empty());
+
+        plugin.handle_backspace(&mut ctx);
+        assert!(plugin.scope_path().is_empty());
+        assert_eq!(hinted_ids(&plugin), vec![1, 5]);
+
+        type_hint(&mut plugin, &mut ctx, 1);
+        type_hint(&mut plugin, &mut ctx, 2);
+        assert_eq!(plugin.mode, NavMode::Normal);
+        assert!(plugin.scope_path().is_empty());
+        assert_eq!(ctx.actions.drain()[0].target_id, 2);
+    }
+
+    #[test]
+    fn test_focused_scope_hints_inside_container() {
+        let mut plugin =
+            NavPlugin::with_config(NavConfig::new().with_hint_scope(HintScope::Focused));
+        let mut ctx = LocustContext::default();
+        nested_targets(&mut ctx);
+
+        plugin.enter_hint_mode(&mut ctx);
+        assert_eq!(hinted_ids(&plugin), vec![1, 2, 3, 4, 5]);
+        plugin.exit_hint_mode(&mut ctx);
+
+        ctx.focus_target(2);
+        plugin.enter_hint_mode(&mut ctx);
+        assert_eq!(hinted_ids(&plugin), vec![2, 3, 4]);
+        plugin.exit_hint_mode(&mut ctx);
+
+        ctx.focus_target(3);
+        plugin.enter_hint_mode(&mut ctx);
+        assert_eq!(hinted_ids(&plugin), vec![4]);
+    }
+
+    #[test]
+    fn test_yank_copies_metadata_and_reports() {
+        use crate::core::clipboard::MemoryClipboard;
+        use crossterm::event::KeyEvent;
+        use ratatui::backend::TestBackend;
+
+        let clipboard = MemoryClipboard::new();
+        let config = NavConfig::new().with_yank_source(YankSource::Metadata("path".into()));
+        let mut plugin = NavPlugin::with_config(config).with_clipboard(clipboard.clone());
+        let mut ctx = LocustContext::default();
+        ctx.targets.register(
+            NavTarget::new(1, Rect::new(0, 0, 10, 1))
+                .with_label("main.rs")
+                .with_metadata("path", "src/main.rs"),
+        );
+        ctx.targets
+            .register(NavTarget::new(2, Rect::new(0, 2, 10, 1)).with_label("README"));
+
+        // Yank mode has no default key
+        let yank = Event::Key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &yank, &mut ctx);
+        assert_eq!(plugin.mode, NavMode::Normal);
+
+        ctx.bind_key("nav.yank", KeyBinding::new(KeyCodeDef::Char('y')))
+            .unwrap();
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &yank, &mut ctx);
+        assert_eq!(plugin.mode, NavMode::Yank);
+        assert_eq!(ctx.modes.current(), &Mode::Hint);
+
+        type_hint(&mut plugin, &mut ctx, 1);
+        assert_eq!(plugin.mode, NavMode::Normal);
+        assert_eq!(clipboard.contents().as_deref(), Some("src/main.rs"));
+        assert_eq!(plugin.message(), Some("Copied: src/main.rs"));
+        assert!(ctx.actions.drain().is_empty());
+
+        // A target without the field falls back to its label
+        plugin.enter_mode(NavMode::Yank, &mut ctx);
+        assert_eq!(plugin.message(), None);
+        type_hint(&mut plugin, &mut ctx, 2);
+        assert_eq!(clipboard.contents().as_deref(), Some("README"));
+
+        let deadline = LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx).unwrap();
+        assert!(LocustPlugin::<TestBackend>::on_tick(
+            &mut plugin,
+            deadline,
+            &mut ctx
+        ));
+        assert_eq!(plugin.message(), None);
+    }
+
+    #[test]
+    fn test_yank_text_is_read_from_the_frame() {
+        use crate::core::clipboard::MemoryClipboard;
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let clipboard = MemoryClipboard::new();
+        let config = NavConfig::new().with_yank_source(YankSource::Text);
+        let mut plugin = NavPlugin::with_config(config).with_clipboard(clipboard.clone());
+        let mut ctx = LocustContext::default();
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(2, 1, 12, 2)).with_label("Error"));
+        plugin.enter_mode(NavMode::Yank, &mut ctx);
+
+        let mut terminal = Terminal::new(TestBackend::new(20, 4)).unwrap();
+        terminal
+            .draw(|frame| {
+                let buffer = frame.buffer_mut();
+                buffer.set_string(2, 1, "disk full", ratatui::style::Style::default());
+                buffer.set_string(2, 2, "  at /var", ratatui::style::Style::default());
+                LocustPlugin::<TestBackend>::render_overlay(&plugin, frame, &ctx);
+            })
+            .unwrap();
+
+        type_hint(&mut plugin, &mut ctx, 1);
+        assert_eq!(
+            clipboard.contents().as_deref(),
+            Some("disk full\n  at /var")
+        );
+        assert_eq!(plugin.message(), Some("Copied: disk full at /var"));
+    }
+
+    #[test]
+    fn test_hints_start_near_focus_and_follow_activations() {
+        let config = NavConfig::new()
+            .with_hint_order(HintOrder::NearFocus)
+            .with_activation_weight(4.0);
+        let mut plugin = NavPlugin::with_config(config);
+        let mut ctx = LocustContext::default();
+        for (id, y) in [(1, 0), (2, 4), (3, 8), (4, 12)] {
+            ctx.targets.register(
+                NavTarget::new(id, Rect::new(0, y, 10, 1)).with_label(format!("row {}", id)),
+            );
+        }
+
+        let hinted = |plugin: &NavPlugin| -> Vec<u64> {
+            plugin.matcher.hints().iter().map(|h| h.target_id).collect()
+        };
+
+        ctx.focus_target(4);
+        plugin.enter_hint_mode(&mut ctx);
+        assert_eq!(hinted(&plugin), vec![4, 3, 2, 1]);
+
+        // One activation makes row 1 rank (24 + 1) / 5 = 5, ahead of row 3 (9)
+        type_hint(&mut plugin, &mut ctx, 1);
+        ctx.focus_target(4);
+        plugin.enter_hint_mode(&mut ctx);
+        assert_eq!(hinted(&plugin), vec![4, 1, 3, 2]);
+        plugin.exit_hint_mode(&mut ctx);
+
+        // Without focus, the centre of the targets (row 6) is used
+        ctx.blur();
+        let mut fresh =
+            NavPlugin::with_config(NavConfig::new().with_hint_order(HintOrder::NearFocus));
+        fresh.enter_hint_mode(&mut ctx);
+        assert_eq!(hinted(&fresh), vec![2, 3, 1, 4]);
+    }
 }
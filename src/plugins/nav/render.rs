@@ -4,8 +4,10 @@
 
 use super::config::NavConfig;
 use super::hints::{Hint, HintMatcher};
+use crate::core::overlay::OcclusionMask;
 use crate::core::targets::{NavTarget, TargetRegistry};
 use ratatui::{
+    buffer::Buffer,
     layout::Rect,
     style::Style,
     text::{Line, Span},
@@ -94,6 +96,7 @@ impl HintRenderer {
         matcher: &HintMatcher,
         registry: &TargetRegistry,
         config: &NavConfig,
+        mask: &OcclusionMask,
     ) {
         // Build a map from target ID to hint
         let hint_map: HashMap<u64, &Hint> =
@@ -110,7 +113,7 @@ impl HintRenderer {
         for target in registry.all() {
             if let Some(hint) = hint_map.get(&target.id) {
                 let is_matching = matching.contains_key(&target.id);
-                self.render_hint(frame, hint, target, config, is_matching);
+                self.render_hint(frame, hint, target, config, is_matching, mask);
             }
         }
     }
@@ -123,12 +126,13 @@ impl HintRenderer {
         target: &NavTarget,
         config: &NavConfig,
         is_matching: bool,
+        mask: &OcclusionMask,
     ) {
         // Calculate hint area based on position
         let hint_area = self.calculate_hint_area(target, hint, frame.area());
 
-        // Check if hint area is visible
-        if hint_area.width == 0 || hint_area.height == 0 {
+        // Check if hint area is visible and not covered by a higher layer
+        if hint_area.width == 0 || hint_area.height == 0 || mask.is_occluded(hint_area) {
             return;
         }
 
@@ -227,34 +231,60 @@ impl HintRenderer {
 ///
 /// Shows the current input and hint count at the top of the screen.
 pub fn render_hint_banner(frame: &mut Frame, matcher: &HintMatcher, style: Style) {
-    let area = {
-        let size = frame.area();
-        Rect {
-            x: size.x,
-            y: size.y,
-            width: size.width,
-            height: 1,
-        }
-    };
+    render_banner(frame, "Hint mode", matcher, style);
+}
 
+/// Renders a status banner for a hint mode named `title`.
+pub fn render_banner(frame: &mut Frame, title: &str, matcher: &HintMatcher, style: Style) {
     let matching_count = matcher.matching_hints().len();
     let total_count = matcher.hints().len();
     let input = matcher.input();
 
     let text = if input.is_empty() {
-        format!(" Hint mode: {} targets (press Esc to exit) ", total_count)
+        format!(" {}: {} targets (press Esc to exit) ", title, total_count)
     } else {
         format!(
-            " Hint mode: {} [{}/{}] ",
-            input, matching_count, total_count
+            " {}: {} [{}/{}] ",
+            title, input, matching_count, total_count
         )
     };
+    render_message(frame, &text, style);
+}
 
-    let line = Line::from(vec![Span::styled(text, style)]);
+/// Renders `text` in the banner line at the top of the screen.
+pub fn render_message(frame: &mut Frame, text: &str, style: Style) {
+    let area = {
+        let size = frame.area();
+        Rect {
+            x: size.x,
+            y: size.y,
+            width: size.width,
+            height: 1,
+        }
+    };
+
+    let line = Line::from(vec![Span::styled(text.to_string(), style)]);
     let paragraph = Paragraph::new(line);
     frame.render_widget(paragraph, area);
 }
 
+/// Text drawn in `rect`, one line per row with trailing blanks removed.
+pub fn cell_text(buffer: &Buffer, rect: Rect) -> String {
+    let rect = rect.intersection(buffer.area);
+    (rect.top()..rect.bottom())
+        .map(|y| {
+            (rect.left()..rect.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
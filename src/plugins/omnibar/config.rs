@@ -18,8 +18,10 @@ use ratatui::style::{Color, Modifier, Style};
 /// ```
 #[derive(Debug, Clone)]
 pub struct OmnibarConfig {
-    /// Key that activates the omnibar (default: '/')
-    pub activation_key: char,
+    /// Key that activates the omnibar, replacing the `omnibar.activate`
+    /// keymap binding (Ctrl+P) when the plugin is registered
+    /// (default: None, use the keymap)
+    pub activation_key: Option<char>,
 
     /// Maximum width of the omnibar popup as percentage of screen width (0-100)
     pub max_width_percent: u16,
@@ -75,7 +77,7 @@ impl OmnibarConfig {
     /// Creates a new configuration with sensible defaults.
     pub fn new() -> Self {
         Self {
-            activation_key: '/',
+            activation_key: None,
             max_width_percent: 60,
             max_height: 3,
             placeholder_text: "Type a command...".to_string(),
@@ -97,7 +99,7 @@ impl OmnibarConfig {
 
     /// Sets the activation key.
     pub fn with_activation_key(mut self, key: char) -> Self {
-        self.activation_key = key;
+        self.activation_key = Some(key);
         self
     }
 
@@ -181,7 +183,7 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = OmnibarConfig::default();
-        assert_eq!(config.activation_key, '/');
+        assert_eq!(config.activation_key, None);
         assert_eq!(config.max_width_percent, 60);
         assert_eq!(config.max_height, 3);
         assert_eq!(config.max_history, 10);
@@ -196,7 +198,7 @@ mod tests {
             .with_placeholder("Enter command")
             .with_max_history(20);
 
-        assert_eq!(config.activation_key, ':');
+        assert_eq!(config.activation_key, Some(':'));
         assert_eq!(config.max_width_percent, 80);
         assert_eq!(config.max_height, 5);
         assert_eq!(config.placeholder_text, "Enter command");
@@ -1,7 +1,8 @@
 //! Locust Omnibar plugin - Command palette for terminal UIs.
 //!
 //! This plugin provides a command palette (omnibar) for quick command execution.
-//! Press '/' (configurable) to activate, type commands, and press Enter to execute.
+//! Press Ctrl+P (the `omnibar.activate` keymap binding) to activate, type commands,
+//! and press Enter to execute.
 //!
 //! # Features
 //!
@@ -40,20 +41,28 @@ pub use state::OmnibarMode;
 
 use crate::core::context::LocustContext;
 use crate::core::input::PluginEventResult;
+use crate::core::modes::Mode;
 use crate::core::plugin::LocustPlugin;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crate::plugins::BUILTIN_PRIORITY;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use ratatui::backend::Backend;
 use ratatui::Frame;
 use render::OmnibarRenderer;
 use state::OmnibarState;
 use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// How long an error message stays in the omnibar before it is dismissed.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Plugin ID, also the owner of the command mode on the mode stack.
+const PLUGIN_ID: &str = "locust.omnibar";
 
 /// Command palette plugin for quick command execution.
 ///
 /// This plugin manages the complete omnibar workflow:
-/// 1. User presses activation key (default: '/')
+/// 1. User presses the activation key (default: Ctrl+P)
 /// 2. Omnibar popup appears with input field
 /// 3. User types command and can navigate history
 /// 4. Press Enter to execute, Esc to cancel
@@ -224,18 +233,39 @@ impl OmnibarPlugin {
     /// Activates the omnibar and marks overlay.
     fn activate(&mut self, ctx: &mut LocustContext) {
         self.state.activate();
+        ctx.modes.push(Mode::Command, PLUGIN_ID);
         ctx.overlay.mark_has_overlay();
     }
 
     /// Deactivates the omnibar.
-    fn deactivate(&mut self) {
+    fn deactivate(&mut self, ctx: &mut LocustContext) {
         log::info!("Omnibar: Deactivating.");
         self.state.deactivate();
+        ctx.modes.remove(&Mode::Command, PLUGIN_ID);
+    }
+
+    /// Activates the target whose label matches `query`, ignoring case.
+    ///
+    /// Returns `true` if a target was activated.
+    fn jump_to_target(query: &str, ctx: &mut LocustContext) -> bool {
+        let target_id = ctx
+            .targets
+            .all()
+            .iter()
+            .find(|t| {
+                t.label
+                    .as_deref()
+                    .is_some_and(|label| label.eq_ignore_ascii_case(query))
+            })
+            .map(|t| t.id);
+
+        target_id.is_some_and(|id| ctx.activate_target(id))
     }
 
     /// Handles command submission.
     ///
-    /// Looks up the command in the registry and executes it.
+    /// Looks up the command in the registry and executes it. Input that
+    /// names no command but matches a target label jumps to that target.
     /// Special handling for clear-history command to clear the omnibar's history.
     fn handle_submit(&mut self, ctx: &mut LocustContext) {
         if let Some(command_name) = self.state.submit() {
@@ -245,6 +275,18 @@ impl OmnibarPlugin {
                 self.state.clear_history();
             }
 
+            // Input naming a target instead of a command jumps to it
+            let is_command = self
+                .registry
+                .lock()
+                .map(|registry| registry.contains(&command_name))
+                .unwrap_or(false);
+            if !is_command && Self::jump_to_target(&command_name, ctx) {
+                log::info!("Omnibar: Jumped to target '{}'.", command_name);
+                self.deactivate(ctx);
+                return;
+            }
+
             // Execute the command
             if let Ok(registry) = self.registry.lock() {
                 match registry.execute(&command_name, ctx) {
@@ -253,19 +295,19 @@ impl OmnibarPlugin {
                     }
                     Err(err) => {
                         log::error!("Locust Omnibar Error: {}", err);
-                        self.state.message = Some((format!("Error: {}", err), Instant::now()));
+                        self.state.message = Some((format!("Error: {}", err), ctx.now()));
                     }
                 }
             } else {
                 let err_msg = "Failed to access command registry".to_string();
                 log::error!("Locust Omnibar Error: {}", err_msg);
-                self.state.message = Some((format!("Error: {}", err_msg), Instant::now()));
+                self.state.message = Some((format!("Error: {}", err_msg), ctx.now()));
             }
-            self.deactivate(); // Deactivate after command submission
+            self.deactivate(ctx); // Deactivate after command submission
         } else {
             // Empty input - just deactivate
             log::info!("Omnibar: Empty command submitted, deactivating.");
-            self.deactivate();
+            self.deactivate(ctx);
         }
     }
 }
@@ -275,89 +317,95 @@ where
     B: Backend + 'static,
 {
     fn id(&self) -> &'static str {
-        "locust.omnibar"
+        PLUGIN_ID
     }
 
     fn priority(&self) -> i32 {
-        40 // Higher priority than nav plugin (50), processes events first
+        BUILTIN_PRIORITY
+    }
+
+    fn before(&self) -> &'static [&'static str] {
+        // The open palette takes keys before tooltips and hints
+        &["locust.tooltip", "locust.nav"]
     }
 
-    fn init(&mut self, _ctx: &mut LocustContext) {
-        // Plugin is initialized
+    fn init(&mut self, ctx: &mut LocustContext) {
+        ctx.bind_configured_key("omnibar.activate", self.config.activation_key);
     }
 
     fn on_event(&mut self, event: &Event, ctx: &mut LocustContext) -> PluginEventResult {
-        // Dismiss message if expired
-        if let Some((_, timestamp)) = &self.state.message {
-            if timestamp.elapsed() > std::time::Duration::from_secs(3) {
-                self.state.message = None;
-                if self.state.is_active() {
-                    return PluginEventResult::ConsumedRequestRedraw;
-                }
+        if let Event::Key(key) = event {
+            // Leave keys alone while another plugin's mode is on top
+            let blocked = match self.state.mode() {
+                OmnibarMode::Inactive => ctx.is_capturing_input(),
+                OmnibarMode::Input | OmnibarMode::Filtered => !ctx.modes.is_current(&Mode::Command),
+            };
+            if blocked {
+                return PluginEventResult::NotHandled;
             }
-        }
 
-        if let Event::Key(KeyEvent {
-            code, modifiers, ..
-        }) = event
-        {
-            match (self.state.mode(), code, modifiers) {
-                // Inactive: activate on configured key
-                (OmnibarMode::Inactive, KeyCode::Char(c), m)
-                    if *c == self.config.activation_key && (*m == KeyModifiers::NONE || (*c == 'O' && *m == KeyModifiers::SHIFT)) =>
-                {
+            let action = ctx.plugin_action("omnibar", key);
+
+            match (
+                self.state.mode(),
+                action.as_deref(),
+                &key.code,
+                &key.modifiers,
+            ) {
+                // Inactive: activate on the bound key
+                (OmnibarMode::Inactive, Some("omnibar.activate"), _, _) => {
                     self.activate(ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
-                // Active: handle escape to cancel
-                (OmnibarMode::Input, KeyCode::Esc, _) => {
-                    self.deactivate();
+                // Active: cancel on the bound key
+                (OmnibarMode::Input, Some("omnibar.cancel"), _, _) => {
+                    self.deactivate(ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Active: handle enter to submit
-                (OmnibarMode::Input, KeyCode::Enter, _) => {
+                (OmnibarMode::Input, _, KeyCode::Enter, _) => {
                     self.handle_submit(ctx);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Active: handle backspace
-                (OmnibarMode::Input, KeyCode::Backspace, _) => {
+                (OmnibarMode::Input, _, KeyCode::Backspace, _) => {
                     self.state.delete_char();
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Active: handle character input
-                (OmnibarMode::Input, KeyCode::Char(c), m) if *m == KeyModifiers::NONE => {
+                (OmnibarMode::Input, _, KeyCode::Char(c), m) if *m == KeyModifiers::NONE => {
                     self.state.insert_char(*c);
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Active: cursor movement
-                (OmnibarMode::Input, KeyCode::Left, _) => {
+                (OmnibarMode::Input, _, KeyCode::Left, _) => {
                     self.state.move_cursor_left();
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
-                (OmnibarMode::Input, KeyCode::Right, _) => {
+                (OmnibarMode::Input, _, KeyCode::Right, _) => {
                     self.state.move_cursor_right();
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
-                (OmnibarMode::Input, KeyCode::Home, _) => {
+                (OmnibarMode::Input, _, KeyCode::Home, _) => {
                     self.state.move_cursor_home();
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
-                (OmnibarMode::Input, KeyCode::End, _) => {
+                (OmnibarMode::Input, _, KeyCode::End, _) => {
                     self.state.move_cursor_end();
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
 
                 // Active: history navigation
-                (OmnibarMode::Input, KeyCode::Up, _) => {
+                (OmnibarMode::Input, _, KeyCode::Up, _) => {
                     self.state.history_prev();
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
-                (OmnibarMode::Input, KeyCode::Down, _) => {
+                (OmnibarMode::Input, _, KeyCode::Down, _) => {
                     self.state.history_next();
                     return PluginEventResult::ConsumedRequestRedraw;
                 }
@@ -369,6 +417,30 @@ where
         PluginEventResult::NotHandled
     }
 
+    fn on_tick(&mut self, now: Instant, _ctx: &mut LocustContext) -> bool {
+        // Dismiss message if expired
+        match &self.state.message {
+            Some((_, timestamp)) if now >= *timestamp + MESSAGE_TIMEOUT => {
+                self.state.message = None;
+                self.state.is_active()
+            }
+            _ => false,
+        }
+    }
+
+    fn next_deadline(&self, _ctx: &LocustContext) -> Option<Instant> {
+        self.state
+            .message
+            .as_ref()
+            .map(|(_, timestamp)| *timestamp + MESSAGE_TIMEOUT)
+    }
+
+    fn on_mode_exit(&mut self, mode: &Mode, ctx: &mut LocustContext) {
+        if *mode == Mode::Command {
+            self.deactivate(ctx);
+        }
+    }
+
     fn render_overlay(&self, frame: &mut Frame, _ctx: &LocustContext) {
         if !self.state.is_active() {
             return;
@@ -379,9 +451,9 @@ where
             .render(frame, &self.state, &self.config, &suggestions);
     }
 
-    fn cleanup(&mut self, _ctx: &mut LocustContext) {
+    fn cleanup(&mut self, ctx: &mut LocustContext) {
         // Cleanup if needed
-        self.deactivate();
+        self.deactivate(ctx);
     }
 }
 
@@ -402,7 +474,7 @@ mod tests {
             .with_activation_key(':')
             .with_max_width(80);
         let plugin = OmnibarPlugin::with_config(config);
-        assert_eq!(plugin.config().activation_key, ':');
+        assert_eq!(plugin.config().activation_key, Some(':'));
         assert_eq!(plugin.config().max_width_percent, 80);
     }
 
@@ -417,9 +489,12 @@ mod tests {
         assert!(plugin.state().is_active());
         assert_eq!(plugin.state().mode(), OmnibarMode::Input);
 
-        plugin.deactivate();
+        assert!(ctx.is_capturing_input());
+
+        plugin.deactivate(&mut ctx);
         assert!(!plugin.state().is_active());
         assert_eq!(plugin.state().mode(), OmnibarMode::Inactive);
+        assert!(!ctx.is_capturing_input());
     }
 
     #[test]
@@ -457,11 +532,79 @@ mod tests {
         assert_eq!(plugin.state().history()[0], "cmd");
     }
 
+    #[test]
+    fn test_submit_target_label_jumps_to_target() {
+        use crate::core::targets::NavTarget;
+        use ratatui::layout::Rect;
+        use std::sync::atomic::Ordering;
+
+        let mut plugin = OmnibarPlugin::new();
+        let mut ctx = LocustContext::default();
+        let called = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&called);
+
+        ctx.targets.register_with_callback(
+            NavTarget::new(7, Rect::new(0, 0, 10, 1)).with_label("Settings"),
+            move |_| {
+                flag.store(true, Ordering::SeqCst);
+                true
+            },
+        );
+
+        plugin.activate(&mut ctx);
+        for c in "settings".chars() {
+            plugin.state_mut().insert_char(c);
+        }
+        plugin.handle_submit(&mut ctx);
+
+        assert!(called.load(Ordering::SeqCst));
+        assert!(ctx.actions.is_empty());
+        assert!(plugin.state().message.is_none());
+    }
+
+    #[test]
+    fn test_tick_dismisses_expired_message() {
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = OmnibarPlugin::new();
+        let mut ctx = LocustContext::default();
+        plugin.activate(&mut ctx);
+
+        let shown_at = Instant::now();
+        plugin.state_mut().message = Some(("Error: boom".into(), shown_at));
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            Some(shown_at + MESSAGE_TIMEOUT)
+        );
+
+        let before = shown_at + MESSAGE_TIMEOUT - Duration::from_millis(1);
+        assert!(!LocustPlugin::<TestBackend>::on_tick(
+            &mut plugin,
+            before,
+            &mut ctx
+        ));
+        assert!(plugin.state().message.is_some());
+
+        let after = shown_at + MESSAGE_TIMEOUT;
+        assert!(LocustPlugin::<TestBackend>::on_tick(
+            &mut plugin,
+            after,
+            &mut ctx
+        ));
+        assert!(plugin.state().message.is_none());
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            None
+        );
+    }
+
     #[test]
     fn test_plugin_priority() {
         let plugin = OmnibarPlugin::new();
         use ratatui::backend::TestBackend;
-        // Omnibar should have higher priority than nav (50)
-        assert!(<OmnibarPlugin as LocustPlugin<TestBackend>>::priority(&plugin) < 50);
+        // Omnibar should see events before nav
+        assert!(
+            <OmnibarPlugin as LocustPlugin<TestBackend>>::before(&plugin).contains(&"locust.nav")
+        );
     }
 }
@@ -0,0 +1,194 @@
+//! Configuration for the pattern hint plugin.
+//!
+//! Patterns are read from the `[plugins.patterns]` section of the Locust
+//! config file:
+//!
+//! ```toml
+//! [plugins.patterns]
+//! builtin = true          # URLs, paths, git hashes, IPs, issue numbers
+//! charset = "asdfghjkl"
+//!
+//! [[plugins.patterns.patterns]]
+//! name = "ticket"
+//! regex = "[A-Z]{2,}-[0-9]+"
+//! action = "open"         # or "copy" (the default)
+//! ```
+
+use crate::core::targets::TargetAction;
+use serde::{Deserialize, Serialize};
+
+/// What the host application should do with a picked match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PatternAction {
+    /// Copy the matched text; emitted as `TargetAction::Custom("copy")`.
+    #[default]
+    Copy,
+    /// Open the matched text; emitted as `TargetAction::Custom("open")`.
+    Open,
+}
+
+impl PatternAction {
+    /// Name of the custom target action, `"copy"` or `"open"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            PatternAction::Copy => "copy",
+            PatternAction::Open => "open",
+        }
+    }
+
+    /// The action of targets created for matches.
+    pub fn target_action(self) -> TargetAction {
+        TargetAction::Custom(self.name().to_string())
+    }
+}
+
+/// A named text pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternDef {
+    /// Name of the pattern, stored in the `pattern` metadata of matches.
+    pub name: String,
+
+    /// Regular expression in the syntax of the `regex` crate.
+    pub regex: String,
+
+    /// Action of the matches.
+    #[serde(default)]
+    pub action: PatternAction,
+}
+
+impl PatternDef {
+    /// Creates a pattern whose matches are copied.
+    pub fn new(name: impl Into<String>, regex: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            regex: regex.into(),
+            action: PatternAction::default(),
+        }
+    }
+
+    /// Sets the action of the matches.
+    pub fn with_action(mut self, action: PatternAction) -> Self {
+        self.action = action;
+        self
+    }
+}
+
+/// The patterns enabled by `builtin`, tried in this order.
+pub fn builtin_patterns() -> Vec<PatternDef> {
+    vec![
+        PatternDef::new(
+            "url",
+            r#"(?:https?|ftp|file)://[^\s<>"'`]*[^\s<>"'`.,;:!?)\]]"#,
+        )
+        .with_action(PatternAction::Open),
+        PatternDef::new("ip", r"\b\d{1,3}(?:\.\d{1,3}){3}(?::\d{1,5})?\b"),
+        PatternDef::new("path", r"(?:[\w.\-~$@]+)?(?:/[\w.\-@]+)+/?")
+            .with_action(PatternAction::Open),
+        PatternDef::new("git_hash", r"\b[0-9a-f]{7,40}\b"),
+        PatternDef::new("issue", r"#\d+\b"),
+    ]
+}
+
+/// Configuration for the pattern hint plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PatternConfig {
+    /// Whether the [`builtin_patterns`] are used after the app's own.
+    pub builtin: bool,
+
+    /// Patterns of the application. Where matches overlap, the earlier
+    /// pattern wins.
+    pub patterns: Vec<PatternDef>,
+
+    /// Character set used for generating hints.
+    pub charset: String,
+}
+
+impl Default for PatternConfig {
+    fn default() -> Self {
+        Self {
+            builtin: true,
+            patterns: Vec::new(),
+            charset: "asdfghjkl".to_string(),
+        }
+    }
+}
+
+impl PatternConfig {
+    /// Creates a configuration with the built-in patterns only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pattern, tried after the ones already added.
+    pub fn with_pattern(mut self, pattern: PatternDef) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Sets whether the built-in patterns are used.
+    pub fn with_builtin(mut self, builtin: bool) -> Self {
+        self.builtin = builtin;
+        self
+    }
+
+    /// Sets the hint character set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the charset is empty.
+    pub fn with_charset(mut self, charset: impl Into<String>) -> Self {
+        let charset = charset.into();
+        assert!(!charset.is_empty(), "Hint charset cannot be empty");
+        self.charset = charset;
+        self
+    }
+
+    /// All patterns in the order they are tried.
+    pub fn all_patterns(&self) -> Vec<PatternDef> {
+        let mut patterns = self.patterns.clone();
+        if self.builtin {
+            patterns.extend(builtin_patterns());
+        }
+        patterns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_toml_section() {
+        let config: PatternConfig = toml::from_str(
+            r#"
+            builtin = false
+
+            [[patterns]]
+            name = "ticket"
+            regex = "[A-Z]{2,}-[0-9]+"
+            action = "open"
+            "#,
+        )
+        .unwrap();
+
+        assert!(!config.builtin);
+        assert_eq!(config.charset, "asdfghjkl");
+        assert_eq!(
+            config.all_patterns(),
+            vec![PatternDef::new("ticket", "[A-Z]{2,}-[0-9]+").with_action(PatternAction::Open)]
+        );
+    }
+
+    #[test]
+    fn test_app_patterns_come_first() {
+        let config = PatternConfig::new().with_pattern(PatternDef::new("sha", "[0-9a-f]{64}"));
+        let names: Vec<String> = config.all_patterns().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["sha", "url", "ip", "path", "git_hash", "issue"]);
+        assert_eq!(
+            PatternAction::Open.target_action(),
+            TargetAction::Custom("open".into())
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! Locust pattern hint plugin - Hints on text the app never registered.
+//!
+//! Like tmux-fingers or Vimium's link hints, this plugin finds URLs, file
+//! paths, git hashes, IP addresses and issue numbers in the rendered frame
+//! and labels them with hints. Press `F` (the `patterns.activate` keymap
+//! binding) and type a hint to pick a match: the plugin emits a
+//! `TargetAction::Custom("copy")` or `Custom("open")` activation labelled
+//! with the matched text, for the host application to perform. Matches get
+//! IDs above [`PATTERN_ID_BASE`], apart from the application's own targets.
+//!
+//! The text is read from the `Buffer` after the application has drawn it,
+//! on a `"scan"` layer below all other overlays so it sees the frame before
+//! they cover it. The hints themselves are drawn on the plugin's default
+//! layer, in the normal overlay band alongside the navigation hints. Patterns come from [`PatternConfig`], or from
+//! the `[plugins.patterns]` section of the config file.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::plugins::patterns::{PatternAction, PatternConfig, PatternDef, PatternPlugin};
+//!
+//! let plugin = PatternPlugin::with_config(
+//!     PatternConfig::new()
+//!         .with_pattern(PatternDef::new("ticket", "[A-Z]{2,}-[0-9]+").with_action(PatternAction::Open)),
+//! );
+//! assert!(!plugin.is_active());
+//! ```
+
+pub mod config;
+pub mod scan;
+
+pub use config::{builtin_patterns, PatternAction, PatternConfig, PatternDef};
+pub use scan::{PATTERN_ID_BASE, PATTERN_METADATA_KEY};
+
+use crate::core::actions::TargetActivation;
+use crate::core::context::LocustContext;
+use crate::core::input::PluginEventResult;
+use crate::core::modes::Mode;
+use crate::core::overlay::{OcclusionMask, OverlayLayer, DEFAULT_Z_INDEX};
+use crate::core::plugin::LocustPlugin;
+use crate::core::targets::TargetRegistry;
+use crate::plugins::nav::hints::{HintGenerator, HintMatcher};
+use crate::plugins::nav::render::{self as hint_render, HintRenderer};
+use crate::plugins::nav::NavConfig;
+use crate::plugins::BUILTIN_PRIORITY;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use log::info;
+use ratatui::backend::Backend;
+use ratatui::Frame;
+use scan::{CompiledPattern, ScreenLine};
+use std::cell::RefCell;
+
+/// Plugin ID, also the owner of its hint mode on the mode stack.
+const PLUGIN_ID: &str = "locust.patterns";
+
+/// Section of the config file holding the [`PatternConfig`].
+pub const CONFIG_SECTION: &str = "patterns";
+
+/// Name of the layer that reads the frame.
+const SCAN_LAYER: &str = "scan";
+
+/// Z-index of the scan layer, below all other overlays so the frame is read
+/// before they are drawn.
+const SCAN_Z_INDEX: i32 = -100;
+
+/// Hint mode over text patterns found in the rendered frame.
+pub struct PatternPlugin {
+    config: PatternConfig,
+    patterns: Vec<CompiledPattern>,
+    active: bool,
+
+    /// Text of the last frame drawn while inactive
+    screen: RefCell<Vec<ScreenLine>>,
+
+    /// Matches of the current hint mode
+    targets: TargetRegistry,
+
+    generator: HintGenerator,
+    matcher: HintMatcher,
+    renderer: HintRenderer,
+
+    /// Hint styles, shared with the navigation plugin
+    hint_config: NavConfig,
+}
+
+impl Default for PatternPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatternPlugin {
+    /// Creates a plugin with the built-in patterns.
+    pub fn new() -> Self {
+        Self::with_config(PatternConfig::default())
+    }
+
+    /// Creates a plugin with custom patterns.
+    ///
+    /// Patterns that are not valid regular expressions are logged and
+    /// left out.
+    pub fn with_config(config: PatternConfig) -> Self {
+        let hint_config = NavConfig::new().with_charset(config.charset.clone());
+        Self {
+            patterns: scan::compile(&config.all_patterns()),
+            generator: HintGenerator::new(config.charset.clone()),
+            config,
+            active: false,
+            screen: RefCell::new(Vec::new()),
+            targets: TargetRegistry::new(),
+            matcher: HintMatcher::new(),
+            renderer: HintRenderer::new(),
+            hint_config,
+        }
+    }
+
+    /// Returns the current configuration.
+    pub fn config(&self) -> &PatternConfig {
+        &self.config
+    }
+
+    /// Returns whether pattern hints are shown.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// The matches hinted in the current hint mode.
+    pub fn targets(&self) -> &TargetRegistry {
+        &self.targets
+    }
+
+    /// Scans the last frame and shows hints on the matches.
+    ///
+    /// Returns `false`, staying inactive, if nothing matched.
+    pub fn activate(&mut self, ctx: &mut LocustContext) -> bool {
+        let found = scan::find_targets(&self.screen.borrow(), &self.patterns);
+        if found.is_empty() {
+            return false;
+        }
+
+        self.targets.clear();
+        for target in found {
+            self.targets.register(target);
+        }
+        let hints = self.generator.generate(self.targets.all());
+        self.matcher.set_hints(hints);
+
+        self.active = true;
+        ctx.modes.push(Mode::Hint, PLUGIN_ID);
+        ctx.overlay.mark_has_overlay();
+        true
+    }
+
+    /// Hides the hints.
+    pub fn deactivate(&mut self, ctx: &mut LocustContext) {
+        self.active = false;
+        self.matcher.clear();
+        self.targets.clear();
+        ctx.modes.remove(&Mode::Hint, PLUGIN_ID);
+    }
+
+    /// Emits the activation of a picked match and leaves hint mode.
+    fn pick(&mut self, target_id: u64, ctx: &mut LocustContext) {
+        if let Some(target) = self.targets.by_id(target_id) {
+            let mut activation = TargetActivation::new(target_id, target.action.clone());
+            if let Some(label) = &target.label {
+                info!("Locust: Picked pattern match {}", label);
+                activation = activation.with_label(label.clone());
+            }
+            ctx.actions.push(activation);
+        }
+        self.deactivate(ctx);
+    }
+
+    /// Takes the config file's `[plugins.patterns]` section, if any.
+    fn load_config(&mut self, ctx: &LocustContext) {
+        if let Some(config) = ctx.get_plugin_config::<PatternConfig>(CONFIG_SECTION) {
+            *self = Self::with_config(config);
+        }
+    }
+
+    /// Reads the frame, or draws the hints while active.
+    /// Read the frame's text while hint mode is off.
+    fn scan_frame(&self, frame: &mut Frame) {
+        if !self.active && !self.patterns.is_empty() {
+            *self.screen.borrow_mut() = scan::screen_lines(frame.buffer_mut());
+        }
+    }
+
+    /// Draw the hints while hint mode is on.
+    fn render_hints(&self, frame: &mut Frame, mask: &OcclusionMask) {
+        if !self.active {
+            return;
+        }
+
+        hint_render::render_hint_banner(frame, &self.matcher, self.hint_config.banner_style);
+        self.renderer
+            .render(frame, &self.matcher, &self.targets, &self.hint_config, mask);
+    }
+}
+
+impl<B> LocustPlugin<B> for PatternPlugin
+where
+    B: Backend + 'static,
+{
+    fn id(&self) -> &'static str {
+        PLUGIN_ID
+    }
+
+    fn priority(&self) -> i32 {
+        BUILTIN_PRIORITY
+    }
+
+    fn init(&mut self, ctx: &mut LocustContext) {
+        self.load_config(ctx);
+        ctx.overlay
+            .add_layer(OverlayLayer::named(PLUGIN_ID, SCAN_LAYER, SCAN_Z_INDEX));
+        ctx.overlay
+            .add_layer(OverlayLayer::new(PLUGIN_ID, DEFAULT_Z_INDEX));
+    }
+
+    fn on_event(&mut self, event: &Event, ctx: &mut LocustContext) -> PluginEventResult {
+        let Event::Key(key) = event else {
+            return PluginEventResult::NotHandled;
+        };
+
+        if !self.active {
+            if ctx.is_capturing_input()
+                || ctx.plugin_action("patterns", key).as_deref() != Some("patterns.activate")
+            {
+                return PluginEventResult::NotHandled;
+            }
+            return if self.activate(ctx) {
+                PluginEventResult::ConsumedRequestRedraw
+            } else {
+                PluginEventResult::Consumed
+            };
+        }
+
+        // Leave keys alone while another plugin's mode is on top
+        if ctx.modes.top().map(|frame| frame.owner.as_str()) != Some(PLUGIN_ID) {
+            return PluginEventResult::NotHandled;
+        }
+
+        match (ctx.plugin_action("patterns", key).as_deref(), key.code) {
+            (Some("patterns.cancel"), _) => self.deactivate(ctx),
+            (_, KeyCode::Backspace) => self.matcher.pop_char(),
+            (_, KeyCode::Char(c))
+                if key.modifiers == KeyModifiers::NONE && self.config.charset.contains(c) =>
+            {
+                if let Some(target_id) = self.matcher.push_char(c) {
+                    self.pick(target_id, ctx);
+                }
+            }
+            _ => return PluginEventResult::NotHandled,
+        }
+        PluginEventResult::ConsumedRequestRedraw
+    }
+
+    fn on_mode_exit(&mut self, mode: &Mode, ctx: &mut LocustContext) {
+        if *mode == Mode::Hint {
+            self.deactivate(ctx);
+        }
+    }
+
+    fn render_overlay(&self, frame: &mut Frame, _ctx: &LocustContext) {
+        self.scan_frame(frame);
+        self.render_hints(frame, &OcclusionMask::new());
+    }
+
+    fn render_layer(
+        &self,
+        frame: &mut Frame,
+        layer: &OverlayLayer,
+        mask: &OcclusionMask,
+        _ctx: &LocustContext,
+    ) {
+        if layer.name == SCAN_LAYER {
+            self.scan_frame(frame);
+        } else if layer.is_default() {
+            self.render_hints(frame, mask);
+        }
+    }
+
+    fn reload_config(&mut self, ctx: &LocustContext) {
+        if !self.active {
+            self.load_config(ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::targets::TargetAction;
+    use crossterm::event::KeyEvent;
+    use ratatui::backend::TestBackend;
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn capture(plugin: &PatternPlugin, line: &str) {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 40, 2));
+        buffer.set_string(0, 1, line, ratatui::style::Style::default());
+        *plugin.screen.borrow_mut() = scan::screen_lines(&buffer);
+    }
+
+    #[test]
+    fn test_pick_emits_custom_action() {
+        let mut plugin = PatternPlugin::new();
+        let mut ctx = LocustContext::default();
+        LocustPlugin::<TestBackend>::init(&mut plugin, &mut ctx);
+        capture(&plugin, "fixed in #12");
+
+        let result = LocustPlugin::<TestBackend>::on_event(
+            &mut plugin,
+            &Event::Key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT)),
+            &mut ctx,
+        );
+        assert!(result.is_consumed());
+        assert!(plugin.is_active());
+        assert_eq!(ctx.modes.current(), &Mode::Hint);
+
+        let hint = plugin.matcher.hints()[0].text.clone();
+        for c in hint.chars() {
+            LocustPlugin::<TestBackend>::on_event(&mut plugin, &key(KeyCode::Char(c)), &mut ctx);
+        }
+
+        assert!(!plugin.is_active());
+        assert_eq!(ctx.modes.current(), &Mode::Normal);
+        let actions = ctx.actions.drain();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].action, TargetAction::Custom("copy".into()));
+        assert_eq!(actions[0].label.as_deref(), Some("#12"));
+    }
+
+    #[test]
+    fn test_nothing_to_hint_stays_inactive() {
+        let mut plugin = PatternPlugin::new();
+        let mut ctx = LocustContext::default();
+        capture(&plugin, "plain text");
+
+        let result =
+            LocustPlugin::<TestBackend>::on_event(&mut plugin, &key(KeyCode::Char('F')), &mut ctx);
+        assert!(result.is_consumed());
+        assert!(!plugin.is_active());
+        assert_eq!(ctx.modes.current(), &Mode::Normal);
+    }
+
+    #[test]
+    fn test_config_section_replaces_patterns() {
+        let mut plugin = PatternPlugin::new();
+        let mut ctx = LocustContext::default();
+        let config: crate::core::config::LocustConfig = toml::from_str(
+            r#"
+            [plugins.patterns]
+            builtin = false
+            patterns = [{ name = "ticket", regex = "LOC-[0-9]+" }]
+            "#,
+        )
+        .unwrap();
+        ctx.update_config(config);
+        LocustPlugin::<TestBackend>::init(&mut plugin, &mut ctx);
+
+        assert!(!plugin.config().builtin);
+        capture(&plugin, "LOC-7 at #3");
+        assert!(plugin.activate(&mut ctx));
+        let labels: Vec<_> = plugin
+            .targets()
+            .all()
+            .iter()
+            .map(|t| t.label.clone().unwrap())
+            .collect();
+        assert_eq!(labels, vec!["LOC-7"]);
+    }
+}
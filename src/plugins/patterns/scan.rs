@@ -0,0 +1,197 @@
+//! Text capture and pattern matching over a rendered buffer.
+
+use super::config::{PatternAction, PatternDef};
+use crate::core::targets::NavTarget;
+use log::warn;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use regex::Regex;
+
+/// Metadata key holding the name of the pattern a target matched.
+pub const PATTERN_METADATA_KEY: &str = "pattern";
+
+/// Base of the target IDs given to matches.
+///
+/// Matches are numbered above it so the activations they emit never share
+/// an ID with a target the application registered.
+pub const PATTERN_ID_BASE: u64 = 1 << 63;
+
+/// One row of a rendered buffer as text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScreenLine {
+    /// Row of the line.
+    pub y: u16,
+
+    /// Column of the first cell.
+    pub x: u16,
+
+    /// Symbols of the row's cells, concatenated.
+    pub text: String,
+
+    /// Byte offset in `text` where each cell starts.
+    offsets: Vec<usize>,
+}
+
+impl ScreenLine {
+    /// Screen columns covered by the bytes `start..end` of `text`.
+    fn columns(&self, start: usize, end: usize) -> (u16, u16) {
+        let cell_at = |byte: usize| self.offsets.partition_point(|&offset| offset <= byte) - 1;
+        let first = cell_at(start);
+        let last = cell_at(end.saturating_sub(1).max(start));
+        (self.x + first as u16, (last - first + 1) as u16)
+    }
+}
+
+/// Reads every row of `buffer` as text.
+pub fn screen_lines(buffer: &Buffer) -> Vec<ScreenLine> {
+    let area = buffer.area;
+    (area.y..area.bottom())
+        .map(|y| {
+            let mut line = ScreenLine {
+                y,
+                x: area.x,
+                ..ScreenLine::default()
+            };
+            for x in area.x..area.right() {
+                line.offsets.push(line.text.len());
+                line.text.push_str(buffer[(x, y)].symbol());
+            }
+            line
+        })
+        .collect()
+}
+
+/// A pattern compiled for scanning.
+#[derive(Debug, Clone)]
+pub struct CompiledPattern {
+    pub name: String,
+    pub regex: Regex,
+    pub action: PatternAction,
+}
+
+/// Compiles `patterns`, leaving out (and logging) those that do not parse.
+pub fn compile(patterns: &[PatternDef]) -> Vec<CompiledPattern> {
+    patterns
+        .iter()
+        .filter_map(|def| match Regex::new(&def.regex) {
+            Ok(regex) => Some(CompiledPattern {
+                name: def.name.clone(),
+                regex,
+                action: def.action,
+            }),
+            Err(err) => {
+                warn!("Skipping invalid pattern {}: {}", def.name, err);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Creates a target for every match of `patterns` in `lines`.
+///
+/// Targets are labelled with the matched text, carry the pattern's action
+/// and are numbered from [`PATTERN_ID_BASE`] + 1 in reading order. A match
+/// overlapping one of an earlier pattern is dropped.
+pub fn find_targets(lines: &[ScreenLine], patterns: &[CompiledPattern]) -> Vec<NavTarget> {
+    let mut found: Vec<(u16, u16, NavTarget)> = Vec::new();
+    for line in lines {
+        let mut claimed: Vec<(usize, usize)> = Vec::new();
+        for pattern in patterns {
+            for m in pattern.regex.find_iter(&line.text) {
+                let text = m.as_str();
+                let overlaps = claimed.iter().any(|&(s, e)| m.start() < e && s < m.end());
+                if text.is_empty() || overlaps {
+                    continue;
+                }
+                claimed.push((m.start(), m.end()));
+
+                let (x, width) = line.columns(m.start(), m.end());
+                let target = NavTarget::new(0, Rect::new(x, line.y, width, 1))
+                    .with_label(text)
+                    .with_action(pattern.action.target_action())
+                    .with_metadata(PATTERN_METADATA_KEY, pattern.name.clone());
+                found.push((line.y, x, target));
+            }
+        }
+    }
+
+    found.sort_by_key(|(y, x, _)| (*y, *x));
+    found
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (_, _, target))| NavTarget {
+            id: PATTERN_ID_BASE + idx as u64 + 1,
+            ..target
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::config::builtin_patterns;
+    use super::*;
+    use crate::core::targets::TargetAction;
+    use ratatui::text::Line;
+    use ratatui::widgets::{Paragraph, Widget};
+
+    fn render(lines: &[&str]) -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 60, lines.len() as u16));
+        let text: Vec<Line> = lines.iter().map(|l| Line::from(*l)).collect();
+        Paragraph::new(text).render(buffer.area, &mut buffer);
+        buffer
+    }
+
+    fn matches(lines: &[&str]) -> Vec<(String, String, Rect)> {
+        let patterns = compile(&builtin_patterns());
+        find_targets(&screen_lines(&render(lines)), &patterns)
+            .into_iter()
+            .map(|t| {
+                let pattern = t.metadata[PATTERN_METADATA_KEY].clone();
+                (pattern, t.label.unwrap(), t.rect)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_patterns() {
+        let found = matches(&[
+            "see https://example.com/a?b=1. or #42",
+            "  commit 3f2a9c1 at 10.0.0.12:8080",
+            "edit ./src/main.rs now",
+        ]);
+
+        let summary: Vec<(&str, &str)> = found
+            .iter()
+            .map(|(p, text, _)| (p.as_str(), text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("url", "https://example.com/a?b=1"),
+                ("issue", "#42"),
+                ("git_hash", "3f2a9c1"),
+                ("ip", "10.0.0.12:8080"),
+                ("path", "./src/main.rs"),
+            ]
+        );
+        assert_eq!(found[0].2, Rect::new(4, 0, 25, 1));
+        assert_eq!(found[4].2, Rect::new(5, 2, 13, 1));
+    }
+
+    #[test]
+    fn test_targets_carry_actions_in_reading_order() {
+        let patterns = compile(&[
+            PatternDef::new("ticket", "[A-Z]+-[0-9]+").with_action(PatternAction::Open),
+            PatternDef::new("broken", "(unclosed"),
+        ]);
+        assert_eq!(patterns.len(), 1);
+
+        let targets = find_targets(&screen_lines(&render(&["LOC-2 and", "LOC-1"])), &patterns);
+        let ids: Vec<(u64, &str)> = targets
+            .iter()
+            .map(|t| (t.id - PATTERN_ID_BASE, t.label.as_deref().unwrap()))
+            .collect();
+        assert_eq!(ids, vec![(1, "LOC-2"), (2, "LOC-1")]);
+        assert_eq!(targets[0].action, TargetAction::Custom("open".into()));
+    }
+}
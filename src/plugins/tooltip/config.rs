@@ -24,12 +24,20 @@
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TooltipConfig {
-    /// Key to activate tooltip when target is focused (None = hover only).
+    /// Key to activate tooltip when target is focused (None = use the keymap).
     ///
-    /// If set, tooltips can be activated by pressing this key when a target
-    /// is focused. If None, tooltips only activate on hover (mouse support required).
+    /// Tooltips can be activated by pressing the `tooltip.show` keymap
+    /// binding ('h') when a target is focused. If set, this key replaces
+    /// that binding when the plugin is registered.
     pub activation_key: Option<char>,
 
+    /// Whether tooltips can be activated from the keyboard at all.
+    ///
+    /// If false, tooltips only activate on hover (mouse support required)
+    /// and the `tooltip.show` binding is removed when the plugin is
+    /// registered. Default: true.
+    pub keyboard_activation: bool,
+
     /// Delay in milliseconds before showing tooltip on hover.
     ///
     /// This prevents tooltips from appearing immediately when the cursor
@@ -105,7 +113,8 @@ impl TooltipConfig {
     /// Creates a new tooltip configuration with sensible defaults.
     pub fn new() -> Self {
         Self {
-            activation_key: Some('h'),
+            activation_key: None,
+            keyboard_activation: true,
             hover_delay_ms: 300,
             auto_hide_timeout_ms: 0, // Disabled by default
             max_width: 50,
@@ -123,12 +132,14 @@ impl TooltipConfig {
     /// Sets the activation key.
     pub fn with_activation_key(mut self, key: char) -> Self {
         self.activation_key = Some(key);
+        self.keyboard_activation = true;
         self
     }
 
     /// Disables keyboard activation (hover only).
     pub fn hover_only(mut self) -> Self {
         self.activation_key = None;
+        self.keyboard_activation = false;
         self
     }
 
@@ -206,7 +217,8 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = TooltipConfig::default();
-        assert_eq!(config.activation_key, Some('h'));
+        assert_eq!(config.activation_key, None);
+        assert!(config.keyboard_activation);
         assert_eq!(config.hover_delay_ms, 300);
         assert_eq!(config.auto_hide_timeout_ms, 0);
         assert_eq!(config.max_width, 50);
@@ -235,6 +247,7 @@ mod tests {
     fn test_hover_only() {
         let config = TooltipConfig::new().hover_only();
         assert_eq!(config.activation_key, None);
+        assert!(!config.keyboard_activation);
     }
 
     #[test]
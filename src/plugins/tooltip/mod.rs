@@ -55,7 +55,9 @@ pub use registry::TooltipRegistry;
 use crate::core::context::LocustContext;
 use crate::core::input::PluginEventResult;
 use crate::core::plugin::LocustPlugin;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crate::core::targets::TargetKey;
+use crate::plugins::BUILTIN_PRIORITY;
+use crossterm::event::{Event, KeyCode, MouseEventKind};
 use positioning::TooltipPositioner;
 use ratatui::backend::Backend;
 use ratatui::Frame;
@@ -78,7 +80,7 @@ pub enum TooltipMode {
 /// Tooltip plugin for contextual help overlays.
 ///
 /// This plugin manages tooltip display for navigation targets:
-/// 1. User hovers over a target (or presses activation key)
+/// 1. User hovers over a target (or presses the `tooltip.show` key)
 /// 2. After configured delay, tooltip appears
 /// 3. Tooltip auto-hides after timeout (if configured) or on user action
 ///
@@ -114,6 +116,9 @@ pub struct TooltipPlugin {
     /// Target ID being hovered (for pending tooltips).
     pending_target_id: Option<u64>,
 
+    /// Key of the pending or shown target, to follow it when IDs change.
+    target_key: Option<TargetKey>,
+
     /// Positioner for calculating tooltip placement.
     positioner: TooltipPositioner,
 
@@ -153,6 +158,7 @@ impl TooltipPlugin {
             shown_at: None,
             hover_started_at: None,
             pending_target_id: None,
+            target_key: None,
             positioner,
             renderer,
         }
@@ -169,12 +175,13 @@ impl TooltipPlugin {
     }
 
     /// Shows a tooltip for the given target immediately.
-    fn show_tooltip(&mut self, target_id: u64, ctx: &mut LocustContext) {
+    fn show_tooltip(&mut self, target_id: u64, now: Instant, ctx: &mut LocustContext) {
         self.mode = TooltipMode::Visible;
         self.current_target_id = Some(target_id);
-        self.shown_at = Some(Instant::now());
+        self.shown_at = Some(now);
         self.hover_started_at = None;
         self.pending_target_id = None;
+        self.target_key = ctx.targets.key_of(target_id).cloned();
         ctx.overlay.mark_has_overlay();
     }
 
@@ -185,61 +192,122 @@ impl TooltipPlugin {
         self.shown_at = None;
         self.hover_started_at = None;
         self.pending_target_id = None;
+        self.target_key = None;
     }
 
     /// Starts the hover delay for a target.
-    #[allow(dead_code)]
-    fn start_hover(&mut self, target_id: u64) {
-        if self.config.hover_delay_ms == 0 {
-            // No delay, show immediately (but we still need context, so mark pending)
-            self.mode = TooltipMode::Pending;
-            self.pending_target_id = Some(target_id);
-            self.hover_started_at = Some(Instant::now());
-        } else {
-            self.mode = TooltipMode::Pending;
-            self.pending_target_id = Some(target_id);
-            self.hover_started_at = Some(Instant::now());
-        }
+    fn start_hover(&mut self, target_id: u64, now: Instant) {
+        self.mode = TooltipMode::Pending;
+        self.pending_target_id = Some(target_id);
+        self.hover_started_at = Some(now);
     }
 
-    /// Checks if auto-hide timeout has expired.
-    fn check_auto_hide(&mut self) -> bool {
-        if self.config.auto_hide_timeout_ms == 0 {
+    /// Follows the pending or shown target to its current ID after targets
+    /// were re-registered, hiding the tooltip if the target is gone.
+    ///
+    /// Returns `true` if a visible tooltip was hidden.
+    fn follow_target(&mut self, ctx: &LocustContext) -> bool {
+        // Targets are not registered yet (e.g. between begin_frame and draw)
+        if ctx.targets.is_empty() {
             return false;
         }
+        let Some(key) = &self.target_key else {
+            return false;
+        };
+
+        let was_visible = self.mode == TooltipMode::Visible;
+        match ctx.targets.by_key(key).map(|t| t.id) {
+            Some(id) if was_visible => self.current_target_id = Some(id),
+            Some(id) => self.pending_target_id = Some(id),
+            None => {
+                self.hide_tooltip();
+                return was_visible;
+            }
+        }
+        false
+    }
 
-        if let Some(shown_at) = self.shown_at {
-            let elapsed = shown_at.elapsed();
-            let timeout = Duration::from_millis(self.config.auto_hide_timeout_ms);
+    /// Follows the hovered target after the pointer moved at `now`.
+    ///
+    /// Hovering a target with a registered tooltip starts the hover delay;
+    /// leaving it hides the tooltip. Returns `true` if a redraw is needed.
+    fn handle_hover(&mut self, now: Instant, ctx: &mut LocustContext) -> bool {
+        let hovered = ctx
+            .mouse
+            .hovered_target
+            .filter(|id| ctx.tooltips.contains(*id));
+
+        let tracked = match self.mode {
+            TooltipMode::Visible => self.current_target_id,
+            TooltipMode::Pending => self.pending_target_id,
+            TooltipMode::Hidden => None,
+        };
+        if hovered == tracked {
+            return false;
+        }
 
-            if elapsed >= timeout {
+        let was_visible = self.mode == TooltipMode::Visible;
+        match hovered {
+            Some(target_id) => {
                 self.hide_tooltip();
-                return true;
+                self.start_hover(target_id, now);
+                self.target_key = ctx.targets.key_of(target_id).cloned();
+                self.check_hover_delay(now, ctx) || was_visible
+            }
+            None => {
+                self.hide_tooltip();
+                was_visible
             }
         }
+    }
 
-        false
+    /// When the visible tooltip should auto-hide, if auto-hide is enabled.
+    fn auto_hide_deadline(&self) -> Option<Instant> {
+        if self.config.auto_hide_timeout_ms == 0 || self.mode != TooltipMode::Visible {
+            return None;
+        }
+
+        self.shown_at
+            .map(|shown_at| shown_at + Duration::from_millis(self.config.auto_hide_timeout_ms))
     }
 
-    /// Checks if hover delay has elapsed and shows tooltip if ready.
-    fn check_hover_delay(&mut self, ctx: &mut LocustContext) -> bool {
+    /// When the pending tooltip should appear.
+    fn hover_deadline(&self) -> Option<Instant> {
         if self.mode != TooltipMode::Pending {
-            return false;
+            return None;
         }
 
-        if let (Some(started_at), Some(target_id)) = (self.hover_started_at, self.pending_target_id)
-        {
-            let elapsed = started_at.elapsed();
-            let delay = Duration::from_millis(self.config.hover_delay_ms);
-
-            if elapsed >= delay {
-                // Check if target still has a tooltip registered
-                if ctx.tooltips.contains(target_id) {
-                    self.show_tooltip(target_id, ctx);
-                    return true;
-                } else {
-                    self.hide_tooltip();
-                }
+        self.hover_started_at
+            .map(|started_at| started_at + Duration::from_millis(self.config.hover_delay_ms))
+    }
+
+    /// Checks if auto-hide timeout has expired.
+    fn check_auto_hide(&mut self, now: Instant) -> bool {
+        match self.auto_hide_deadline() {
+            Some(deadline) if now >= deadline => {
+                self.hide_tooltip();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks if hover delay has elapsed and shows tooltip if ready.
+    fn check_hover_delay(&mut self, now: Instant, ctx: &mut LocustContext) -> bool {
+        let Some(deadline) = self.hover_deadline() else {
+            return false;
+        };
+        let Some(target_id) = self.pending_target_id else {
+            return false;
+        };
+
+        if now >= deadline {
+            // Check if target still has a tooltip registered
+            if ctx.tooltips.contains(target_id) {
+                self.show_tooltip(target_id, now, ctx);
+                return true;
+            } else {
+                self.hide_tooltip();
             }
         }
 
@@ -256,51 +324,68 @@ where
     }
 
     fn priority(&self) -> i32 {
-        45 // Between omnibar (40) and nav (50)
+        BUILTIN_PRIORITY
     }
 
-    fn init(&mut self, _ctx: &mut LocustContext) {
-        // Plugin is initialized
+    fn before(&self) -> &'static [&'static str] {
+        &["locust.nav"]
     }
 
-    fn on_event(&mut self, event: &Event, ctx: &mut LocustContext) -> PluginEventResult {
-        // Check for auto-hide timeout
-        if self.check_auto_hide() {
-            return PluginEventResult::ConsumedRequestRedraw;
+    fn init(&mut self, ctx: &mut LocustContext) {
+        if self.config.keyboard_activation {
+            ctx.bind_configured_key("tooltip.show", self.config.activation_key);
+        } else {
+            ctx.unbind_key("tooltip.show");
         }
+    }
 
-        // Check for hover delay expiration
-        if self.check_hover_delay(ctx) {
-            return PluginEventResult::ConsumedRequestRedraw;
+    fn on_event(&mut self, event: &Event, ctx: &mut LocustContext) -> PluginEventResult {
+        self.follow_target(ctx);
+
+        if let Event::Mouse(mouse) = event {
+            if matches!(mouse.kind, MouseEventKind::Moved | MouseEventKind::Drag(_))
+                && ctx.mouse_enabled()
+            {
+                // Locust requests the redraw when the hovered target changes
+                self.handle_hover(ctx.now(), ctx);
+            }
+            // Pointer movement is never consumed so the app still sees it
+            return PluginEventResult::NotHandled;
         }
 
-        if let Event::Key(KeyEvent {
-            code, modifiers, ..
-        }) = event
-        {
-            match (code, modifiers) {
-                // Activation key pressed (if configured)
-                (KeyCode::Char(c), m)
-                    if self.config.activation_key == Some(*c) && *m == KeyModifiers::NONE =>
-                {
-                    // TODO: Determine which target has focus
-                    // For now, just toggle if already visible
+        if let Event::Key(key) = event {
+            // Keys are text (e.g. hint characters) while a mode captures input
+            if ctx.is_capturing_input() {
+                return PluginEventResult::NotHandled;
+            }
+
+            let action = ctx.plugin_action("tooltip", key);
+
+            match (action.as_deref(), &key.code) {
+                // Show key toggles the tooltip
+                (Some("tooltip.show"), _) => {
                     if self.mode == TooltipMode::Visible {
                         self.hide_tooltip();
-                    } else {
-                        // In real implementation, would get focused target from context
-                        // For demonstration, we'll just note this in comments
-                        // self.show_tooltip(focused_target_id, ctx);
+                        return PluginEventResult::ConsumedRequestRedraw;
+                    }
+
+                    // The focused target wins over the hovered one
+                    let target_id = [ctx.focus.focused(), ctx.mouse.hovered_target]
+                        .into_iter()
+                        .flatten()
+                        .find(|id| ctx.tooltips.contains(*id));
+                    if let Some(target_id) = target_id {
+                        self.show_tooltip(target_id, ctx.now(), ctx);
+                        return PluginEventResult::ConsumedRequestRedraw;
                     }
-                    return PluginEventResult::ConsumedRequestRedraw;
                 }
 
-                // Escape or any movement hides tooltip
-                (KeyCode::Esc, _)
-                | (KeyCode::Up, _)
-                | (KeyCode::Down, _)
-                | (KeyCode::Left, _)
-                | (KeyCode::Right, _)
+                // Hide key or any movement hides tooltip
+                (Some("tooltip.hide"), _)
+                | (_, KeyCode::Up)
+                | (_, KeyCode::Down)
+                | (_, KeyCode::Left)
+                | (_, KeyCode::Right)
                     if self.mode == TooltipMode::Visible =>
                 {
                     self.hide_tooltip();
@@ -314,6 +399,17 @@ where
         PluginEventResult::NotHandled
     }
 
+    fn on_tick(&mut self, now: Instant, ctx: &mut LocustContext) -> bool {
+        let lost = self.follow_target(ctx);
+        let hidden = self.check_auto_hide(now);
+        let shown = self.check_hover_delay(now, ctx);
+        lost || hidden || shown
+    }
+
+    fn next_deadline(&self, _ctx: &LocustContext) -> Option<Instant> {
+        self.auto_hide_deadline().or_else(|| self.hover_deadline())
+    }
+
     fn render_overlay(&self, frame: &mut Frame, ctx: &LocustContext) {
         if self.mode != TooltipMode::Visible {
             return;
@@ -323,12 +419,16 @@ where
             return;
         };
 
-        // Get target and tooltip content
-        let Some(target) = ctx.targets.by_id(target_id) else {
-            return;
+        // Get target and tooltip content; the target may have a new ID
+        let (target, content) = match &self.target_key {
+            Some(key) => {
+                let target = ctx.targets.by_key(key);
+                let content = target.and_then(|t| ctx.tooltips.get_for_target(t.id, key));
+                (target, content)
+            }
+            None => (ctx.targets.by_id(target_id), ctx.tooltips.get(target_id)),
         };
-
-        let Some(content) = ctx.tooltips.get(target_id) else {
+        let (Some(target), Some(content)) = (target, content) else {
             return;
         };
 
@@ -387,7 +487,7 @@ mod tests {
 
         assert_eq!(plugin.mode(), TooltipMode::Hidden);
 
-        plugin.show_tooltip(1, &mut ctx);
+        plugin.show_tooltip(1, Instant::now(), &mut ctx);
         assert_eq!(plugin.mode(), TooltipMode::Visible);
         assert_eq!(plugin.current_target_id, Some(1));
 
@@ -400,12 +500,46 @@ mod tests {
     fn test_start_hover() {
         let mut plugin = TooltipPlugin::new();
 
-        plugin.start_hover(5);
+        plugin.start_hover(5, Instant::now());
         assert_eq!(plugin.mode(), TooltipMode::Pending);
         assert_eq!(plugin.pending_target_id, Some(5));
         assert!(plugin.hover_started_at.is_some());
     }
 
+    #[test]
+    fn test_hover_shows_tooltip_without_delay() {
+        let mut plugin = TooltipPlugin::with_config(TooltipConfig::new().with_hover_delay_ms(0));
+        let mut ctx = LocustContext::default();
+
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(10, 10, 20, 3)));
+        ctx.tooltips.register(1, TooltipContent::new("Test"));
+
+        ctx.update_hover(12, 11);
+        assert!(plugin.handle_hover(Instant::now(), &mut ctx));
+        assert_eq!(plugin.mode(), TooltipMode::Visible);
+        assert_eq!(plugin.current_target_id, Some(1));
+
+        ctx.update_hover(0, 0);
+        assert!(plugin.handle_hover(Instant::now(), &mut ctx));
+        assert_eq!(plugin.mode(), TooltipMode::Hidden);
+    }
+
+    #[test]
+    fn test_hover_waits_for_delay() {
+        let mut plugin = TooltipPlugin::new();
+        let mut ctx = LocustContext::default();
+
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(10, 10, 20, 3)));
+        ctx.tooltips.register(1, TooltipContent::new("Test"));
+
+        ctx.update_hover(12, 11);
+        plugin.handle_hover(Instant::now(), &mut ctx);
+        assert_eq!(plugin.mode(), TooltipMode::Pending);
+        assert_eq!(plugin.pending_target_id, Some(1));
+    }
+
     #[test]
     fn test_auto_hide_disabled_by_default() {
         let mut plugin = TooltipPlugin::new();
@@ -415,21 +549,136 @@ mod tests {
             .register(NavTarget::new(1, Rect::new(10, 10, 20, 3)));
         ctx.tooltips.register(1, TooltipContent::new("Test"));
 
-        plugin.show_tooltip(1, &mut ctx);
+        plugin.show_tooltip(1, Instant::now(), &mut ctx);
 
         // With default config (auto_hide_timeout_ms = 0), should not auto-hide
-        assert!(!plugin.check_auto_hide());
+        assert!(!plugin.check_auto_hide(Instant::now() + Duration::from_secs(60)));
+        assert_eq!(plugin.mode(), TooltipMode::Visible);
+    }
+
+    #[test]
+    fn test_tick_shows_pending_tooltip_after_delay() {
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = TooltipPlugin::with_config(TooltipConfig::new().with_hover_delay_ms(300));
+        let mut ctx = LocustContext::default();
+        ctx.tooltips.register(1, TooltipContent::new("Test"));
+
+        let start = Instant::now();
+        plugin.start_hover(1, start);
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            Some(start + Duration::from_millis(300))
+        );
+
+        let tick = |plugin: &mut TooltipPlugin, ctx: &mut LocustContext, ms| {
+            LocustPlugin::<TestBackend>::on_tick(plugin, start + Duration::from_millis(ms), ctx)
+        };
+        assert!(!tick(&mut plugin, &mut ctx, 299));
+        assert_eq!(plugin.mode(), TooltipMode::Pending);
+        assert!(tick(&mut plugin, &mut ctx, 300));
         assert_eq!(plugin.mode(), TooltipMode::Visible);
     }
 
+    #[test]
+    fn test_tick_auto_hides_tooltip() {
+        use ratatui::backend::TestBackend;
+
+        let mut plugin =
+            TooltipPlugin::with_config(TooltipConfig::new().with_auto_hide_timeout_ms(1000));
+        let mut ctx = LocustContext::default();
+        let start = Instant::now();
+        plugin.show_tooltip(1, start, &mut ctx);
+
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            Some(start + Duration::from_secs(1))
+        );
+        assert!(LocustPlugin::<TestBackend>::on_tick(
+            &mut plugin,
+            start + Duration::from_secs(1),
+            &mut ctx
+        ));
+        assert_eq!(plugin.mode(), TooltipMode::Hidden);
+        assert_eq!(
+            LocustPlugin::<TestBackend>::next_deadline(&plugin, &ctx),
+            None
+        );
+    }
+
+    #[test]
+    fn test_show_key_toggles_hovered_tooltip() {
+        use crossterm::event::{KeyEvent, KeyModifiers};
+        use ratatui::backend::TestBackend;
+
+        let mut plugin = TooltipPlugin::new();
+        let mut ctx = LocustContext::default();
+        LocustPlugin::<TestBackend>::init(&mut plugin, &mut ctx);
+        let show = Event::Key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE));
+
+        // Nothing to show, so the key is left for the application
+        assert_eq!(
+            LocustPlugin::<TestBackend>::on_event(&mut plugin, &show, &mut ctx),
+            PluginEventResult::NotHandled
+        );
+
+        ctx.tooltips.register(3, TooltipContent::new("Help"));
+        ctx.mouse.hovered_target = Some(3);
+        assert_eq!(
+            LocustPlugin::<TestBackend>::on_event(&mut plugin, &show, &mut ctx),
+            PluginEventResult::ConsumedRequestRedraw
+        );
+        assert_eq!(plugin.current_target_id, Some(3));
+
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &show, &mut ctx);
+        assert_eq!(plugin.mode(), TooltipMode::Hidden);
+    }
+
+    #[test]
+    fn test_show_key_prefers_focused_target() {
+        use crate::core::targets::NavTarget;
+        use crossterm::event::{KeyEvent, KeyModifiers};
+        use ratatui::backend::TestBackend;
+        use ratatui::layout::Rect;
+
+        let mut plugin = TooltipPlugin::new();
+        let mut ctx = LocustContext::default();
+        LocustPlugin::<TestBackend>::init(&mut plugin, &mut ctx);
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+        ctx.tooltips.register(1, TooltipContent::new("Focused"));
+        ctx.tooltips.register(2, TooltipContent::new("Hovered"));
+        ctx.mouse.hovered_target = Some(2);
+        ctx.focus_target(1);
+
+        let show = Event::Key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE));
+        LocustPlugin::<TestBackend>::on_event(&mut plugin, &show, &mut ctx);
+        assert_eq!(plugin.current_target_id, Some(1));
+    }
+
+    #[test]
+    fn test_no_activation_key_unbinds_show() {
+        use ratatui::backend::TestBackend;
+
+        let config = TooltipConfig {
+            keyboard_activation: false,
+            ..TooltipConfig::default()
+        };
+        let mut plugin = TooltipPlugin::with_config(config);
+        let mut ctx = LocustContext::default();
+        LocustPlugin::<TestBackend>::init(&mut plugin, &mut ctx);
+
+        assert!(ctx.keymap.get_binding("tooltip.show").is_none());
+    }
+
     #[test]
     fn test_plugin_priority() {
         use ratatui::backend::TestBackend;
         let plugin = TooltipPlugin::new();
-        // Should be between omnibar (40) and nav (50)
+        // Should see events before nav
         assert_eq!(
-            <TooltipPlugin as LocustPlugin<TestBackend>>::priority(&plugin),
-            45
+            <TooltipPlugin as LocustPlugin<TestBackend>>::before(&plugin),
+            &["locust.nav"]
         );
     }
 }
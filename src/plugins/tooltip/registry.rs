@@ -4,6 +4,8 @@
 //! to their associated tooltip content.
 
 use super::content::TooltipContent;
+use crate::core::targets::{TargetKey, TargetRegistry};
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 /// Registry that maps target IDs to tooltip content.
@@ -11,6 +13,10 @@ use std::collections::HashMap;
 /// Applications register tooltips for specific target IDs, and the
 /// TooltipPlugin retrieves and displays them when targets are hovered.
 ///
+/// Once its target is registered, a tooltip follows the target's key, so
+/// it moves along when the application regenerates IDs; see
+/// [`follow_targets`](Self::follow_targets).
+///
 /// # Examples
 ///
 /// ```rust
@@ -40,6 +46,12 @@ use std::collections::HashMap;
 pub struct TooltipRegistry {
     /// Map from target ID to tooltip content.
     tooltips: HashMap<u64, TooltipContent>,
+
+    /// Key of the target each tooltip was found on.
+    keys: HashMap<u64, TargetKey>,
+
+    /// Tooltips whose target is not registered this frame.
+    detached: HashMap<TargetKey, TooltipContent>,
 }
 
 impl TooltipRegistry {
@@ -52,6 +64,7 @@ impl TooltipRegistry {
     ///
     /// If a tooltip already exists for this target ID, it will be replaced.
     pub fn register(&mut self, target_id: u64, content: TooltipContent) {
+        self.keys.remove(&target_id);
         self.tooltips.insert(target_id, content);
     }
 
@@ -62,26 +75,129 @@ impl TooltipRegistry {
         self.tooltips.get(&target_id)
     }
 
+    /// Retrieves the tooltip for the target with `target_id` and `key`.
+    ///
+    /// Unlike [`get`](Self::get), this finds the tooltip right after the
+    /// application regenerated IDs, before
+    /// [`follow_targets`](Self::follow_targets) moved it.
+    pub fn get_for_target(&self, target_id: u64, key: &TargetKey) -> Option<&TooltipContent> {
+        // A tooltip filed under this ID for another target is stale
+        if self.keys.get(&target_id).is_none_or(|bound| bound == key) {
+            if let Some(content) = self.tooltips.get(&target_id) {
+                return Some(content);
+            }
+        }
+
+        self.keys
+            .iter()
+            .find(|&(_, bound)| bound == key)
+            .and_then(|(id, _)| self.tooltips.get(id))
+            .or_else(|| self.detached.get(key))
+    }
+
     /// Removes the tooltip for a target.
     ///
     /// Returns true if a tooltip was removed, false if none existed.
     pub fn remove(&mut self, target_id: u64) -> bool {
+        self.keys.remove(&target_id);
         self.tooltips.remove(&target_id).is_some()
     }
 
     /// Clears all registered tooltips.
     pub fn clear(&mut self) {
         self.tooltips.clear();
+        self.keys.clear();
+        self.detached.clear();
     }
 
-    /// Returns the number of registered tooltips.
+    /// Returns the number of registered tooltips, including those whose
+    /// target is not registered this frame.
     pub fn len(&self) -> usize {
-        self.tooltips.len()
+        self.tooltips.len() + self.detached.len()
     }
 
     /// Returns true if no tooltips are registered.
     pub fn is_empty(&self) -> bool {
-        self.tooltips.is_empty()
+        self.len() == 0
+    }
+
+    /// Moves tooltips to the current IDs of their targets.
+    ///
+    /// A tooltip remembers the key of the target it was first found on.
+    /// When that key has a new ID the tooltip moves there, unless a tooltip
+    /// was registered for the new ID directly; while the key is gone the
+    /// tooltip is set aside until it comes back. `Locust` calls this before
+    /// handling each event and tick.
+    pub fn follow_targets(&mut self, targets: &TargetRegistry) {
+        let moved: Vec<u64> = self
+            .keys
+            .iter()
+            .filter(|&(&id, key)| targets.key_of(id) != Some(key))
+            .map(|(&id, _)| id)
+            .collect();
+        for id in moved {
+            let key = self.keys.remove(&id).expect("collected from keys");
+            if let Some(content) = self.tooltips.remove(&id) {
+                self.detached.insert(key, content);
+            }
+        }
+
+        for &id in self.tooltips.keys() {
+            if let Some(key) = targets.key_of(id) {
+                self.keys.entry(id).or_insert_with(|| key.clone());
+            }
+        }
+
+        let found: Vec<(TargetKey, u64)> = self
+            .detached
+            .keys()
+            .filter_map(|key| Some((key.clone(), targets.by_key(key)?.id)))
+            .collect();
+        for (key, id) in found {
+            let content = self.detached.remove(&key).expect("collected from detached");
+            // A tooltip registered for the new ID replaces the old one
+            if let Entry::Vacant(entry) = self.tooltips.entry(id) {
+                entry.insert(content);
+                self.keys.insert(id, key);
+            }
+        }
+    }
+
+    /// The tooltips registered for current IDs, to compare against with
+    /// [`changes_since`](Self::changes_since).
+    pub(crate) fn snapshot(&self) -> HashMap<u64, TooltipContent> {
+        self.tooltips.clone()
+    }
+
+    /// Tooltips registered or replaced since `before`.
+    pub(crate) fn changes_since(&self, before: &HashMap<u64, TooltipContent>) -> TooltipChanges {
+        TooltipChanges(
+            self.tooltips
+                .iter()
+                .filter(|&(id, content)| before.get(id) != Some(content))
+                .map(|(&id, content)| (id, before.get(&id).cloned(), content.clone()))
+                .collect(),
+        )
+    }
+
+    /// Puts back the tooltips `changes` replaced, and removes the ones it
+    /// added.
+    ///
+    /// Tooltips changed again since `changes` was recorded are left alone.
+    pub(crate) fn revert(&mut self, changes: TooltipChanges) {
+        for (id, old, new) in changes.0 {
+            if self.tooltips.get(&id) != Some(&new) {
+                continue;
+            }
+            match old {
+                Some(content) => {
+                    self.tooltips.insert(id, content);
+                }
+                None => {
+                    self.remove(id);
+                }
+            }
+        }
     }
 
     /// Returns all registered target IDs.
@@ -95,10 +211,19 @@ impl TooltipRegistry {
     }
 }
 
+/// A tooltip that changed: the target ID, the content before and after.
+type Change = (u64, Option<TooltipContent>, TooltipContent);
+
+/// Tooltips changed in a registry, with the content they replaced.
+#[derive(Debug, Default)]
+pub(crate) struct TooltipChanges(Vec<Change>);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::targets::NavTarget;
     use crate::plugins::tooltip::content::TooltipStyle;
+    use ratatui::layout::Rect;
 
     #[test]
     fn test_registry_creation() {
@@ -171,6 +296,30 @@ mod tests {
         assert_eq!(ids, vec![1, 5, 10]);
     }
 
+    #[test]
+    fn test_tooltips_follow_regenerated_ids() {
+        let mut targets = TargetRegistry::new();
+        targets.register(NavTarget::new(1, Rect::new(0, 0, 5, 1)).with_label("Save"));
+        targets.register(NavTarget::new(2, Rect::new(0, 1, 5, 1)).with_label("Quit"));
+        let mut registry = TooltipRegistry::new();
+        registry.register(1, TooltipContent::new("Saves the file"));
+        registry.follow_targets(&targets);
+
+        // "Quit" takes the ID of "Save", which is gone for a frame
+        targets.clear();
+        targets.register(NavTarget::new(1, Rect::new(0, 1, 5, 1)).with_label("Quit"));
+        registry.follow_targets(&targets);
+        assert!(!registry.contains(1));
+        assert_eq!(registry.len(), 1);
+
+        targets.clear();
+        targets.register(NavTarget::new(7, Rect::new(0, 0, 5, 1)).with_label("Save"));
+        targets.register(NavTarget::new(1, Rect::new(0, 1, 5, 1)).with_label("Quit"));
+        registry.follow_targets(&targets);
+        assert_eq!(registry.get(7).unwrap().body, "Saves the file");
+        assert!(!registry.contains(1));
+    }
+
     #[test]
     fn test_multiple_tooltips_with_styles() {
         let mut registry = TooltipRegistry::new();
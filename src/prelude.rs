@@ -12,22 +12,28 @@ pub use crate::core::{Locust, LocustConfig, LocustContext};
 
 // Plugin system
 pub use crate::core::input::{LocustEventOutcome, PluginEventResult};
+pub use crate::core::modes::{Mode, ModeFrame};
 pub use crate::core::plugin::LocustPlugin;
 
 // Navigation and targets
+pub use crate::core::actions::{ActionQueue, ScrollDirection, TargetActivation};
 pub use crate::core::targets::{
-    NavTarget, TargetAction, TargetBuilder, TargetPriority, TargetRegistry, TargetState,
+    NavTarget, OffscreenTarget, TargetAction, TargetBuilder, TargetPriority, TargetRegistry,
+    TargetState,
 };
+pub use crate::ratatui_ext::DiscoveryConfig;
 
 // Overlay management
-pub use crate::core::overlay::{OverlayLayer, OverlayState};
+pub use crate::core::overlay::{OcclusionMask, OverlayLayer, OverlayState};
 
 // Built-in plugins
 pub use crate::plugins::highlight::{
     HighlightConfig, HighlightPlugin, MessagePosition, Tour, TourStep,
 };
-pub use crate::plugins::nav::{NavConfig, NavMode, NavPlugin};
+pub use crate::plugins::hud::MetricsHudPlugin;
+pub use crate::plugins::nav::{HintScope, NavConfig, NavMode, NavPlugin};
 pub use crate::plugins::omnibar::{BorderType, OmnibarConfig, OmnibarMode, OmnibarPlugin};
+pub use crate::plugins::patterns::PatternPlugin;
 pub use crate::plugins::tooltip::{TooltipConfig, TooltipPlugin, TooltipStyle};
 
 // Re-export commonly used ratatui types
@@ -0,0 +1,582 @@
+//! Heuristic target discovery for apps without adapters.
+//!
+//! An existing app gets hints before any adapter calls are added: after the
+//! app has drawn a frame, [`discover`] looks through the `Buffer` for widget
+//! idioms and synthesizes low-priority targets for them:
+//!
+//! - boxes drawn with box-drawing borders become `"panel"` targets, which
+//!   are the parents of the targets found inside them;
+//! - rows split by ` │ ` dividers become `"tab"` targets;
+//! - `[ Button ]` brackets become `"button"` targets;
+//! - rows marked with `>` (or `▶`, `»`) and reverse-video rows become
+//!   selected `"list_item"` targets.
+//!
+//! [`merge_into`] adds them to a registry, leaving out those that collide
+//! with explicitly registered targets, so adapters can be added one widget
+//! at a time. `Locust::discover_targets` does both when
+//! `ctx.discovery.enabled` is set.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::prelude::*;
+//! use ratatui::backend::TestBackend;
+//! use ratatui::widgets::{Block, Borders, Paragraph};
+//! use ratatui::Terminal;
+//!
+//! let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+//! locust.ctx.discovery.enabled = true;
+//!
+//! let mut terminal = Terminal::new(TestBackend::new(30, 5)).unwrap();
+//! terminal
+//!     .draw(|frame| {
+//!         locust.begin_frame();
+//!         let panel = Paragraph::new("[ Save ]").block(Block::default().borders(Borders::ALL));
+//!         frame.render_widget(panel, frame.area());
+//!         locust.discover_targets(frame.buffer_mut());
+//!         locust.render_overlay(frame);
+//!     })
+//!     .unwrap();
+//!
+//! let roles: Vec<_> = locust.ctx.targets.all().iter().map(|t| t.role().unwrap()).collect();
+//! assert_eq!(roles, ["panel", "button"]);
+//! ```
+
+use crate::core::targets::{
+    NavTarget, TargetAction, TargetBuilder, TargetPriority, TargetRegistry, TargetState,
+};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use std::collections::{HashMap, HashSet};
+
+/// Metadata key holding the name of the heuristic that found a target.
+pub const DISCOVERED_METADATA_KEY: &str = "discovered";
+
+const TOP_LEFT: &[char] = &['┌', '╭', '╔', '┏'];
+const TOP_RIGHT: &[char] = &['┐', '╮', '╗', '┓'];
+const BOTTOM_LEFT: &[char] = &['└', '╰', '╚', '┗'];
+const BOTTOM_RIGHT: &[char] = &['┘', '╯', '╝', '┛'];
+const VERTICAL: &[char] = &['│', '║', '┃', '├', '┤', '╟', '╢', '┣', '┫'];
+const HORIZONTAL: &[char] = &['─', '═', '━', '┬', '┴', '╤', '╧', '┳', '┻'];
+const DIVIDERS: &[char] = &['│', '┃', '|'];
+const SELECTION_MARKERS: &[char] = &['>', '▶', '▸', '»', '➤', '→'];
+
+/// Longest tab title, in cells, that is still taken for a tab.
+const MAX_TAB_WIDTH: usize = 24;
+
+/// Longest button text, in cells, between the brackets.
+const MAX_BUTTON_WIDTH: usize = 30;
+
+/// Which heuristics discovery runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryConfig {
+    /// Whether `Locust::discover_targets` runs at all. Off by default.
+    pub enabled: bool,
+
+    /// Boxes drawn with box-drawing characters.
+    pub borders: bool,
+
+    /// Rows of titles split by ` │ ` dividers.
+    pub tabs: bool,
+
+    /// Text in square brackets, like `[ OK ]`.
+    pub buttons: bool,
+
+    /// Rows starting with a selection marker, like `> item`.
+    pub selection: bool,
+
+    /// Runs of reverse-video cells.
+    pub reverse_video: bool,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            borders: true,
+            tabs: true,
+            buttons: true,
+            selection: true,
+            reverse_video: true,
+        }
+    }
+}
+
+impl DiscoveryConfig {
+    /// Creates the default configuration: discovery off, every heuristic on.
+    ///
+    /// Turn discovery on with [`with_enabled`](Self::with_enabled).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether discovery runs.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Sets whether bordered boxes become panel targets.
+    pub fn with_borders(mut self, borders: bool) -> Self {
+        self.borders = borders;
+        self
+    }
+
+    /// Sets whether tab bars become tab targets.
+    pub fn with_tabs(mut self, tabs: bool) -> Self {
+        self.tabs = tabs;
+        self
+    }
+
+    /// Sets whether bracketed text becomes button targets.
+    pub fn with_buttons(mut self, buttons: bool) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    /// Sets whether marked rows become list item targets.
+    pub fn with_selection(mut self, selection: bool) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets whether reverse-video rows become list item targets.
+    pub fn with_reverse_video(mut self, reverse_video: bool) -> Self {
+        self.reverse_video = reverse_video;
+        self
+    }
+}
+
+/// The cells of a buffer, with the borders of found panels marked.
+struct Screen<'a> {
+    buffer: &'a Buffer,
+    borders: HashSet<(u16, u16)>,
+}
+
+impl Screen<'_> {
+    fn ch(&self, x: u16, y: u16) -> char {
+        self.buffer[(x, y)].symbol().chars().next().unwrap_or(' ')
+    }
+
+    fn text(&self, x: u16, y: u16, width: u16) -> String {
+        (x..x + width)
+            .map(|x| self.buffer[(x, y)].symbol())
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    /// Spans `(start, end)` of row `y` between panel borders.
+    fn runs(&self, y: u16) -> Vec<(u16, u16)> {
+        let area = self.buffer.area;
+        let mut runs = Vec::new();
+        let mut start = area.x;
+        for x in area.x..=area.right() {
+            if x == area.right() || self.borders.contains(&(x, y)) {
+                if x > start {
+                    runs.push((start, x));
+                }
+                start = x + 1;
+            }
+        }
+        runs
+    }
+
+    /// The non-blank part of `start..end` on row `y`.
+    fn trimmed(&self, y: u16, start: u16, end: u16) -> Option<(u16, u16)> {
+        let first = (start..end).find(|&x| self.ch(x, y) != ' ')?;
+        let last = (start..end).rev().find(|&x| self.ch(x, y) != ' ')?;
+        Some((first, last + 1))
+    }
+
+    fn is_divider(&self, x: u16, y: u16) -> bool {
+        DIVIDERS.contains(&self.ch(x, y)) && !self.borders.contains(&(x, y))
+    }
+}
+
+/// Finds targets in a rendered buffer with the heuristics enabled in
+/// `config`.
+///
+/// `config.enabled` is not checked. Targets are numbered from 1, panels
+/// first, and nest inside the innermost panel around them.
+pub fn discover(buffer: &Buffer, config: &DiscoveryConfig) -> Vec<NavTarget> {
+    let mut screen = Screen {
+        buffer,
+        borders: HashSet::new(),
+    };
+    // Roles come from the builder; IDs are set once overlapping finds are dropped
+    let mut builder = TargetBuilder::new();
+
+    let mut panels = Vec::new();
+    if config.borders {
+        for rect in find_panels(&screen) {
+            for x in rect.left()..rect.right() {
+                screen.borders.insert((x, rect.top()));
+                screen.borders.insert((x, rect.bottom() - 1));
+            }
+            for y in rect.top()..rect.bottom() {
+                screen.borders.insert((rect.left(), y));
+                screen.borders.insert((rect.right() - 1, y));
+            }
+            let title = screen
+                .text(rect.x + 1, rect.y, rect.width - 2)
+                .trim_matches(|c: char| HORIZONTAL.contains(&c) || c == ' ')
+                .to_string();
+            let mut panel = NavTarget::new(0, rect)
+                .with_action(TargetAction::Select)
+                .with_role("panel");
+            if !title.is_empty() {
+                panel = panel.with_label(title);
+            }
+            panels.push(tagged(panel, "border"));
+        }
+    }
+
+    let mut leaves: Vec<NavTarget> = Vec::new();
+    let mut claim = |target: NavTarget| {
+        if !leaves.iter().any(|t| t.rect.intersects(target.rect)) {
+            leaves.push(target);
+        }
+    };
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        for (start, end) in screen.runs(y) {
+            if config.tabs {
+                for (rect, title) in find_tabs(&screen, y, start, end) {
+                    claim(tagged(builder.tab(rect, title), "tab"));
+                }
+            }
+            if config.buttons {
+                for (rect, text) in find_buttons(&screen, y, start, end) {
+                    claim(tagged(builder.button(rect, text), "button"));
+                }
+            }
+            if config.selection {
+                if let Some((rect, text)) = find_marked_row(&screen, y, start, end) {
+                    let item = builder
+                        .list_item(rect, text)
+                        .with_state(TargetState::Selected);
+                    claim(tagged(item, "selection"));
+                }
+            }
+            if config.reverse_video {
+                for (rect, text) in find_reversed(&screen, y, start, end) {
+                    let item = builder
+                        .list_item(rect, text)
+                        .with_state(TargetState::Selected);
+                    claim(tagged(item, "reverse_video"));
+                }
+            }
+        }
+    }
+
+    let mut targets: Vec<NavTarget> = panels.into_iter().chain(leaves).collect();
+    for (idx, target) in targets.iter_mut().enumerate() {
+        target.id = idx as u64 + 1;
+    }
+    for idx in 0..targets.len() {
+        let rect = targets[idx].rect;
+        targets[idx].parent = targets
+            .iter()
+            .filter(|p| p.role() == Some("panel") && p.rect != rect && contains(p.rect, rect))
+            .min_by_key(|p| p.rect.area())
+            .map(|p| p.id);
+    }
+    targets
+}
+
+/// Registers `discovered` targets in `registry` after its explicit ones.
+///
+/// Discovered targets get IDs above the highest registered one. A target
+/// colliding with a registered one is left out; a panel only collides with
+/// targets it does not fully enclose. Parents that were left out are
+/// replaced by their nearest registered ancestor. Returns the number of
+/// targets registered.
+pub fn merge_into(registry: &mut TargetRegistry, discovered: Vec<NavTarget>) -> usize {
+    let collides = |target: &NavTarget| {
+        registry.all().iter().any(|explicit| {
+            let enclosed = target.role() == Some("panel")
+                && target.rect != explicit.rect
+                && contains(target.rect, explicit.rect);
+            target.rect.intersects(explicit.rect) && !enclosed
+        })
+    };
+    let kept: HashSet<u64> = discovered
+        .iter()
+        .filter(|t| !collides(t))
+        .map(|t| t.id)
+        .collect();
+    let parents: HashMap<u64, Option<u64>> = discovered.iter().map(|t| (t.id, t.parent)).collect();
+
+    let base = registry.all().iter().map(|t| t.id).max().unwrap_or(0);
+    let mut registered = 0;
+    for mut target in discovered {
+        if !kept.contains(&target.id) {
+            continue;
+        }
+        let mut parent = target.parent;
+        while let Some(id) = parent.filter(|id| !kept.contains(id)) {
+            parent = parents.get(&id).copied().flatten();
+        }
+        target.id += base;
+        target.parent = parent.map(|id| id + base);
+        registry.register(target);
+        registered += 1;
+    }
+    registered
+}
+
+fn tagged(target: NavTarget, heuristic: &str) -> NavTarget {
+    target
+        .with_priority(TargetPriority::Low)
+        .with_metadata(DISCOVERED_METADATA_KEY, heuristic)
+}
+
+fn contains(outer: Rect, inner: Rect) -> bool {
+    outer.union(inner) == outer
+}
+
+/// Boxes whose four corners and sides are drawn with box-drawing characters.
+fn find_panels(screen: &Screen) -> Vec<Rect> {
+    let area = screen.buffer.area;
+    let mut panels = Vec::new();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if !TOP_LEFT.contains(&screen.ch(x, y)) {
+                continue;
+            }
+            let bottom = (x + 1..area.right())
+                .filter(|&right| TOP_RIGHT.contains(&screen.ch(right, y)))
+                .find_map(|right| panel_bottom(screen, x, right, y).map(|bottom| (right, bottom)));
+            if let Some((right, bottom)) = bottom {
+                panels.push(Rect::new(x, y, right - x + 1, bottom - y + 1));
+            }
+        }
+    }
+    panels
+}
+
+/// Row of the bottom corners of a box with top corners at `left` and `right`.
+fn panel_bottom(screen: &Screen, left: u16, right: u16, top: u16) -> Option<u16> {
+    for y in top + 1..screen.buffer.area.bottom() {
+        let (l, r) = (screen.ch(left, y), screen.ch(right, y));
+        if BOTTOM_LEFT.contains(&l) && BOTTOM_RIGHT.contains(&r) {
+            return Some(y);
+        }
+        if !VERTICAL.contains(&l) || !VERTICAL.contains(&r) {
+            return None;
+        }
+    }
+    None
+}
+
+/// Titles of a row split by dividers with a space on each side.
+///
+/// Dividers lined up with one on the row above or below are taken for
+/// table columns instead.
+fn find_tabs(screen: &Screen, y: u16, start: u16, end: u16) -> Vec<(Rect, String)> {
+    let area = screen.buffer.area;
+    let dividers: Vec<u16> = (start + 1..end.saturating_sub(1))
+        .filter(|&x| {
+            screen.is_divider(x, y) && screen.ch(x - 1, y) == ' ' && screen.ch(x + 1, y) == ' '
+        })
+        .collect();
+    let in_column = |x: u16| {
+        (y > area.top() && screen.is_divider(x, y - 1))
+            || (y + 1 < area.bottom() && screen.is_divider(x, y + 1))
+    };
+    if dividers.is_empty() || dividers.iter().any(|&x| in_column(x)) {
+        return Vec::new();
+    }
+
+    let bounds = std::iter::once(start)
+        .chain(dividers.iter().map(|&x| x + 1))
+        .zip(dividers.iter().copied().chain(std::iter::once(end)));
+    let mut tabs = Vec::new();
+    for (from, to) in bounds {
+        let Some((first, last)) = screen.trimmed(y, from, to) else {
+            return Vec::new();
+        };
+        let title = screen.text(first, y, last - first);
+        if title.chars().count() > MAX_TAB_WIDTH || title.contains("  ") {
+            return Vec::new();
+        }
+        tabs.push((Rect::new(first, y, last - first, 1), title));
+    }
+    tabs
+}
+
+/// Bracketed text, like `[ OK ]` or `[Cancel]`.
+fn find_buttons(screen: &Screen, y: u16, start: u16, end: u16) -> Vec<(Rect, String)> {
+    let mut buttons = Vec::new();
+    let mut x = start;
+    while x < end {
+        if screen.ch(x, y) != '[' {
+            x += 1;
+            continue;
+        }
+        let close = (x + 1..end)
+            .take(MAX_BUTTON_WIDTH + 1)
+            .find(|&c| matches!(screen.ch(c, y), '[' | ']'))
+            .filter(|&c| screen.ch(c, y) == ']');
+        match close {
+            Some(close) => {
+                let text = screen.text(x + 1, y, close - x - 1);
+                if !text.is_empty() {
+                    buttons.push((Rect::new(x, y, close - x + 1, 1), text));
+                }
+                x = close + 1;
+            }
+            None => x += 1,
+        }
+    }
+    buttons
+}
+
+/// A row starting with a selection marker, such as ratatui's `>> `.
+fn find_marked_row(screen: &Screen, y: u16, start: u16, end: u16) -> Option<(Rect, String)> {
+    let (first, last) = screen.trimmed(y, start, end)?;
+    let text_start = (first..last).find(|&x| !SELECTION_MARKERS.contains(&screen.ch(x, y)))?;
+    if text_start == first || screen.ch(text_start, y) != ' ' {
+        return None;
+    }
+    let text = screen.text(text_start, y, last - text_start);
+    Some((Rect::new(first, y, last - first, 1), text))
+}
+
+/// Runs of reverse-video cells holding some text.
+fn find_reversed(screen: &Screen, y: u16, start: u16, end: u16) -> Vec<(Rect, String)> {
+    let reversed = |x: u16| screen.buffer[(x, y)].modifier.contains(Modifier::REVERSED);
+    let mut rows = Vec::new();
+    let mut x = start;
+    while x < end {
+        if !reversed(x) {
+            x += 1;
+            continue;
+        }
+        let run_end = (x..end).find(|&x| !reversed(x)).unwrap_or(end);
+        let text = screen.text(x, y, run_end - x);
+        if !text.is_empty() {
+            rows.push((Rect::new(x, y, run_end - x, 1), text));
+        }
+        x = run_end;
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Style, Stylize};
+    use ratatui::widgets::{Block, Borders, List, ListState, StatefulWidget, Tabs, Widget};
+
+    fn summary(targets: &[NavTarget]) -> Vec<(u64, &str, &str, Option<u64>)> {
+        targets
+            .iter()
+            .map(|t| {
+                (
+                    t.id,
+                    t.role().unwrap(),
+                    t.label.as_deref().unwrap_or(""),
+                    t.parent,
+                )
+            })
+            .collect()
+    }
+
+    fn app() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 40, 10));
+        Tabs::new(["Inbox", "Sent", "Drafts"])
+            .select(0)
+            .block(Block::default().borders(Borders::ALL))
+            .render(Rect::new(0, 0, 40, 3), &mut buffer);
+
+        let list = List::new(["first", "second"])
+            .highlight_symbol(">> ")
+            .block(Block::default().borders(Borders::ALL).title("Mail"));
+        let mut state = ListState::default().with_selected(Some(1));
+        StatefulWidget::render(list, Rect::new(0, 3, 20, 4), &mut buffer, &mut state);
+
+        buffer.set_string(22, 4, "[ Send ] [Cancel]", Style::default());
+        buffer.set_string(22, 6, "  row ", Style::default().reversed());
+        buffer
+    }
+
+    #[test]
+    fn test_discovers_widget_idioms() {
+        let targets = discover(&app(), &DiscoveryConfig::new());
+
+        assert_eq!(
+            summary(&targets),
+            vec![
+                (1, "panel", "", None),
+                (2, "panel", "Mail", None),
+                (3, "tab", "Inbox", Some(1)),
+                (4, "tab", "Sent", Some(1)),
+                (5, "tab", "Drafts", Some(1)),
+                (6, "button", "Send", None),
+                (7, "button", "Cancel", None),
+                (8, "list_item", "second", Some(2)),
+                (9, "list_item", "row", None),
+            ]
+        );
+        assert!(targets.iter().all(|t| t.priority == TargetPriority::Low));
+        assert_eq!(targets[3].rect, Rect::new(10, 1, 4, 1));
+        assert_eq!(targets[5].rect, Rect::new(22, 4, 8, 1));
+        assert_eq!(targets[7].rect, Rect::new(1, 5, 9, 1));
+        assert_eq!(targets[7].state, TargetState::Selected);
+        assert_eq!(
+            targets[8].metadata[DISCOVERED_METADATA_KEY],
+            "reverse_video"
+        );
+    }
+
+    #[test]
+    fn test_new_is_disabled_like_default() {
+        assert_eq!(DiscoveryConfig::new(), DiscoveryConfig::default());
+        assert!(DiscoveryConfig::new().with_enabled(true).enabled);
+    }
+
+    #[test]
+    fn test_heuristics_can_be_turned_off() {
+        let config = DiscoveryConfig::new()
+            .with_borders(false)
+            .with_tabs(false)
+            .with_selection(false)
+            .with_reverse_video(false);
+        let targets = discover(&app(), &config);
+        let roles: Vec<_> = targets.iter().map(|t| t.role().unwrap()).collect();
+        assert_eq!(roles, ["button", "button"]);
+    }
+
+    #[test]
+    fn test_table_columns_are_not_tabs() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 2));
+        buffer.set_string(0, 0, "a │ b", Style::default());
+        buffer.set_string(0, 1, "c │ d", Style::default());
+        let targets = discover(&buffer, &DiscoveryConfig::new());
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn test_merge_skips_explicit_targets() {
+        let mut registry = TargetRegistry::new();
+        registry.register(NavTarget::new(10, Rect::new(22, 4, 8, 1)).with_label("Send"));
+        registry.register(NavTarget::new(11, Rect::new(0, 0, 40, 3)).with_label("Tabs"));
+
+        let registered = merge_into(&mut registry, discover(&app(), &DiscoveryConfig::new()));
+
+        // The tab bar and the Send button were registered explicitly
+        assert_eq!(registered, 4);
+        assert_eq!(
+            summary(&registry.all()[2..]),
+            vec![
+                (13, "panel", "Mail", None),
+                (18, "button", "Cancel", None),
+                (19, "list_item", "second", Some(13)),
+                (20, "list_item", "row", None),
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod adapters;
+pub mod discovery;
 pub mod log_tailer;
 
 pub use adapters::{register_simple_row_targets, Navigable};
+pub use discovery::{discover, merge_into, DiscoveryConfig};
 pub use log_tailer::LogTailer;
@@ -0,0 +1,430 @@
+//! Label-based automation for end-to-end tests.
+//!
+//! Every interactive element of a Locust application is a [`NavTarget`],
+//! so tests can address the UI the way a user reads it: by label, group
+//! or role. A [`LocustDriver`] wraps a [`LocustHarness`], finds targets
+//! with [`Locator`]s in the last drawn frame, activates them through the
+//! action queue and waits for targets to appear in later frames.
+//!
+//! Activations are passed to the driver's action handler, which plays the
+//! part of the application's event loop, and a new frame is drawn after
+//! every step.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::prelude::*;
+//! use locust::testing::{Locator, LocustDriver, LocustHarness};
+//! use ratatui::layout::Rect;
+//! use std::cell::Cell;
+//! use std::rc::Rc;
+//!
+//! let saved = Rc::new(Cell::new(false));
+//! let shown = saved.clone();
+//! let harness = LocustHarness::new(20, 4, move |_frame, ctx| {
+//!     ctx.targets.register(NavTarget::new(1, Rect::new(0, 0, 4, 1)).with_label("Save"));
+//!     if shown.get() {
+//!         ctx.targets.register(NavTarget::new(2, Rect::new(0, 1, 5, 1)).with_label("Saved"));
+//!     }
+//! });
+//!
+//! let mut driver = LocustDriver::new(harness).with_action_handler(move |activation| {
+//!     if activation.label.as_deref() == Some("Save") {
+//!         saved.set(true);
+//!     }
+//! });
+//!
+//! driver.activate(&Locator::label("Save")).unwrap();
+//! driver.wait_for(&Locator::label("Saved")).unwrap();
+//! ```
+
+use crate::core::actions::TargetActivation;
+use crate::core::targets::{NavTarget, TargetState};
+use crate::testing::harness::LocustHarness;
+use std::fmt;
+
+/// Frames drawn by [`LocustDriver::wait_for`] before giving up.
+pub const DEFAULT_MAX_FRAMES: u32 = 10;
+
+/// Errors from finding or driving targets.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DriverError {
+    #[error("No target matches {0}")]
+    NotFound(Locator),
+    #[error("{count} targets match {locator}; narrow it down or use nth()")]
+    Ambiguous { locator: Locator, count: usize },
+    #[error("No target matches {locator} after {frames} frames")]
+    Timeout { locator: Locator, frames: u32 },
+    #[error("A target matching {locator} is still present after {frames} frames")]
+    StillPresent { locator: Locator, frames: u32 },
+    #[error("Target matching {0} is disabled")]
+    Disabled(Locator),
+    #[error("Target matching {locator} is {actual:?}, expected {expected:?}")]
+    UnexpectedState {
+        locator: Locator,
+        expected: TargetState,
+        actual: TargetState,
+    },
+}
+
+/// Describes which targets to find.
+///
+/// All conditions must hold. A locator that matches several targets is an
+/// error unless one is picked with [`nth`](Self::nth).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Locator {
+    label: Option<String>,
+    group: Option<String>,
+    role: Option<String>,
+    metadata: Vec<(String, String)>,
+    index: Option<usize>,
+}
+
+impl Locator {
+    /// Matches every target.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Matches targets with exactly this label.
+    pub fn label(label: impl Into<String>) -> Self {
+        Self::any().with_label(label)
+    }
+
+    /// Matches targets in this group.
+    pub fn group(group: impl Into<String>) -> Self {
+        Self::any().in_group(group)
+    }
+
+    /// Matches targets with this role (see [`NavTarget::with_role`]).
+    pub fn role(role: impl Into<String>) -> Self {
+        Self::any().with_role(role)
+    }
+
+    /// Also requires this label.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Also requires this group.
+    pub fn in_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Also requires this role.
+    pub fn with_role(mut self, role: impl Into<String>) -> Self {
+        self.role = Some(role.into());
+        self
+    }
+
+    /// Also requires a metadata entry.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Picks the `index`-th match in reading order (top to bottom, then
+    /// left to right).
+    pub fn nth(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Returns whether `target` satisfies the conditions. The
+    /// [`nth`](Self::nth) index is not considered.
+    pub fn matches(&self, target: &NavTarget) -> bool {
+        let field = |wanted: &Option<String>, actual: Option<&str>| {
+            wanted.as_deref().is_none_or(|w| actual == Some(w))
+        };
+        field(&self.label, target.label.as_deref())
+            && field(&self.group, target.group.as_deref())
+            && field(&self.role, target.role())
+            && self
+                .metadata
+                .iter()
+                .all(|(key, value)| target.metadata.get(key) == Some(value))
+    }
+
+    /// The matching targets in reading order.
+    fn select<'a>(&self, targets: &'a [NavTarget]) -> Vec<&'a NavTarget> {
+        let mut matches: Vec<&NavTarget> = targets.iter().filter(|t| self.matches(t)).collect();
+        matches.sort_by_key(|t| (t.rect.y, t.rect.x));
+        match self.index {
+            Some(index) => matches.into_iter().nth(index).into_iter().collect(),
+            None => matches,
+        }
+    }
+}
+
+/// Formats the locator for error messages, e.g. `label="Submit" role=button`.
+impl fmt::Display for Locator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(label) = &self.label {
+            parts.push(format!("label={:?}", label));
+        }
+        if let Some(group) = &self.group {
+            parts.push(format!("group={:?}", group));
+        }
+        if let Some(role) = &self.role {
+            parts.push(format!("role={}", role));
+        }
+        for (key, value) in &self.metadata {
+            parts.push(format!("{}={:?}", key, value));
+        }
+        if let Some(index) = self.index {
+            parts.push(format!("nth={}", index));
+        }
+        if parts.is_empty() {
+            write!(f, "any target")
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+type ActionHandler = Box<dyn FnMut(&TargetActivation)>;
+
+/// Drives a [`LocustHarness`] by target labels instead of coordinates.
+pub struct LocustDriver {
+    harness: LocustHarness,
+    handler: Option<ActionHandler>,
+    activations: Vec<TargetActivation>,
+    max_frames: u32,
+}
+
+impl LocustDriver {
+    /// Wraps a harness. Its plugins and app closure are used as they are.
+    pub fn new(harness: LocustHarness) -> Self {
+        Self {
+            harness,
+            handler: None,
+            activations: Vec::new(),
+            max_frames: DEFAULT_MAX_FRAMES,
+        }
+    }
+
+    /// Sets the handler for target activations, standing in for the code
+    /// that drains `Locust::drain_actions` in the application.
+    pub fn with_action_handler(mut self, handler: impl FnMut(&TargetActivation) + 'static) -> Self {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets how many frames the `wait_*` methods draw before failing.
+    pub fn with_max_frames(mut self, frames: u32) -> Self {
+        self.max_frames = frames;
+        self
+    }
+
+    /// The wrapped harness.
+    pub fn harness(&self) -> &LocustHarness {
+        &self.harness
+    }
+
+    /// The wrapped harness, e.g. to take a snapshot.
+    pub fn harness_mut(&mut self) -> &mut LocustHarness {
+        &mut self.harness
+    }
+
+    /// Every activation handled so far, oldest first.
+    pub fn activations(&self) -> &[TargetActivation] {
+        &self.activations
+    }
+
+    /// Finds the one target matching `locator` in the last frame.
+    pub fn find(&mut self, locator: &Locator) -> Result<NavTarget, DriverError> {
+        self.ensure_frame();
+        let matches = locator.select(self.harness.locust.ctx.targets.all());
+        match matches.as_slice() {
+            [] => Err(DriverError::NotFound(locator.clone())),
+            [target] => Ok((*target).clone()),
+            _ => Err(DriverError::Ambiguous {
+                locator: locator.clone(),
+                count: matches.len(),
+            }),
+        }
+    }
+
+    /// Finds all targets matching `locator` in the last frame, in reading
+    /// order.
+    pub fn find_all(&mut self, locator: &Locator) -> Vec<NavTarget> {
+        self.ensure_frame();
+        locator
+            .select(self.harness.locust.ctx.targets.all())
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Finds the one target labelled `label`.
+    pub fn find_by_label(&mut self, label: &str) -> Result<NavTarget, DriverError> {
+        self.find(&Locator::label(label))
+    }
+
+    /// Finds all targets in `group`.
+    pub fn find_in_group(&mut self, group: &str) -> Vec<NavTarget> {
+        self.find_all(&Locator::group(group))
+    }
+
+    /// Finds all targets with `role`.
+    pub fn find_by_role(&mut self, role: &str) -> Vec<NavTarget> {
+        self.find_all(&Locator::role(role))
+    }
+
+    /// Draws frames until exactly one target matches `locator`.
+    ///
+    /// The last frame is checked first, so this returns immediately if the
+    /// target is already on screen.
+    pub fn wait_for(&mut self, locator: &Locator) -> Result<NavTarget, DriverError> {
+        for _ in 0..self.max_frames {
+            match self.find(locator) {
+                Err(DriverError::NotFound(_)) => self.step(),
+                result => return result,
+            }
+        }
+        self.find(locator).map_err(|err| match err {
+            DriverError::NotFound(locator) => DriverError::Timeout {
+                locator,
+                frames: self.max_frames,
+            },
+            err => err,
+        })
+    }
+
+    /// Draws frames until no target matches `locator`.
+    pub fn wait_until_gone(&mut self, locator: &Locator) -> Result<(), DriverError> {
+        for _ in 0..=self.max_frames {
+            if self.find_all(locator).is_empty() {
+                return Ok(());
+            }
+            self.step();
+        }
+        Err(DriverError::StillPresent {
+            locator: locator.clone(),
+            frames: self.max_frames,
+        })
+    }
+
+    /// Waits for the target and activates it, as if picked through a hint.
+    ///
+    /// The activation goes through `LocustContext::activate_target`, so
+    /// target callbacks run first. Returns [`DriverError::Disabled`] for
+    /// disabled targets.
+    pub fn activate(&mut self, locator: &Locator) -> Result<NavTarget, DriverError> {
+        let target = self.wait_for(locator)?;
+        if !self.harness.locust.ctx.activate_target(target.id) {
+            return Err(DriverError::Disabled(locator.clone()));
+        }
+        self.step();
+        Ok(target)
+    }
+
+    /// Waits for the target and gives it keyboard focus.
+    pub fn focus(&mut self, locator: &Locator) -> Result<NavTarget, DriverError> {
+        let target = self.wait_for(locator)?;
+        if !self.harness.locust.ctx.focus_target(target.id) {
+            return Err(DriverError::Disabled(locator.clone()));
+        }
+        self.step();
+        Ok(target)
+    }
+
+    /// Presses one key (see [`LocustHarness::press`]), then handles the
+    /// resulting activations and draws a frame.
+    pub fn press(&mut self, key: &str) {
+        self.harness.press(key);
+        self.step();
+    }
+
+    /// Types `text` (see [`LocustHarness::type_str`]), then handles the
+    /// resulting activations and draws a frame.
+    pub fn type_str(&mut self, text: &str) {
+        self.harness.type_str(text);
+        self.step();
+    }
+
+    /// Checks that exactly one target matches and that it is in `state`.
+    pub fn expect_state(
+        &mut self,
+        locator: &Locator,
+        expected: TargetState,
+    ) -> Result<(), DriverError> {
+        let actual = self.find(locator)?.state;
+        if actual != expected {
+            return Err(DriverError::UnexpectedState {
+                locator: locator.clone(),
+                expected,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Passes queued activations to the handler, then draws a frame.
+    fn step(&mut self) {
+        for activation in self.harness.locust.drain_actions() {
+            if let Some(handler) = self.handler.as_mut() {
+                handler(&activation);
+            }
+            self.activations.push(activation);
+        }
+        self.harness.render();
+    }
+
+    fn ensure_frame(&mut self) {
+        if self.harness.locust.ctx.frame_count == 0 {
+            self.harness.render();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    fn target(id: u64, y: u16) -> NavTarget {
+        NavTarget::new(id, Rect::new(0, y, 5, 1))
+    }
+
+    #[test]
+    fn test_locator_matches_all_conditions() {
+        let save = target(1, 0)
+            .with_label("Save")
+            .with_group("toolbar")
+            .with_role("button")
+            .with_metadata("dirty", "true");
+
+        assert!(Locator::label("Save").matches(&save));
+        assert!(Locator::role("button").in_group("toolbar").matches(&save));
+        assert!(Locator::any().with_metadata("dirty", "true").matches(&save));
+        assert!(!Locator::label("Save").in_group("sidebar").matches(&save));
+        assert!(!Locator::label("save").matches(&save));
+    }
+
+    #[test]
+    fn test_nth_picks_in_reading_order() {
+        let targets = vec![
+            target(1, 4).with_group("list"),
+            target(2, 0).with_group("list"),
+            target(3, 2).with_group("list"),
+        ];
+        let ids = |locator: Locator| -> Vec<u64> {
+            locator.select(&targets).iter().map(|t| t.id).collect()
+        };
+
+        assert_eq!(ids(Locator::group("list")), vec![2, 3, 1]);
+        assert_eq!(ids(Locator::group("list").nth(1)), vec![3]);
+        assert!(ids(Locator::group("list").nth(3)).is_empty());
+    }
+
+    #[test]
+    fn test_locator_display() {
+        let locator = Locator::label("Submit").with_role("button").nth(0);
+        assert_eq!(locator.to_string(), r#"label="Submit" role=button nth=0"#);
+        assert_eq!(Locator::any().to_string(), "any target");
+    }
+}
@@ -0,0 +1,335 @@
+//! Headless harness for testing a Locust integration.
+//!
+//! [`LocustHarness`] owns a `Terminal<TestBackend>`, a [`Locust`] instance
+//! and the application's render closure. Tests script input with
+//! [`press`](LocustHarness::press) and [`type_str`](LocustHarness::type_str),
+//! draw frames the same way the application's loop does (`begin_frame`,
+//! the app, then `render_overlay`) and compare the result against golden
+//! snapshots.
+//!
+//! A snapshot is the buffer's text followed by one marker line per run of
+//! styled cells, so a hint that loses its colour shows up as a diff:
+//!
+//! ```text
+//! a Inbox
+//!   Sent
+//! -- styles --
+//! y=0 x=0..1 fg=Black bg=Yellow mod=BOLD
+//! ```
+//!
+//! Goldens live in `tests/snapshots/<name>.snap` under the crate being
+//! tested. Run the tests with `LOCUST_UPDATE_SNAPSHOTS=1` to write them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use locust::prelude::*;
+//! use locust::testing::LocustHarness;
+//! use ratatui::layout::Rect;
+//! use ratatui::widgets::Paragraph;
+//!
+//! let mut harness = LocustHarness::new(20, 2, |frame, ctx| {
+//!     frame.render_widget(Paragraph::new("Inbox"), frame.area());
+//!     ctx.targets.register(NavTarget::new(1, Rect::new(0, 0, 5, 1)));
+//! })
+//! .with_plugin(NavPlugin::new());
+//!
+//! assert!(harness.press("f").consumed);
+//! // The hint label is drawn over the target
+//! assert!(harness.snapshot().contains("bg=Yellow"));
+//!
+//! harness.press("a");
+//! assert_eq!(harness.locust.drain_actions()[0].target_id, 1);
+//! ```
+
+use crate::core::context::{Locust, LocustConfig, LocustContext};
+use crate::core::input::LocustEventOutcome;
+use crate::core::keybindings::KeyBinding;
+use crate::core::plugin::LocustPlugin;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use ratatui::{Frame, Terminal};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// Environment variable that makes [`LocustHarness::assert_snapshot`]
+/// write goldens instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "LOCUST_UPDATE_SNAPSHOTS";
+
+/// Separates the text of a snapshot from its style markers.
+const STYLES_HEADER: &str = "-- styles --";
+
+type AppRender = Box<dyn FnMut(&mut Frame, &mut LocustContext)>;
+
+/// Drives a [`Locust`] and an application render closure on a
+/// [`TestBackend`].
+pub struct LocustHarness {
+    /// The Locust instance under test.
+    pub locust: Locust<TestBackend>,
+    terminal: Terminal<TestBackend>,
+    app: AppRender,
+    snapshot_dir: PathBuf,
+}
+
+impl LocustHarness {
+    /// Creates a harness with a `width` x `height` terminal.
+    ///
+    /// `app` draws the application and registers its targets, like the
+    /// application's own draw code. No plugins are registered.
+    pub fn new(
+        width: u16,
+        height: u16,
+        app: impl FnMut(&mut Frame, &mut LocustContext) + 'static,
+    ) -> Self {
+        let snapshot_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join("tests")
+            .join("snapshots");
+
+        Self {
+            locust: Locust::new(LocustConfig::default()),
+            terminal: Terminal::new(TestBackend::new(width, height))
+                .expect("TestBackend never fails"),
+            app: Box::new(app),
+            snapshot_dir,
+        }
+    }
+
+    /// Registers a plugin.
+    pub fn with_plugin<P>(mut self, plugin: P) -> Self
+    where
+        P: LocustPlugin<TestBackend> + 'static,
+    {
+        self.locust.register_plugin(plugin);
+        self
+    }
+
+    /// Sets the directory goldens are read from and written to.
+    pub fn with_snapshot_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.snapshot_dir = dir.into();
+        self
+    }
+
+    /// The terminal frames are drawn to.
+    pub fn terminal(&self) -> &Terminal<TestBackend> {
+        &self.terminal
+    }
+
+    /// Draws one frame: `begin_frame`, the app, then the plugin overlays.
+    pub fn render(&mut self) -> &Buffer {
+        let Self {
+            locust,
+            terminal,
+            app,
+            ..
+        } = self;
+
+        locust.begin_frame();
+        terminal
+            .draw(|frame| {
+                app(frame, &mut locust.ctx);
+                locust.render_overlay(frame);
+            })
+            .expect("TestBackend never fails");
+        terminal.backend().buffer()
+    }
+
+    /// Passes an event to Locust.
+    ///
+    /// Events are handled against the targets of the last frame; if no
+    /// frame has been drawn yet, one is drawn first.
+    pub fn send(&mut self, event: Event) -> LocustEventOutcome {
+        if self.locust.ctx.frame_count == 0 {
+            self.render();
+        }
+        self.locust.on_event(&event)
+    }
+
+    /// Presses one key, written in sequence notation: `f`, `esc`,
+    /// `C-p`, `S-tab`, `<F5>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` cannot be parsed.
+    pub fn press(&mut self, key: &str) -> LocustEventOutcome {
+        let binding: KeyBinding = key
+            .parse()
+            .unwrap_or_else(|err| panic!("LocustHarness: {}", err));
+        let event = KeyEvent::new(binding.key.to_keycode(), binding.get_modifiers());
+        self.send(Event::Key(event))
+    }
+
+    /// Types each character of `text` as an unmodified key press.
+    ///
+    /// Returns the outcome of the last key.
+    pub fn type_str(&mut self, text: &str) -> LocustEventOutcome {
+        let mut outcome = LocustEventOutcome::NOT_HANDLED;
+        for c in text.chars() {
+            let event = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            outcome = self.send(Event::Key(event));
+        }
+        outcome
+    }
+
+    /// Draws a frame and returns it in snapshot form.
+    pub fn snapshot(&mut self) -> String {
+        snapshot_buffer(self.render())
+    }
+
+    /// Draws a frame and compares it against the golden `name`.
+    ///
+    /// With [`UPDATE_SNAPSHOTS_ENV`] set, the golden is written instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the golden is missing or differs from the frame.
+    pub fn assert_snapshot(&mut self, name: &str) {
+        let actual = self.snapshot();
+        let path = self.snapshot_path(name);
+
+        if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).expect("Cannot create snapshot directory");
+            }
+            std::fs::write(&path, &actual).expect("Cannot write snapshot");
+            return;
+        }
+
+        let expected = match std::fs::read_to_string(&path) {
+            Ok(expected) => expected,
+            Err(err) => panic!(
+                "Snapshot {:?} cannot be read ({}); run with {}=1 to create it.\n\n{}",
+                path, err, UPDATE_SNAPSHOTS_ENV, actual
+            ),
+        };
+        if expected != actual {
+            panic!(
+                "Snapshot {:?} differs; run with {}=1 to accept.\n\n\
+                 --- expected\n{}\n+++ actual\n{}",
+                path, UPDATE_SNAPSHOTS_ENV, expected, actual
+            );
+        }
+    }
+
+    fn snapshot_path(&self, name: &str) -> PathBuf {
+        self.snapshot_dir.join(format!("{}.snap", name))
+    }
+}
+
+/// Formats a buffer as a snapshot.
+///
+/// Rows are written with trailing spaces removed. Each run of identically
+/// styled cells on a row that is not the default style gets a marker line
+/// after the text, e.g. `y=2 x=4..6 fg=Black bg=Yellow mod=BOLD`.
+pub fn snapshot_buffer(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    let mut styles = String::new();
+
+    for y in area.top()..area.bottom() {
+        let row: String = (area.left()..area.right())
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        text.push_str(row.trim_end());
+        text.push('\n');
+
+        let mut x = area.left();
+        while x < area.right() {
+            let style = style_marker(&buffer[(x, y)]);
+            let start = x;
+            while x < area.right() && style_marker(&buffer[(x, y)]) == style {
+                x += 1;
+            }
+            if let Some(style) = style {
+                let _ = writeln!(styles, "y={} x={}..{} {}", y, start, x, style);
+            }
+        }
+    }
+
+    if !styles.is_empty() {
+        text.push_str(STYLES_HEADER);
+        text.push('\n');
+        text.push_str(&styles);
+    }
+    text
+}
+
+/// Describes a cell's style, or `None` for the default style.
+fn style_marker(cell: &Cell) -> Option<String> {
+    let mut parts = Vec::new();
+    if cell.fg != Color::Reset {
+        parts.push(format!("fg={:?}", cell.fg));
+    }
+    if cell.bg != Color::Reset {
+        parts.push(format!("bg={:?}", cell.bg));
+    }
+    if cell.modifier != Modifier::empty() {
+        let names: Vec<&str> = cell.modifier.iter_names().map(|(name, _)| name).collect();
+        parts.push(format!("mod={}", names.join("|")));
+    }
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::targets::NavTarget;
+    use crate::plugins::nav::NavPlugin;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+    use ratatui::widgets::Paragraph;
+
+    #[test]
+    fn test_snapshot_marks_style_runs() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 2));
+        buffer.set_string(0, 0, "ab", Style::default().fg(Color::Red));
+        buffer.set_string(2, 0, "cd  ", Style::default());
+        buffer.set_string(
+            1,
+            1,
+            "x",
+            Style::default()
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD | Modifier::ITALIC),
+        );
+
+        assert_eq!(
+            snapshot_buffer(&buffer),
+            "abcd\n x\n-- styles --\n\
+             y=0 x=0..2 fg=Red\n\
+             y=1 x=1..2 bg=Blue mod=BOLD|ITALIC\n"
+        );
+    }
+
+    #[test]
+    fn test_unstyled_snapshot_has_no_style_section() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buffer.set_string(0, 0, "hi", Style::default());
+        assert_eq!(snapshot_buffer(&buffer), "hi\n");
+    }
+
+    #[test]
+    fn test_press_parses_sequence_notation() {
+        let mut harness = LocustHarness::new(10, 1, |frame, ctx| {
+            frame.render_widget(Paragraph::new("Inbox"), frame.area());
+            ctx.targets
+                .register(NavTarget::new(1, Rect::new(0, 0, 5, 1)));
+        })
+        .with_plugin(NavPlugin::new());
+
+        // The first event draws a frame so targets are registered
+        assert!(harness.press("f").consumed);
+        assert_eq!(harness.locust.ctx.frame_count, 1);
+        assert!(harness.press("<esc>").consumed);
+        assert!(!harness.press("C-x").consumed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid key")]
+    fn test_press_rejects_unknown_keys() {
+        LocustHarness::new(10, 1, |_, _| {}).press("nokey");
+    }
+}
@@ -0,0 +1,12 @@
+//! Tools for testing applications that embed Locust.
+//!
+//! - [`LocustHarness`] draws frames on a `TestBackend`, scripts key presses
+//!   and compares frames against golden snapshots.
+//! - [`LocustDriver`] builds on the harness to find targets by label, group
+//!   or role and drive end-to-end flows without screen coordinates.
+
+pub mod driver;
+pub mod harness;
+
+pub use driver::{DriverError, Locator, LocustDriver};
+pub use harness::{snapshot_buffer, LocustHarness, UPDATE_SNAPSHOTS_ENV};
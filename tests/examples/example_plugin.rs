@@ -29,7 +29,7 @@ impl StatusBarPlugin {
     }
 }
 
-impl<B: Backend> LocustPlugin<B> for StatusBarPlugin {
+impl<B: Backend + 'static> LocustPlugin<B> for StatusBarPlugin {
     fn id(&self) -> &'static str {
         Self::ID
     }
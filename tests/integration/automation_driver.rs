@@ -0,0 +1,142 @@
+//! Integration tests for driving an application by target labels.
+
+use locust::core::targets::TargetBuilder;
+use locust::prelude::*;
+use locust::testing::{DriverError, Locator, LocustDriver, LocustHarness};
+use ratatui::layout::Rect;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A folder sidebar and a form whose Submit button appears a couple of
+/// frames after Load is activated.
+#[derive(Default)]
+struct App {
+    folder: String,
+    /// Frames until loading finishes, like a background fetch
+    loading: u8,
+    loaded: bool,
+    submitted: bool,
+}
+
+fn driver() -> (LocustDriver, Rc<RefCell<App>>) {
+    let app = Rc::new(RefCell::new(App::default()));
+
+    let view = app.clone();
+    let harness = LocustHarness::new(40, 10, move |_frame, ctx| {
+        let mut app = view.borrow_mut();
+        if app.loading > 0 {
+            app.loading -= 1;
+            app.loaded = app.loading == 0;
+        }
+
+        let mut builder = TargetBuilder::new();
+        for (row, folder) in ["Inbox", "Sent", "Drafts"].into_iter().enumerate() {
+            let item = builder
+                .list_item(Rect::new(0, row as u16, 10, 1), folder)
+                .with_group("sidebar");
+            ctx.targets.register(item);
+        }
+
+        let load = builder.button(Rect::new(12, 0, 6, 1), "Load");
+        ctx.targets.register(if app.loading > 0 || app.loaded {
+            load.with_state(TargetState::Disabled)
+        } else {
+            load
+        });
+        if app.loaded && !app.submitted {
+            ctx.targets
+                .register(builder.button(Rect::new(12, 2, 8, 1), "Submit"));
+        }
+    })
+    .with_plugin(NavPlugin::new());
+
+    let state = app.clone();
+    let driver = LocustDriver::new(harness).with_action_handler(move |activation| {
+        let mut app = state.borrow_mut();
+        match activation.label.as_deref() {
+            Some("Load") => app.loading = 2,
+            Some("Submit") => app.submitted = true,
+            Some(folder) => app.folder = folder.to_string(),
+            None => {}
+        }
+    });
+    (driver, app)
+}
+
+#[test]
+fn test_find_by_label_group_and_role() {
+    let (mut driver, _) = driver();
+
+    assert_eq!(driver.find_by_label("Sent").unwrap().rect.y, 1);
+    assert_eq!(driver.find_in_group("sidebar").len(), 3);
+    assert_eq!(driver.find_by_role("button").len(), 1);
+    assert!(matches!(
+        driver.find(&Locator::role("list_item")),
+        Err(DriverError::Ambiguous { count: 3, .. })
+    ));
+    assert!(matches!(
+        driver.find_by_label("Submit"),
+        Err(DriverError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_end_to_end_flow() {
+    let (mut driver, app) = driver();
+
+    driver.activate(&Locator::group("sidebar").nth(2)).unwrap();
+    assert_eq!(app.borrow().folder, "Drafts");
+
+    driver.activate(&Locator::label("Load")).unwrap();
+    let submit = driver.wait_for(&Locator::label("Submit")).unwrap();
+    assert_eq!(submit.role(), Some("button"));
+    driver
+        .expect_state(&Locator::label("Load"), TargetState::Disabled)
+        .unwrap();
+
+    driver.activate(&Locator::label("Submit")).unwrap();
+    driver.wait_until_gone(&Locator::label("Submit")).unwrap();
+    assert!(app.borrow().submitted);
+
+    let labels: Vec<_> = driver
+        .activations()
+        .iter()
+        .filter_map(|a| a.label.clone())
+        .collect();
+    assert_eq!(labels, vec!["Drafts", "Load", "Submit"]);
+}
+
+#[test]
+fn test_hint_keys_go_through_the_handler() {
+    let (mut driver, app) = driver();
+
+    driver.press("f");
+    driver.type_str("d");
+    assert_eq!(app.borrow().folder, "Sent");
+}
+
+#[test]
+fn test_failures_are_reported() {
+    let (driver, _) = driver();
+    let mut driver = driver.with_max_frames(3);
+
+    assert_eq!(
+        driver.wait_for(&Locator::label("Submit")).unwrap_err(),
+        DriverError::Timeout {
+            locator: Locator::label("Submit"),
+            frames: 3
+        }
+    );
+    driver.activate(&Locator::label("Load")).unwrap();
+    assert!(matches!(
+        driver.activate(&Locator::label("Load")),
+        Err(DriverError::Disabled(_))
+    ));
+    assert!(matches!(
+        driver.expect_state(&Locator::label("Inbox"), TargetState::Selected),
+        Err(DriverError::UnexpectedState {
+            actual: TargetState::Normal,
+            ..
+        })
+    ));
+}
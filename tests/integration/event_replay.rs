@@ -0,0 +1,166 @@
+//! Integration tests for recording events and replaying them.
+
+use locust::core::recording::{EventRecorder, Recording, RecordingError};
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+fn key(c: char) -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+}
+
+fn ctrl(c: char) -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL))
+}
+
+fn esc() -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+}
+
+fn create_locust() -> Locust<TestBackend> {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    locust.register_plugin(OmnibarPlugin::new());
+    locust
+}
+
+fn draw(locust: &mut Locust<TestBackend>) {
+    let targets = &mut locust.ctx.targets;
+    targets.register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_label("Inbox"));
+    targets.register(NavTarget::new(2, Rect::new(0, 2, 10, 1)).with_label("Sent"));
+}
+
+/// Runs a short session, recording it to `path`.
+fn record_session(path: &std::path::Path) {
+    let mut locust = create_locust();
+    locust.start_recording(EventRecorder::create(path).unwrap());
+
+    let frame = |locust: &mut Locust<TestBackend>| {
+        locust.begin_frame();
+        draw(locust);
+    };
+
+    frame(&mut locust);
+    locust.on_event(&key('f'));
+    frame(&mut locust);
+    locust.on_event(&key('a'));
+    frame(&mut locust);
+    locust.on_event(&ctrl('p'));
+    locust.on_event(&key('q'));
+    locust.on_event(&esc());
+    locust.on_event(&key('z'));
+
+    assert_eq!(locust.stop_recording().unwrap().len(), 6);
+}
+
+#[test]
+fn test_recording_is_json_lines() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("session.jsonl");
+    record_session(&path);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents.lines().count(), 6);
+
+    let recording = Recording::load(&path).unwrap();
+    let consumers: Vec<Option<&str>> = recording
+        .events
+        .iter()
+        .map(|e| e.consumed_by.as_deref())
+        .collect();
+    assert_eq!(
+        consumers,
+        vec![
+            Some("locust.nav"),
+            Some("locust.nav"),
+            Some("locust.omnibar"),
+            Some("locust.omnibar"),
+            // Esc pops command mode in Locust itself
+            None,
+            None
+        ]
+    );
+    let frames: Vec<u64> = recording.events.iter().map(|e| e.frame).collect();
+    assert_eq!(frames, vec![1, 2, 3, 3, 3, 3]);
+    assert!(!recording.events[5].outcome.consumed);
+}
+
+#[test]
+fn test_replay_reproduces_outcomes() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("session.jsonl");
+    record_session(&path);
+
+    let recording = Recording::load(&path).unwrap();
+    let mut locust = create_locust();
+    recording.replay(&mut locust, draw).unwrap();
+
+    // The hint for the first target was typed during the session
+    assert_eq!(locust.drain_actions().len(), 1);
+}
+
+#[test]
+fn test_replay_reports_divergence() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("session.jsonl");
+    record_session(&path);
+
+    let recording = Recording::load(&path).unwrap();
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+
+    let Err(RecordingError::Mismatch {
+        index,
+        expected,
+        actual,
+    }) = recording.replay(&mut locust, draw)
+    else {
+        panic!("replay without the omnibar should diverge");
+    };
+    assert_eq!(index, 2);
+    assert_eq!(expected.consumed_by.as_deref(), Some("locust.omnibar"));
+    assert_eq!(actual.consumed_by, None);
+}
+
+#[test]
+fn test_replay_reproduces_sequence_timeouts() {
+    let create_locust = || {
+        let mut locust = create_locust();
+        locust
+            .ctx
+            .keymap
+            .bind_sequence("top", "g g".parse().unwrap())
+            .unwrap();
+        locust
+    };
+    let mut locust = create_locust();
+    locust.start_recording(EventRecorder::in_memory());
+
+    let start = Instant::now() + Duration::from_secs(60);
+    let at = |ms| start + Duration::from_millis(ms);
+    locust.on_event_at(&key('g'), at(0));
+    // The timeout replays the lone 'g' to the app
+    assert!(locust.tick(at(1000)));
+    locust.on_event_at(&key('g'), at(1100));
+    locust.on_event_at(&key('g'), at(1200));
+    assert_eq!(
+        locust.ctx.key_sequences.take_action().as_deref(),
+        Some("top")
+    );
+
+    let recording = locust.stop_recording().unwrap().into_recording().unwrap();
+    let tick = &recording.events[1];
+    assert!(tick.event.is_none());
+    assert_eq!(tick.at, Duration::from_millis(1000));
+    assert!(tick.outcome.request_redraw);
+    assert_eq!(recording.events[3].at, Duration::from_millis(1200));
+
+    let mut fresh = create_locust();
+    recording.replay(&mut fresh, draw).unwrap();
+    assert_eq!(
+        fresh.ctx.key_sequences.take_action().as_deref(),
+        Some("top")
+    );
+}
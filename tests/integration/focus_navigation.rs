@@ -0,0 +1,301 @@
+//! Integration tests for keyboard focus routed through Locust.
+
+use locust::core::focus::{FocusEvent, FocusOrder, FocusTracking};
+use locust::core::keybindings::{KeyBinding, KeyCodeDef};
+use locust::core::spatial::SpatialConfig;
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+
+fn press(code: KeyCode, modifiers: KeyModifiers) -> Event {
+    Event::Key(KeyEvent::new(code, modifiers))
+}
+
+fn tab() -> Event {
+    press(KeyCode::Tab, KeyModifiers::NONE)
+}
+
+fn shift_tab() -> Event {
+    press(KeyCode::BackTab, KeyModifiers::SHIFT)
+}
+
+fn enter() -> Event {
+    press(KeyCode::Enter, KeyModifiers::NONE)
+}
+
+/// Registers the frame's targets, offsetting IDs like an app that
+/// regenerates them every frame.
+fn draw_frame(locust: &mut Locust<TestBackend>, id_base: u64) {
+    locust.begin_frame();
+    let targets = &mut locust.ctx.targets;
+    targets.register(NavTarget::new(id_base + 1, Rect::new(20, 0, 5, 1)).with_label("Save"));
+    targets.register(NavTarget::new(id_base + 2, Rect::new(0, 0, 5, 1)).with_label("Open"));
+    targets.register(
+        NavTarget::new(id_base + 3, Rect::new(0, 2, 5, 1))
+            .with_label("Quit")
+            .with_priority(TargetPriority::High),
+    );
+}
+
+fn create_locust() -> Locust<TestBackend> {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    let named =
+        |key: &str, modifiers| KeyBinding::with_modifiers(KeyCodeDef::Named(key.into()), modifiers);
+    for (action, binding) in [
+        ("focus_next", named("tab", KeyModifiers::NONE)),
+        ("focus_prev", named("backtab", KeyModifiers::NONE)),
+        ("focus_up", named("up", KeyModifiers::SHIFT)),
+        ("focus_down", named("down", KeyModifiers::SHIFT)),
+        ("focus_left", named("left", KeyModifiers::SHIFT)),
+        ("focus_right", named("right", KeyModifiers::SHIFT)),
+    ] {
+        locust.ctx.bind_key(action, binding).unwrap();
+    }
+    draw_frame(&mut locust, 0);
+    locust
+}
+
+#[test]
+fn test_unbound_focus_keys_reach_the_app() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    draw_frame(&mut locust, 0);
+
+    assert!(!locust.on_event(&tab()).consumed);
+    assert!(!locust.on_event(&shift_tab()).consumed);
+    assert!(!locust.on_event(&shift_arrow(KeyCode::Down)).consumed);
+    assert_eq!(locust.ctx.focus.focused(), None);
+}
+
+#[test]
+fn test_tab_cycles_in_reading_order() {
+    let mut locust = create_locust();
+
+    assert!(locust.on_event(&tab()).consumed);
+    assert_eq!(locust.ctx.focus.focused(), Some(2));
+    locust.on_event(&tab());
+    assert_eq!(locust.ctx.focus.focused(), Some(1));
+    locust.on_event(&tab());
+    locust.on_event(&tab());
+    assert_eq!(locust.ctx.focus.focused(), Some(2));
+
+    locust.on_event(&shift_tab());
+    assert_eq!(locust.ctx.focus.focused(), Some(3));
+    assert_eq!(
+        locust.ctx.targets.by_id(3).unwrap().state,
+        TargetState::Highlighted
+    );
+    assert_eq!(
+        locust.ctx.targets.by_id(2).unwrap().state,
+        TargetState::Normal
+    );
+}
+
+#[test]
+fn test_priority_order() {
+    let mut locust = create_locust();
+    locust.ctx.focus.order = FocusOrder::Priority;
+
+    locust.on_event(&tab());
+    assert_eq!(locust.ctx.focus.focused(), Some(3));
+}
+
+#[test]
+fn test_focus_events_are_queued() {
+    let mut locust = create_locust();
+    locust.on_event(&tab());
+    locust.on_event(&tab());
+
+    assert_eq!(
+        locust.ctx.focus.drain_events(),
+        vec![
+            FocusEvent::Focused(2),
+            FocusEvent::Blurred(2),
+            FocusEvent::Focused(1)
+        ]
+    );
+}
+
+#[test]
+fn test_enter_activates_focused_target() {
+    let mut locust = create_locust();
+
+    // Nothing focused: Enter belongs to the application
+    assert!(!locust.on_event(&enter()).consumed);
+
+    locust.on_event(&tab());
+    assert!(locust.on_event(&enter()).consumed);
+
+    let actions = locust.drain_actions();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].target_id, 2);
+    assert_eq!(locust.ctx.focus.selected(), Some(2));
+    assert_eq!(
+        locust.ctx.targets.by_id(2).unwrap().state,
+        TargetState::Selected
+    );
+}
+
+#[test]
+fn test_focus_follows_label_across_frames() {
+    let mut locust = create_locust();
+    locust.ctx.focus.tracking = FocusTracking::Label;
+    locust.on_event(&tab());
+    locust.on_event(&tab());
+    assert_eq!(
+        locust.ctx.focused_target().unwrap().label.as_deref(),
+        Some("Save")
+    );
+
+    draw_frame(&mut locust, 100);
+    locust.on_event(&tab());
+
+    // "Save" is now 101; the next target in reading order is "Quit"
+    assert_eq!(locust.ctx.focus.focused(), Some(103));
+}
+
+#[test]
+fn test_target_states_follow_regenerated_ids() {
+    let mut locust = create_locust();
+    locust.ctx.focus.tracking = FocusTracking::Label;
+    locust.on_event(&tab());
+    locust.on_event(&enter());
+    locust.ctx.update_hover(22, 0);
+
+    // The same targets with their IDs shuffled
+    locust.begin_frame();
+    let targets = &mut locust.ctx.targets;
+    targets.register(NavTarget::new(2, Rect::new(20, 0, 5, 1)).with_label("Save"));
+    targets.register(NavTarget::new(3, Rect::new(0, 0, 5, 1)).with_label("Open"));
+    targets.register(
+        NavTarget::new(1, Rect::new(0, 2, 5, 1))
+            .with_label("Quit")
+            .with_priority(TargetPriority::High),
+    );
+
+    // Before any sync, the states are already on the right targets
+    let state = |locust: &Locust<TestBackend>, id| locust.ctx.targets.by_id(id).unwrap().state;
+    assert_eq!(state(&locust, 1), TargetState::Normal);
+    assert_eq!(state(&locust, 2), TargetState::Highlighted);
+    assert_eq!(state(&locust, 3), TargetState::Selected);
+
+    locust.tick(std::time::Instant::now());
+    assert_eq!(locust.ctx.focus.focused(), Some(3));
+    assert_eq!(locust.ctx.focus.selected(), Some(3));
+    assert_eq!(locust.ctx.mouse.hovered_target, Some(2));
+
+    // Moving the pointer away clears the highlight it left behind
+    locust.ctx.update_hover(0, 5);
+    assert_eq!(state(&locust, 2), TargetState::Normal);
+    assert_eq!(state(&locust, 1), TargetState::Normal);
+}
+
+#[test]
+fn test_focus_is_lost_when_target_disappears() {
+    let mut locust = create_locust();
+    locust.on_event(&tab());
+    locust.ctx.focus.drain_events();
+
+    draw_frame(&mut locust, 100);
+    locust.tick(std::time::Instant::now());
+
+    assert_eq!(locust.ctx.focus.focused(), None);
+    assert_eq!(
+        locust.ctx.focus.drain_events(),
+        vec![FocusEvent::Blurred(2)]
+    );
+}
+
+#[test]
+fn test_group_fallback_queues_focus_events() {
+    let mut locust = create_locust();
+    locust.ctx.focus.tracking = FocusTracking::Group;
+    locust.begin_frame();
+    for (id, y) in [(1, 0), (2, 1)] {
+        locust
+            .ctx
+            .targets
+            .register(NavTarget::new(id, Rect::new(0, y, 5, 1)).with_group("list"));
+    }
+    locust.on_event(&tab());
+    locust.ctx.focus.drain_events();
+
+    // Row 1 scrolls away; focus falls back to the rest of the group
+    locust.begin_frame();
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(2, Rect::new(0, 0, 5, 1)).with_group("list"));
+    locust.tick(std::time::Instant::now());
+
+    assert_eq!(locust.ctx.focus.focused(), Some(2));
+    assert_eq!(
+        locust.ctx.focus.drain_events(),
+        vec![FocusEvent::Blurred(1), FocusEvent::Focused(2)]
+    );
+    assert_eq!(
+        locust.ctx.targets.by_id(2).unwrap().state,
+        TargetState::Highlighted
+    );
+}
+
+#[test]
+fn test_enter_passes_through_without_registered_target() {
+    let mut locust = create_locust();
+    locust.on_event(&tab());
+
+    // Between begin_frame and drawing, the focused target is not registered
+    locust.begin_frame();
+    assert!(!locust.on_event(&enter()).consumed);
+    assert!(locust.drain_actions().is_empty());
+    assert_eq!(locust.ctx.focus.selected(), None);
+}
+
+#[test]
+fn test_hint_mode_keeps_tab() {
+    let mut locust = create_locust();
+    locust.on_event(&press(KeyCode::Char('f'), KeyModifiers::NONE));
+
+    locust.on_event(&tab());
+    assert_eq!(locust.ctx.focus.focused(), None);
+}
+
+fn shift_arrow(code: KeyCode) -> Event {
+    press(code, KeyModifiers::SHIFT)
+}
+
+#[test]
+fn test_shift_arrows_move_focus_spatially() {
+    let mut locust = create_locust();
+
+    // Nothing focused yet: start at the first target
+    locust.on_event(&shift_arrow(KeyCode::Right));
+    assert_eq!(locust.ctx.focus.focused(), Some(2));
+
+    locust.on_event(&shift_arrow(KeyCode::Right));
+    assert_eq!(locust.ctx.focus.focused(), Some(1));
+    locust.on_event(&shift_arrow(KeyCode::Left));
+    locust.on_event(&shift_arrow(KeyCode::Down));
+    assert_eq!(locust.ctx.focus.focused(), Some(3));
+
+    // Nothing further down; the key is left for the application
+    assert!(!locust.on_event(&shift_arrow(KeyCode::Down)).consumed);
+
+    // Plain arrows are untouched
+    assert!(
+        !locust
+            .on_event(&press(KeyCode::Up, KeyModifiers::NONE))
+            .consumed
+    );
+}
+
+#[test]
+fn test_spatial_wrap() {
+    let mut locust = create_locust();
+    locust.ctx.focus.spatial = SpatialConfig::new().with_wrap(true);
+    locust.ctx.focus_target(1);
+
+    locust.on_event(&shift_arrow(KeyCode::Right));
+    assert_eq!(locust.ctx.focus.focused(), Some(2));
+}
@@ -0,0 +1,56 @@
+//! Integration tests for the headless test harness.
+
+use locust::prelude::*;
+use locust::testing::LocustHarness;
+use ratatui::layout::Rect;
+use ratatui::widgets::Paragraph;
+
+/// A mailbox list below the row the hint status bar is drawn on.
+fn mailbox() -> LocustHarness {
+    LocustHarness::new(30, 6, |frame, ctx| {
+        let area = Rect::new(0, 2, 30, 2);
+        frame.render_widget(Paragraph::new("Inbox\nSent"), area);
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(0, 2, 5, 1)).with_label("Inbox"));
+        ctx.targets
+            .register(NavTarget::new(2, Rect::new(0, 3, 4, 1)).with_label("Sent"));
+    })
+    .with_plugin(NavPlugin::new())
+    .with_plugin(OmnibarPlugin::new())
+}
+
+#[test]
+fn test_hint_overlay_snapshot() {
+    let mut harness = mailbox();
+    harness.assert_snapshot("harness_plain");
+
+    assert!(harness.press("f").consumed);
+    harness.assert_snapshot("harness_hints");
+
+    harness.press("s");
+    let actions = harness.locust.drain_actions();
+    assert_eq!(actions[0].target_id, 2);
+}
+
+#[test]
+fn test_omnibar_snapshot() {
+    let mut harness = mailbox();
+    assert!(harness.press("C-p").consumed);
+    harness.type_str("quit");
+    harness.assert_snapshot("harness_omnibar");
+}
+
+#[test]
+fn test_changed_frame_fails_snapshot() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("stale.snap"), "\n\nInbox\nOutbox\n").unwrap();
+    let mut harness = mailbox().with_snapshot_dir(dir.path());
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        harness.assert_snapshot("stale");
+    }));
+    // Updating goldens would overwrite the stale file instead
+    if std::env::var_os(locust::testing::UPDATE_SNAPSHOTS_ENV).is_none() {
+        assert!(result.is_err());
+    }
+}
@@ -273,7 +273,7 @@ fn test_custom_config_integration() {
 
     let plugin = HighlightPlugin::with_config(config);
 
-    assert_eq!(plugin.config().activation_key, 'h');
+    assert_eq!(plugin.config().activation_key, Some('h'));
     assert_eq!(plugin.config().dim_opacity, 150);
     assert!(!plugin.config().show_navigation_hints);
 }
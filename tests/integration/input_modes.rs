@@ -0,0 +1,156 @@
+//! Integration tests for the mode stack shared by plugins.
+
+use locust::core::keybindings::{KeyBinding, KeyCodeDef, KeySequence};
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+
+fn key(c: char) -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+}
+
+fn esc() -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+}
+
+fn ctrl_p() -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL))
+}
+
+fn create_locust() -> Locust<TestBackend> {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    locust.register_plugin(OmnibarPlugin::new());
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_label("One"));
+    locust
+}
+
+fn nav_mode(locust: &Locust<TestBackend>) -> NavMode {
+    locust.get_plugin::<NavPlugin>().unwrap().mode()
+}
+
+fn omnibar_mode(locust: &Locust<TestBackend>) -> OmnibarMode {
+    locust.get_plugin::<OmnibarPlugin>().unwrap().state().mode()
+}
+
+#[test]
+fn test_escape_pops_hint_mode() {
+    let mut locust = create_locust();
+
+    locust.on_event(&key('f'));
+    assert_eq!(locust.ctx.modes.current(), &Mode::Hint);
+    assert!(locust.is_capturing_input());
+
+    let outcome = locust.on_event(&esc());
+    assert!(outcome.consumed);
+    assert_eq!(locust.ctx.modes.current(), &Mode::Normal);
+    assert_eq!(nav_mode(&locust), NavMode::Normal);
+
+    // In normal mode Esc goes to the app
+    assert!(!locust.on_event(&esc()).consumed);
+}
+
+#[test]
+fn test_command_mode_blocks_other_plugins() {
+    let mut locust = create_locust();
+
+    locust.on_event(&ctrl_p());
+    assert_eq!(omnibar_mode(&locust), OmnibarMode::Input);
+    assert!(locust.is_capturing_input());
+
+    // 'f' is typed into the omnibar instead of starting hint mode
+    locust.on_event(&key('f'));
+    assert_eq!(nav_mode(&locust), NavMode::Normal);
+    assert_eq!(locust.ctx.modes.depth(), 1);
+
+    locust.on_event(&esc());
+    assert_eq!(omnibar_mode(&locust), OmnibarMode::Inactive);
+    assert!(!locust.is_capturing_input());
+}
+
+#[test]
+fn test_hint_mode_blocks_omnibar_activation() {
+    let mut locust = create_locust();
+
+    locust.on_event(&key('f'));
+    locust.on_event(&ctrl_p());
+
+    assert_eq!(omnibar_mode(&locust), OmnibarMode::Inactive);
+    assert_eq!(locust.ctx.modes.current(), &Mode::Hint);
+}
+
+#[test]
+fn test_mode_scoped_binding() {
+    let mut locust = create_locust();
+    locust
+        .ctx
+        .keymap
+        .bind_in_mode("hint", "nav.cancel", KeyBinding::new(KeyCodeDef::Char('x')))
+        .unwrap();
+
+    // 'x' means nothing in normal mode
+    assert!(!locust.on_event(&key('x')).consumed);
+
+    locust.on_event(&key('f'));
+    locust.on_event(&key('x'));
+    assert_eq!(nav_mode(&locust), NavMode::Normal);
+    assert_eq!(locust.ctx.modes.current(), &Mode::Normal);
+}
+
+#[test]
+fn test_sequences_pause_while_capturing_input() {
+    let mut locust = create_locust();
+    let sequence: KeySequence = "g g".parse().unwrap();
+    locust.ctx.keymap.bind_sequence("top", sequence).unwrap();
+
+    locust.on_event(&ctrl_p());
+    locust.on_event(&key('g'));
+    locust.on_event(&key('g'));
+
+    assert_eq!(
+        locust
+            .get_plugin::<OmnibarPlugin>()
+            .unwrap()
+            .state()
+            .buffer(),
+        "gg"
+    );
+    assert_eq!(locust.ctx.key_sequences.take_action(), None);
+}
+
+#[test]
+fn test_unskippable_tour_ignores_pop_binding() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    let mut highlight = HighlightPlugin::new();
+    highlight.register_tour(
+        Tour::new("intro")
+            .add_step(TourStep::new("Welcome", "Hello"))
+            .with_skippable(false),
+    );
+    locust.register_plugin(highlight);
+
+    locust.on_event(&key('?'));
+    assert_eq!(locust.ctx.modes.current(), &Mode::Tour);
+
+    locust.on_event(&esc());
+    assert_eq!(locust.ctx.modes.current(), &Mode::Tour);
+}
+
+#[test]
+fn test_app_defined_mode() {
+    let mut locust = create_locust();
+    locust
+        .ctx
+        .modes
+        .push_frame(ModeFrame::new(Mode::from("insert"), "app").capturing_input(true));
+
+    // Hint mode stays off while the app's insert mode captures keys
+    assert!(!locust.on_event(&key('f')).consumed);
+    assert_eq!(nav_mode(&locust), NavMode::Normal);
+
+    assert_eq!(locust.pop_mode(), Some(Mode::Custom("insert".into())));
+    assert_eq!(locust.pop_mode(), None);
+}
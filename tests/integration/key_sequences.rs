@@ -0,0 +1,122 @@
+//! Integration tests for multi-key sequences dispatched through Locust.
+
+use locust::core::keybindings::{KeyBinding, KeyCodeDef, KeySequence};
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use std::time::Instant;
+
+fn key(c: char) -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+}
+
+fn sequence(s: &str) -> KeySequence {
+    s.parse().unwrap()
+}
+
+fn create_locust() -> Locust<TestBackend> {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(1, Rect::new(0, 0, 10, 1)).with_label("One"));
+    locust
+}
+
+fn nav_mode(locust: &Locust<TestBackend>) -> NavMode {
+    locust.get_plugin::<NavPlugin>().unwrap().mode
+}
+
+#[test]
+fn test_leader_sequence_triggers_plugin_action() {
+    let mut locust = create_locust();
+    locust.ctx.keymap.leader = KeyBinding::new(KeyCodeDef::Char(' '));
+    locust
+        .ctx
+        .keymap
+        .bind_sequence("nav.activate", sequence("<leader> n"))
+        .unwrap();
+
+    let outcome = locust.on_event(&key(' '));
+    assert!(outcome.consumed);
+    assert_eq!(locust.ctx.key_sequences.pending_display(), "<space>");
+    assert_eq!(nav_mode(&locust), NavMode::Normal);
+
+    let outcome = locust.on_event(&key('n'));
+    assert!(outcome.consumed);
+    assert!(!locust.ctx.key_sequences.is_pending());
+    assert_eq!(nav_mode(&locust), NavMode::Hint);
+}
+
+#[test]
+fn test_unhandled_sequence_action_is_queued_for_app() {
+    let mut locust = create_locust();
+    locust
+        .ctx
+        .keymap
+        .bind_sequence("top", sequence("g g"))
+        .unwrap();
+
+    locust.on_event(&key('g'));
+    let outcome = locust.on_event(&key('g'));
+
+    assert!(outcome.consumed);
+    assert_eq!(
+        locust.ctx.key_sequences.take_action().as_deref(),
+        Some("top")
+    );
+    assert_eq!(locust.ctx.key_sequences.take_action(), None);
+}
+
+#[test]
+fn test_abandoned_sequence_replays_keys() {
+    let mut locust = create_locust();
+    locust
+        .ctx
+        .keymap
+        .bind_sequence("top", sequence("g g"))
+        .unwrap();
+
+    // 'g' is held, then 'x' breaks the sequence
+    locust.on_event(&key('g'));
+    let outcome = locust.on_event(&key('x'));
+
+    assert!(!outcome.consumed);
+    assert_eq!(
+        locust.ctx.key_sequences.take_key(),
+        Some(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE))
+    );
+
+    // The key that breaks the sequence still reaches plugins
+    locust.on_event(&key('g'));
+    locust.on_event(&key('f'));
+    assert_eq!(nav_mode(&locust), NavMode::Hint);
+    assert_eq!(
+        locust.ctx.key_sequences.take_key(),
+        Some(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE))
+    );
+}
+
+#[test]
+fn test_pending_sequence_times_out_on_tick() {
+    let mut locust = create_locust();
+    locust
+        .ctx
+        .keymap
+        .bind_sequence("find", sequence("f f"))
+        .unwrap();
+
+    let start = Instant::now();
+    locust.on_event(&key('f'));
+    assert_eq!(nav_mode(&locust), NavMode::Normal);
+
+    let deadline = locust.next_deadline().expect("sequence deadline");
+    assert!(deadline > start);
+    assert!(!locust.tick(start));
+
+    // The held 'f' falls back to its single-key binding
+    assert!(locust.tick(deadline));
+    assert_eq!(nav_mode(&locust), NavMode::Hint);
+    assert!(!locust.ctx.key_sequences.is_pending());
+}
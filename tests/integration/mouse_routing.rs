@@ -0,0 +1,115 @@
+//! Integration tests for mouse routing through the target registry.
+
+use crossterm::event::{MouseButton, MouseEventKind};
+use locust::core::actions::ScrollDirection;
+use locust::core::config::LocustConfig as FullConfig;
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+
+fn mouse_event(kind: MouseEventKind, column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+fn create_locust() -> Locust<TestBackend> {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.ctx.targets.register(
+        NavTarget::new(1, Rect::new(0, 0, 40, 10))
+            .with_label("Panel")
+            .with_action(TargetAction::Select),
+    );
+    locust.ctx.targets.register(
+        NavTarget::new(2, Rect::new(5, 2, 10, 1))
+            .with_label("Button")
+            .with_action(TargetAction::Activate),
+    );
+    locust
+}
+
+#[test]
+fn test_left_click_activates_topmost_target() {
+    let mut locust = create_locust();
+
+    let outcome = locust.on_event(&mouse_event(MouseEventKind::Down(MouseButton::Left), 6, 2));
+    assert!(outcome.consumed);
+
+    let actions = locust.drain_actions();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].target_id, 2);
+    assert_eq!(actions[0].action, TargetAction::Activate);
+}
+
+#[test]
+fn test_click_outside_targets_is_not_handled() {
+    let mut locust = create_locust();
+
+    let outcome = locust.on_event(&mouse_event(
+        MouseEventKind::Down(MouseButton::Left),
+        60,
+        20,
+    ));
+    assert!(!outcome.consumed);
+    assert!(locust.drain_actions().is_empty());
+}
+
+#[test]
+fn test_hover_highlights_target() {
+    let mut locust = create_locust();
+
+    let outcome = locust.on_event(&mouse_event(MouseEventKind::Moved, 6, 2));
+    assert!(!outcome.consumed);
+    assert!(outcome.request_redraw);
+    assert_eq!(locust.ctx.mouse.hovered_target, Some(2));
+    assert_eq!(
+        locust.ctx.targets.by_id(2).unwrap().state,
+        TargetState::Highlighted
+    );
+
+    // Highlight follows the pointer and persists across frames
+    locust.on_event(&mouse_event(MouseEventKind::Moved, 1, 1));
+    assert_eq!(
+        locust.ctx.targets.by_id(2).unwrap().state,
+        TargetState::Normal
+    );
+
+    locust.begin_frame();
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(1, Rect::new(0, 0, 40, 10)).with_label("Panel"));
+    assert_eq!(
+        locust.ctx.targets.by_id(1).unwrap().state,
+        TargetState::Highlighted
+    );
+}
+
+#[test]
+fn test_scroll_wheel_requests_scroll() {
+    let mut locust = create_locust();
+
+    locust.on_event(&mouse_event(MouseEventKind::ScrollDown, 20, 5));
+
+    let actions = locust.drain_actions();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].target_id, 1);
+    assert_eq!(actions[0].action, TargetAction::Scroll);
+    assert_eq!(actions[0].scroll, Some(ScrollDirection::Down));
+}
+
+#[test]
+fn test_mouse_support_disabled() {
+    let mut locust = create_locust();
+    let mut config = FullConfig::new();
+    config.global.mouse_support = false;
+    locust.update_config(config).unwrap();
+
+    let outcome = locust.on_event(&mouse_event(MouseEventKind::Down(MouseButton::Left), 6, 2));
+    assert!(!outcome.consumed);
+    assert!(locust.ctx.mouse.hovered_target.is_none());
+    assert!(locust.drain_actions().is_empty());
+}
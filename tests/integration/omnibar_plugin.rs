@@ -8,9 +8,11 @@
 //! - History navigation via events
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
-use locust::core::context::LocustContext;
+use locust::core::context::{Locust, LocustConfig, LocustContext};
 use locust::core::input::PluginEventResult;
+use locust::core::keybindings::{KeyBinding, KeyCodeDef};
 use locust::core::plugin::LocustPlugin;
+use locust::plugins::nav::NavPlugin;
 use locust::plugins::omnibar::{OmnibarConfig, OmnibarMode, OmnibarPlugin};
 use ratatui::backend::TestBackend;
 
@@ -53,11 +55,11 @@ fn test_plugin_initialization() {
 }
 
 #[test]
-fn test_activation_with_default_key() {
+fn test_activation_with_default_keymap_binding() {
     let mut plugin = OmnibarPlugin::new();
     let mut ctx = LocustContext::default();
 
-    let event = Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
+    let event = Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
     let result = plugin_on_event(&mut plugin, &event, &mut ctx);
 
     assert_eq!(result, PluginEventResult::ConsumedRequestRedraw);
@@ -70,6 +72,7 @@ fn test_activation_with_custom_key() {
     let config = OmnibarConfig::new().with_activation_key(':');
     let mut plugin = OmnibarPlugin::with_config(config);
     let mut ctx = LocustContext::default();
+    plugin_init(&mut plugin, &mut ctx);
 
     let event = Event::Key(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE));
     let result = plugin_on_event(&mut plugin, &event, &mut ctx);
@@ -78,6 +81,46 @@ fn test_activation_with_custom_key() {
     assert!(plugin.state().is_active());
 }
 
+#[test]
+fn test_activation_follows_keymap_remap() {
+    let mut plugin = OmnibarPlugin::new();
+    let mut ctx = LocustContext::default();
+    plugin_init(&mut plugin, &mut ctx);
+
+    ctx.bind_key(
+        "omnibar.activate",
+        KeyBinding::with_modifiers(KeyCodeDef::Char('k'), KeyModifiers::ALT),
+    )
+    .unwrap();
+
+    // The old binding no longer opens the omnibar
+    let event = Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+    assert_eq!(
+        plugin_on_event(&mut plugin, &event, &mut ctx),
+        PluginEventResult::NotHandled
+    );
+
+    let event = Event::Key(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::ALT));
+    let result = plugin_on_event(&mut plugin, &event, &mut ctx);
+
+    assert_eq!(result, PluginEventResult::ConsumedRequestRedraw);
+    assert!(plugin.state().is_active());
+}
+
+#[test]
+fn test_shifted_activation_key() {
+    let config = OmnibarConfig::new().with_activation_key('O');
+    let mut plugin = OmnibarPlugin::with_config(config);
+    let mut ctx = LocustContext::default();
+    plugin_init(&mut plugin, &mut ctx);
+
+    let event = Event::Key(KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT));
+    let result = plugin_on_event(&mut plugin, &event, &mut ctx);
+
+    assert_eq!(result, PluginEventResult::ConsumedRequestRedraw);
+    assert!(plugin.state().is_active());
+}
+
 #[test]
 fn test_activation_ignores_modified_keys() {
     let mut plugin = OmnibarPlugin::new();
@@ -97,7 +140,7 @@ fn test_deactivation_with_escape() {
     let mut ctx = LocustContext::default();
 
     // Activate
-    let event = Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
+    let event = Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
     plugin_on_event(&mut plugin, &event, &mut ctx);
     assert!(plugin.state().is_active());
 
@@ -118,7 +161,7 @@ fn test_character_input_when_active() {
     // Activate
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
 
@@ -156,7 +199,7 @@ fn test_character_input_when_inactive_not_handled() {
     let result = plugin_on_event(&mut plugin, &event, &mut ctx);
 
     // Should not handle when inactive (unless it's the activation key)
-    if plugin.config().activation_key != 'a' {
+    if plugin.config().activation_key != Some('a') {
         assert_eq!(result, PluginEventResult::NotHandled);
     }
 }
@@ -169,7 +212,7 @@ fn test_backspace_handling() {
     // Activate and type
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
     plugin_on_event(
@@ -209,7 +252,7 @@ fn test_enter_submits_command() {
     // Activate and type
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
     plugin_on_event(
@@ -249,7 +292,7 @@ fn test_enter_on_empty_input() {
     // Activate (no input)
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
 
@@ -273,7 +316,7 @@ fn test_cursor_movement_left_right() {
     // Activate and type
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
     plugin_on_event(
@@ -319,7 +362,7 @@ fn test_cursor_home_end() {
     // Activate and type
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
     plugin_on_event(
@@ -373,7 +416,7 @@ fn test_history_navigation_up_down() {
     // Add history
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
     plugin_on_event(
@@ -389,7 +432,7 @@ fn test_history_navigation_up_down() {
 
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
     plugin_on_event(
@@ -406,7 +449,7 @@ fn test_history_navigation_up_down() {
     // Navigate history
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
 
@@ -440,7 +483,7 @@ fn test_plugin_cleanup() {
     // Activate
     plugin_on_event(
         &mut plugin,
-        &Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
+        &Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
         &mut ctx,
     );
     assert!(plugin.state().is_active());
@@ -453,9 +496,13 @@ fn test_plugin_cleanup() {
 #[test]
 fn test_plugin_priority() {
     let plugin = OmnibarPlugin::new();
-    // Omnibar should have higher priority than nav plugin
-    assert_eq!(plugin_priority(&plugin), 40);
-    assert!(plugin_priority(&plugin) < 50);
+    assert_eq!(plugin_priority(&plugin), locust::plugins::BUILTIN_PRIORITY);
+
+    // Omnibar should see events before nav, whatever the registration order
+    let mut locust = Locust::<Backend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    locust.register_plugin(plugin);
+    assert_eq!(locust.plugin_ids(), vec!["locust.omnibar", "locust.nav"]);
 }
 
 #[test]
@@ -0,0 +1,155 @@
+//! Integration tests for compositing plugin overlays through the layer stack.
+
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::{Frame, Terminal};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Fills each of its layers with the first letter of the layer name.
+struct LayeredPlugin {
+    id: &'static str,
+    priority: i32,
+    /// Layer names and the number of masked areas, in draw order.
+    draws: Rc<RefCell<Vec<(String, usize)>>>,
+}
+
+impl LayeredPlugin {
+    fn new(id: &'static str, priority: i32) -> Self {
+        Self {
+            id,
+            priority,
+            draws: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+}
+
+impl<B: Backend + 'static> LocustPlugin<B> for LayeredPlugin {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn on_event(&mut self, _event: &Event, _ctx: &mut LocustContext) -> PluginEventResult {
+        PluginEventResult::NotHandled
+    }
+
+    fn render_overlay(&self, _frame: &mut Frame, _ctx: &LocustContext) {}
+
+    fn render_layer(
+        &self,
+        frame: &mut Frame,
+        layer: &OverlayLayer,
+        mask: &OcclusionMask,
+        _ctx: &LocustContext,
+    ) {
+        self.draws
+            .borrow_mut()
+            .push((layer.name.clone(), mask.rects().len()));
+
+        let area = layer.occludes.unwrap_or(Rect::new(0, 0, 20, 1));
+        let fill = layer.name[..1].repeat(area.width as usize);
+        for y in area.top()..area.bottom() {
+            frame
+                .buffer_mut()
+                .set_string(area.x, y, &fill, Style::default());
+        }
+    }
+}
+
+fn draw(locust: &Locust<TestBackend>) -> Terminal<TestBackend> {
+    let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+    terminal.draw(|frame| locust.render_overlay(frame)).unwrap();
+    terminal
+}
+
+fn cell(terminal: &Terminal<TestBackend>, x: u16, y: u16) -> String {
+    terminal.backend().buffer()[(x, y)].symbol().to_string()
+}
+
+#[test]
+fn test_layers_draw_by_z_index_not_priority() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    let plugin = LayeredPlugin::new("app.layers", 10);
+    let draws = plugin.draws.clone();
+    locust.register_plugin(plugin);
+
+    locust.ctx.overlay.add_layer(
+        OverlayLayer::named("app.layers", "modal", 200).with_occlusion(Rect::new(5, 0, 10, 3)),
+    );
+    locust
+        .ctx
+        .overlay
+        .add_layer(OverlayLayer::named("app.layers", "tooltip", 120));
+
+    let terminal = draw(&locust);
+
+    assert_eq!(
+        *draws.borrow(),
+        vec![("tooltip".to_string(), 1), ("modal".to_string(), 0)]
+    );
+    // The modal covers the tooltip where they overlap
+    assert_eq!(cell(&terminal, 0, 0), "t");
+    assert_eq!(cell(&terminal, 5, 0), "m");
+}
+
+#[test]
+fn test_invisible_layers_are_skipped() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    let plugin = LayeredPlugin::new("app.layers", 10);
+    let draws = plugin.draws.clone();
+    locust.register_plugin(plugin);
+
+    locust
+        .ctx
+        .overlay
+        .add_layer(OverlayLayer::named("app.layers", "tooltip", 120));
+    locust.ctx.overlay.add_layer(
+        OverlayLayer::named("app.layers", "modal", 200).with_occlusion(Rect::new(5, 0, 10, 3)),
+    );
+    locust
+        .ctx
+        .overlay
+        .set_named_layer_visibility("app.layers", "modal", false);
+
+    let terminal = draw(&locust);
+
+    // Hidden layers neither draw nor occlude
+    assert_eq!(*draws.borrow(), vec![("tooltip".to_string(), 0)]);
+    assert_eq!(cell(&terminal, 5, 0), "t");
+}
+
+#[test]
+fn test_hints_under_a_modal_are_not_drawn() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    locust.register_plugin(LayeredPlugin::new("app.layers", 10));
+
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(1, Rect::new(0, 5, 10, 1)));
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(2, Rect::new(20, 5, 10, 1)));
+    locust.ctx.overlay.add_layer(
+        OverlayLayer::named("app.layers", "modal", 200).with_occlusion(Rect::new(22, 4, 10, 3)),
+    );
+
+    locust.on_event(&Event::Key(KeyEvent::new(
+        KeyCode::Char('f'),
+        KeyModifiers::NONE,
+    )));
+    let terminal = draw(&locust);
+
+    // Target 2's hint would be cut in half by the modal, so it is left out
+    assert_ne!(cell(&terminal, 1, 5), " ");
+    let row: String = (20..24).map(|x| cell(&terminal, x, 5)).collect();
+    assert_eq!(row, "  mm");
+}
@@ -0,0 +1,92 @@
+//! Integration tests for hints on text patterns in the rendered frame.
+
+use locust::plugins::patterns::PATTERN_ID_BASE;
+use locust::prelude::*;
+use locust::testing::LocustHarness;
+use ratatui::layout::Rect;
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+/// A build log mentioning a URL and a commit, below the hint banner row.
+fn build_log() -> LocustHarness {
+    LocustHarness::new(40, 5, |frame, ctx| {
+        let text = "deploy ok: https://ci.example.com/42\nat commit 9fceb02 (#7)";
+        frame.render_widget(Paragraph::new(text), Rect::new(0, 2, 40, 2));
+        ctx.targets
+            .register(NavTarget::new(1, Rect::new(0, 4, 6, 1)).with_label("Retry"));
+    })
+    .with_plugin(NavPlugin::new())
+    .with_plugin(PatternPlugin::new())
+}
+
+#[test]
+fn test_pattern_hints_snapshot() {
+    let mut harness = build_log();
+    assert!(harness.press("F").consumed);
+    harness.assert_snapshot("pattern_hints");
+
+    let plugin = harness.locust.get_plugin::<PatternPlugin>().unwrap();
+    let labels: Vec<&str> = plugin
+        .targets()
+        .all()
+        .iter()
+        .filter_map(|t| t.label.as_deref())
+        .collect();
+    assert_eq!(labels, ["https://ci.example.com/42", "9fceb02", "#7"]);
+}
+
+#[test]
+fn test_picking_a_match_emits_its_action() {
+    let mut harness = build_log();
+    harness.press("F");
+    // The URL comes first in reading order and gets the first hint
+    harness.press("a");
+
+    let actions = harness.locust.drain_actions();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].target_id, PATTERN_ID_BASE + 1);
+    assert_eq!(actions[0].action, TargetAction::Custom("open".into()));
+    assert_eq!(
+        actions[0].label.as_deref(),
+        Some("https://ci.example.com/42")
+    );
+    assert_eq!(harness.locust.ctx.modes.current(), &Mode::Normal);
+
+    // Registered targets keep working through the navigation plugin
+    harness.press("f");
+    harness.press("a");
+    assert_eq!(harness.locust.drain_actions()[0].target_id, 1);
+}
+
+/// Background overlay that blanks the build log rows.
+struct Backdrop;
+
+impl<B: Backend + 'static> LocustPlugin<B> for Backdrop {
+    fn id(&self) -> &'static str {
+        "app.backdrop"
+    }
+
+    fn init(&mut self, ctx: &mut LocustContext) {
+        ctx.overlay.add_layer(OverlayLayer::new("app.backdrop", 0));
+    }
+
+    fn on_event(&mut self, _event: &Event, _ctx: &mut LocustContext) -> PluginEventResult {
+        PluginEventResult::NotHandled
+    }
+
+    fn render_overlay(&self, frame: &mut Frame, _ctx: &LocustContext) {
+        frame.render_widget(Clear, Rect::new(0, 2, 40, 2));
+    }
+}
+
+#[test]
+fn test_hints_draw_above_background_overlays() {
+    let mut harness = build_log().with_plugin(Backdrop);
+    harness.press("F");
+
+    // The text was read before the backdrop covered it, and the hints are
+    // drawn over the backdrop
+    let buffer = harness.render();
+    assert_eq!(buffer[(12, 2)].symbol(), "a");
+    assert_eq!(buffer[(14, 2)].symbol(), " ");
+}
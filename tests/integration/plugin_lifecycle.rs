@@ -1,7 +1,11 @@
 //! Integration tests for plugin lifecycle.
 
+use locust::core::keybindings::{KeyBinding, KeyCodeDef};
+use locust::core::PluginOrderError;
+use locust::plugins::tooltip::TooltipContent;
 use locust::prelude::*;
 use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
 use ratatui::Frame;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -12,6 +16,8 @@ struct MockPlugin {
     id: &'static str,
     priority: i32,
     lifecycle: Rc<RefCell<Vec<String>>>,
+    bindings: Vec<(&'static str, char)>,
+    tooltips: Vec<(u64, &'static str)>,
 }
 
 impl MockPlugin {
@@ -20,15 +26,29 @@ impl MockPlugin {
             id,
             priority,
             lifecycle: Rc::new(RefCell::new(Vec::new())),
+            bindings: Vec::new(),
+            tooltips: Vec::new(),
         }
     }
 
+    /// Binds `action` to `key` in `init`.
+    fn with_binding(mut self, action: &'static str, key: char) -> Self {
+        self.bindings.push((action, key));
+        self
+    }
+
+    /// Registers a tooltip for target `id` in `init`.
+    fn with_tooltip(mut self, id: u64, text: &'static str) -> Self {
+        self.tooltips.push((id, text));
+        self
+    }
+
     fn lifecycle(&self) -> Vec<String> {
         self.lifecycle.borrow().clone()
     }
 }
 
-impl<B: Backend> LocustPlugin<B> for MockPlugin {
+impl<B: Backend + 'static> LocustPlugin<B> for MockPlugin {
     fn id(&self) -> &'static str {
         self.id
     }
@@ -37,10 +57,17 @@ impl<B: Backend> LocustPlugin<B> for MockPlugin {
         self.priority
     }
 
-    fn init(&mut self, _ctx: &mut LocustContext) {
+    fn init(&mut self, ctx: &mut LocustContext) {
         self.lifecycle
             .borrow_mut()
             .push(format!("{}:init", self.id));
+        for &(action, key) in &self.bindings {
+            ctx.bind_key(action, KeyBinding::new(KeyCodeDef::Char(key)))
+                .unwrap();
+        }
+        for &(id, text) in &self.tooltips {
+            ctx.tooltips.register(id, TooltipContent::new(text));
+        }
     }
 
     fn on_event(&mut self, event: &Event, _ctx: &mut LocustContext) -> PluginEventResult {
@@ -230,3 +257,260 @@ fn test_has_plugin() {
     assert!(locust.has_plugin("test_plugin"));
     assert!(!locust.has_plugin("other_plugin"));
 }
+
+fn render(locust: &Locust<TestBackend>) {
+    let mut terminal = ratatui::Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|frame| locust.render_overlay(frame)).unwrap();
+}
+
+fn key(c: char) -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+}
+
+#[test]
+fn test_unregister_plugin() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.ctx.tooltips.register(1, TooltipContent::new("Open"));
+    let plugin = MockPlugin::new("panel", 100)
+        .with_binding("panel.close", 'c')
+        .with_binding("quit", 'x')
+        .with_tooltip(1, "Open the panel")
+        .with_tooltip(2, "Close the panel");
+    let lifecycle = plugin.lifecycle.clone();
+    locust.register_plugin(plugin);
+    locust.ctx.tooltips.register(3, TooltipContent::new("Help"));
+    locust
+        .ctx
+        .overlay
+        .add_layer(OverlayLayer::named("panel", "modal", 200));
+    locust
+        .ctx
+        .keymap
+        .bind("panel.open", KeyBinding::new(KeyCodeDef::Char('o')))
+        .unwrap();
+    locust.ctx.modes.push(Mode::Custom("panel".into()), "panel");
+
+    assert!(locust.unregister_plugin("panel"));
+
+    assert!(!locust.has_plugin("panel"));
+    assert_eq!(lifecycle.borrow().last().unwrap(), "panel:cleanup");
+    assert!(!locust.ctx.overlay.has_layer("panel"));
+    // The plugin's own bindings are reverted, the app's are kept
+    assert!(locust.ctx.keymap.get_binding("panel.close").is_none());
+    assert_eq!(
+        locust.ctx.keymap.get_binding("quit"),
+        Some(&KeyBinding::new(KeyCodeDef::Char('q')))
+    );
+    assert!(locust.ctx.keymap.get_binding("panel.open").is_some());
+    // So are its tooltips, putting back the one it replaced
+    assert_eq!(
+        locust.ctx.tooltips.get(1),
+        Some(&TooltipContent::new("Open"))
+    );
+    assert!(!locust.ctx.tooltips.contains(2));
+    assert!(locust.ctx.tooltips.contains(3));
+    assert_eq!(locust.ctx.modes.current(), &Mode::Normal);
+    assert!(!locust.unregister_plugin("panel"));
+
+    // Cleanup is not run a second time on drop
+    drop(locust);
+    assert_eq!(lifecycle.borrow().len(), 2);
+}
+
+#[test]
+fn test_disabled_plugin_keeps_state() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::new());
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+    let mock = MockPlugin::new("app.mock", 100);
+    let lifecycle = mock.lifecycle.clone();
+    locust.register_plugin(mock);
+
+    assert!(locust.set_plugin_enabled("app.mock", false));
+    assert!(!locust.is_plugin_enabled("app.mock"));
+    locust.on_event(&key('a'));
+    render(&locust);
+    assert_eq!(*lifecycle.borrow(), vec!["app.mock:init"]);
+
+    // Disabling the nav plugin leaves hint mode; 'f' then goes to the app
+    locust.on_event(&key('f'));
+    assert_eq!(locust.ctx.modes.current(), &Mode::Hint);
+    locust.set_plugin_enabled("locust.nav", false);
+    assert_eq!(locust.ctx.modes.current(), &Mode::Normal);
+    assert!(!locust.on_event(&key('f')).consumed);
+
+    locust.set_plugin_enabled("locust.nav", true);
+    assert!(locust.on_event(&key('f')).consumed);
+    assert!(!locust.set_plugin_enabled("missing", true));
+}
+
+#[test]
+fn test_replace_plugin_keeps_priority_slot() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    let first = MockPlugin::new("first", 50);
+    let old = MockPlugin::new("swapped", 100);
+    let last = MockPlugin::new("last", 200);
+    let first_log = first.lifecycle.clone();
+    let old_log = old.lifecycle.clone();
+    locust.register_plugin(first);
+    locust.register_plugin(old);
+    locust.register_plugin(last);
+    locust.set_plugin_enabled("swapped", false);
+
+    // Same ID, but a priority that would sort it first
+    let new = MockPlugin::new("swapped", 10);
+    let new_log = new.lifecycle.clone();
+    assert!(locust.replace_plugin(new));
+
+    assert_eq!(old_log.borrow().last().unwrap(), "swapped:cleanup");
+    assert_eq!(*new_log.borrow(), vec!["swapped:init"]);
+    assert_eq!(locust.plugin_count(), 3);
+    assert!(!locust.is_plugin_enabled("swapped"));
+
+    locust.set_plugin_enabled("swapped", true);
+    first_log.borrow_mut().clear();
+    // 'x' is consumed by the first plugin with priority < 100; the new
+    // instance still runs after "first"
+    locust.on_event(&key('x'));
+    assert_eq!(*first_log.borrow(), vec!["first:on_event"]);
+    assert_eq!(*new_log.borrow(), vec!["swapped:init"]);
+}
+
+#[test]
+fn test_replaced_builtin_plugins_keep_keys_and_app_data() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(NavPlugin::with_config(NavConfig::new().with_hint_key('g')));
+    locust.register_plugin(TooltipPlugin::new());
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(1, Rect::new(0, 0, 10, 1)));
+    locust
+        .ctx
+        .tooltips
+        .register(1, TooltipContent::new("Saves the file"));
+
+    // The new instance has no key of its own; the keymap's 'f' is back
+    assert!(locust.replace_plugin(NavPlugin::new()));
+    assert!(!locust.on_event(&key('g')).consumed);
+    assert!(locust.on_event(&key('f')).consumed);
+    assert_eq!(locust.ctx.modes.current(), &Mode::Hint);
+    locust.pop_mode();
+
+    // Re-registering after an unregister works the same way
+    assert!(locust.unregister_plugin("locust.nav"));
+    locust.register_plugin(NavPlugin::new());
+    assert!(locust.on_event(&key('f')).consumed);
+    assert_eq!(locust.ctx.modes.current(), &Mode::Hint);
+
+    // Tooltips the application registered survive a tooltip plugin swap
+    assert!(locust.replace_plugin(TooltipPlugin::new()));
+    assert!(locust.ctx.tooltips.contains(1));
+}
+
+/// Plugin that only declares ordering constraints.
+struct OrderedPlugin {
+    id: &'static str,
+    depends_on: &'static [&'static str],
+    before: &'static [&'static str],
+    init_log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl<B: Backend + 'static> LocustPlugin<B> for OrderedPlugin {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn depends_on(&self) -> &'static [&'static str] {
+        self.depends_on
+    }
+
+    fn before(&self) -> &'static [&'static str] {
+        self.before
+    }
+
+    fn init(&mut self, _ctx: &mut LocustContext) {
+        self.init_log.borrow_mut().push(self.id);
+    }
+
+    fn on_event(&mut self, _event: &Event, _ctx: &mut LocustContext) -> PluginEventResult {
+        PluginEventResult::NotHandled
+    }
+
+    fn render_overlay(&self, _frame: &mut Frame, _ctx: &LocustContext) {}
+}
+
+#[test]
+fn test_plugin_ordering_declarations() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    let init_log = Rc::new(RefCell::new(Vec::new()));
+    let plugin = |id, depends_on, before| OrderedPlugin {
+        id,
+        depends_on,
+        before,
+        init_log: init_log.clone(),
+    };
+
+    locust.register_plugin(NavPlugin::new());
+    locust.register_plugin(OmnibarPlugin::new());
+    locust.register_plugin(plugin("app.keys", &[], &["locust.nav"]));
+    locust.register_plugin(plugin("app.status", &["app.keys"], &[]));
+
+    assert_eq!(
+        locust.plugin_ids(),
+        vec!["locust.omnibar", "app.keys", "locust.nav", "app.status"]
+    );
+    assert_eq!(*init_log.borrow(), vec!["app.keys", "app.status"]);
+
+    // A missing dependency is reported and nothing is registered
+    let err = locust
+        .try_register_plugin(plugin("app.late", &["app.missing"], &[]))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        PluginOrderError::MissingDependency {
+            plugin: "app.late".into(),
+            dependency: "app.missing".into()
+        }
+    );
+
+    // app.loop would have to run both after app.status and before app.keys
+    let err = locust
+        .try_register_plugin(plugin("app.loop", &["app.status"], &["app.keys"]))
+        .unwrap_err();
+    assert!(matches!(err, PluginOrderError::Cycle(_)));
+    assert_eq!(locust.plugin_count(), 4);
+    assert_eq!(init_log.borrow().len(), 2);
+}
+
+#[test]
+fn test_unregister_refuses_required_plugin() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    let init_log = Rc::new(RefCell::new(Vec::new()));
+    let plugin = |id, depends_on| OrderedPlugin {
+        id,
+        depends_on,
+        before: &[],
+        init_log: init_log.clone(),
+    };
+    locust.register_plugin(plugin("app.keys", &[]));
+    locust.register_plugin(plugin("app.status", &["app.keys"]));
+
+    assert_eq!(
+        locust.try_unregister_plugin("app.keys"),
+        Err(PluginOrderError::Required {
+            plugin: "app.keys".into(),
+            dependent: "app.status".into()
+        })
+    );
+    assert!(!locust.unregister_plugin("app.keys"));
+    assert!(locust.has_plugin("app.keys"));
+
+    // Once the dependent is gone, the dependency can go too
+    assert_eq!(locust.try_unregister_plugin("app.status"), Ok(true));
+    assert_eq!(locust.try_unregister_plugin("app.keys"), Ok(true));
+    assert_eq!(locust.try_unregister_plugin("app.keys"), Ok(false));
+}
@@ -0,0 +1,76 @@
+//! Integration tests for runtime plugin timings and the metrics HUD.
+
+use locust::core::metrics::PluginPhase;
+use locust::prelude::*;
+use locust::testing::LocustHarness;
+use ratatui::layout::Rect;
+
+fn harness() -> LocustHarness {
+    LocustHarness::new(60, 8, |_frame, ctx| {
+        for id in 1..=3 {
+            ctx.targets
+                .register(NavTarget::new(id, Rect::new(0, 2 + id as u16, 10, 1)));
+        }
+    })
+}
+
+#[test]
+fn test_hooks_are_timed_when_enabled() {
+    let mut harness = harness();
+    harness.locust.ctx.metrics.enabled = true;
+    harness.locust.register_plugin(NavPlugin::new());
+
+    harness.press("f");
+    harness.render();
+    harness.render();
+
+    let metrics = &harness.locust.ctx.metrics;
+    assert!(metrics
+        .percentiles("locust.nav", PluginPhase::Init)
+        .is_some());
+    assert_eq!(
+        metrics
+            .percentiles("locust.nav", PluginPhase::Event)
+            .unwrap()
+            .samples,
+        1
+    );
+    // Render timings of the last frame arrive with the next one
+    assert_eq!(
+        metrics
+            .percentiles("locust.nav", PluginPhase::Render)
+            .unwrap()
+            .samples,
+        2
+    );
+    assert_eq!(metrics.frame_targets(), Some(3));
+}
+
+#[test]
+fn test_nothing_is_recorded_when_disabled() {
+    let mut harness = harness().with_plugin(NavPlugin::new());
+    harness.press("f");
+    harness.render();
+
+    assert!(harness.locust.ctx.metrics.plugin_ids().is_empty());
+    assert_eq!(harness.locust.ctx.metrics.frame_targets(), None);
+}
+
+#[test]
+fn test_hud_toggles_and_shows_plugins() {
+    let mut harness = harness()
+        .with_plugin(NavPlugin::new())
+        .with_plugin(MetricsHudPlugin::new());
+    assert!(!harness.snapshot().contains("Metrics"));
+
+    assert!(harness.press("F12").consumed);
+    harness.press("x");
+    harness.render();
+    let frame = harness.snapshot();
+    assert!(frame.contains(" Metrics "));
+    assert!(frame.contains("locust.nav"));
+    assert!(frame.contains("targets 3"));
+
+    harness.press("F12");
+    assert!(!harness.snapshot().contains("Metrics"));
+}
@@ -0,0 +1,73 @@
+//! Integration tests for plugin timers driven by events and ticks.
+
+use crossterm::event::MouseEventKind;
+use locust::plugins::highlight::{HighlightPlugin, Tour, TourStep};
+use locust::plugins::tooltip::{TooltipConfig, TooltipContent, TooltipMode, TooltipPlugin};
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use std::time::{Duration, Instant};
+
+fn key(c: char) -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+}
+
+fn moved(column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Moved,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[test]
+fn test_tooltip_timers_advance_on_events_without_tick() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(TooltipPlugin::with_config(
+        TooltipConfig::new()
+            .with_hover_delay_ms(300)
+            .with_auto_hide_timeout_ms(1000),
+    ));
+    locust
+        .ctx
+        .targets
+        .register(NavTarget::new(1, Rect::new(10, 10, 20, 3)));
+    locust.ctx.tooltips.register(1, TooltipContent::new("Save"));
+
+    let mode = |locust: &Locust<TestBackend>| locust.get_plugin::<TooltipPlugin>().unwrap().mode();
+    let start = Instant::now();
+    let at = |ms| start + Duration::from_millis(ms);
+
+    locust.on_event_at(&moved(12, 11), at(0));
+    assert_eq!(mode(&locust), TooltipMode::Pending);
+
+    // The host never calls tick; later events carry the timers forward
+    assert!(locust.on_event_at(&moved(13, 11), at(300)).request_redraw);
+    assert_eq!(mode(&locust), TooltipMode::Visible);
+
+    locust.on_event_at(&key('z'), at(1300));
+    assert_eq!(mode(&locust), TooltipMode::Hidden);
+}
+
+#[test]
+fn test_tour_auto_advances_on_events_without_tick() {
+    let mut plugin = HighlightPlugin::new();
+    plugin.register_tour(
+        Tour::new("intro").add_step(TourStep::new("Welcome", "First").with_auto_advance(500)),
+    );
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(plugin);
+
+    // The step timer starts at the instant of the key that started the tour
+    let start = Instant::now() + Duration::from_secs(60);
+    assert!(locust.on_event_at(&key('?'), start).consumed);
+
+    locust.on_event_at(&key('z'), start + Duration::from_millis(499));
+    let plugin = locust.get_plugin::<HighlightPlugin>().unwrap();
+    assert!(!plugin.is_tour_completed("intro"));
+
+    locust.on_event_at(&key('z'), start + Duration::from_millis(500));
+    let plugin = locust.get_plugin::<HighlightPlugin>().unwrap();
+    assert!(plugin.is_tour_completed("intro"));
+}
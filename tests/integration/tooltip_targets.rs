@@ -0,0 +1,71 @@
+//! Integration tests for tooltips of applications that regenerate target IDs.
+
+use crossterm::event::MouseEventKind;
+use locust::plugins::tooltip::{TooltipConfig, TooltipContent, TooltipMode, TooltipPlugin};
+use locust::prelude::*;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::Terminal;
+use std::time::Instant;
+
+fn moved(column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Moved,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+fn draw_frame(locust: &mut Locust<TestBackend>, save_id: u64, quit_id: u64) {
+    locust.begin_frame();
+    let targets = &mut locust.ctx.targets;
+    targets.register(NavTarget::new(save_id, Rect::new(2, 1, 10, 1)).with_label("Save"));
+    targets.register(NavTarget::new(quit_id, Rect::new(2, 12, 10, 1)).with_label("Quit"));
+}
+
+/// Row of the first line of the rendered overlay that contains `text`.
+fn row_of(locust: &Locust<TestBackend>, text: &str) -> Option<u16> {
+    let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+    terminal.draw(|frame| locust.render_overlay(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..20).find(|&y| {
+        let row: String = (0..40).map(|x| buffer[(x, y)].symbol()).collect();
+        row.contains(text)
+    })
+}
+
+#[test]
+fn test_tooltip_follows_its_target_to_a_new_id() {
+    let mut locust = Locust::<TestBackend>::new(LocustConfig::default());
+    locust.register_plugin(TooltipPlugin::with_config(
+        TooltipConfig::new()
+            .with_hover_delay_ms(0)
+            .with_auto_hide_timeout_ms(0),
+    ));
+    draw_frame(&mut locust, 1, 2);
+    // Registered once, like an application with stable IDs would
+    locust
+        .ctx
+        .tooltips
+        .register(1, TooltipContent::new("Saves the file"));
+
+    locust.on_event(&moved(4, 1));
+    let mode = |locust: &Locust<TestBackend>| locust.get_plugin::<TooltipPlugin>().unwrap().mode();
+    assert_eq!(mode(&locust), TooltipMode::Visible);
+    let shown_at = row_of(&locust, "Saves the file").unwrap();
+
+    // "Quit" now has the ID "Save" had
+    draw_frame(&mut locust, 2, 1);
+    assert_eq!(row_of(&locust, "Saves the file"), Some(shown_at));
+
+    locust.tick(Instant::now());
+    assert!(locust.ctx.tooltips.contains(2));
+    assert!(!locust.ctx.tooltips.contains(1));
+    assert_eq!(mode(&locust), TooltipMode::Visible);
+    assert_eq!(row_of(&locust, "Saves the file"), Some(shown_at));
+
+    // Hovering "Quit" does not bring up the tooltip of "Save"
+    locust.on_event(&moved(4, 12));
+    assert_eq!(mode(&locust), TooltipMode::Hidden);
+}
@@ -6,8 +6,20 @@ mod unit {
 }
 
 mod integration {
+    mod automation_driver;
+    mod event_replay;
+    mod focus_navigation;
+    mod harness_snapshots;
+    mod input_modes;
+    mod key_sequences;
+    mod mouse_routing;
     mod omnibar_plugin;
+    mod overlay_compositor;
+    mod pattern_hints;
     mod plugin_lifecycle;
+    mod plugin_metrics;
+    mod plugin_timers;
+    mod tooltip_targets;
 }
 
 mod examples {
@@ -0,0 +1,68 @@
+//! Property-based tests for hint generation
+//!
+//! Prefix-free hints must never let one hint shadow another.
+
+use locust::core::targets::NavTarget;
+use locust::plugins::nav::hints::{HintGenerator, HintMatcher};
+use proptest::prelude::*;
+use ratatui::layout::Rect;
+
+/// Charsets of 2 to 10 distinct characters.
+fn charset() -> impl Strategy<Value = String> {
+    proptest::sample::subsequence("asdfghjkl;qwertyuiop".chars().collect::<Vec<_>>(), 2..=10)
+        .prop_shuffle()
+        .prop_map(|chars| chars.into_iter().collect())
+}
+
+proptest! {
+    #[test]
+    fn test_hints_are_prefix_free(charset in charset(), count in 0usize..1500) {
+        let hints = HintGenerator::new(charset).generate_hint_strings(count);
+        prop_assert_eq!(hints.len(), count);
+
+        let mut sorted = hints.clone();
+        sorted.sort();
+        for pair in sorted.windows(2) {
+            // Any prefix sorts directly before some string it prefixes
+            prop_assert!(!pair[1].starts_with(&pair[0]), "{:?} is a prefix of {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_hint_lengths_are_balanced(charset in charset(), count in 1usize..1500) {
+        let k = charset.chars().count();
+        let hints = HintGenerator::new(charset).generate_hint_strings(count);
+        let lengths: Vec<usize> = hints.iter().map(|h| h.chars().count()).collect();
+
+        // The longest hint is as short as any unique hints allow
+        let mut longest = 1;
+        while k.pow(longest as u32) < count {
+            longest += 1;
+        }
+        prop_assert_eq!(lengths.iter().max().copied(), Some(longest));
+        prop_assert!(lengths.iter().all(|&len| len + 1 >= longest));
+        prop_assert!(lengths.windows(2).all(|w| w[0] <= w[1]), "shortest hints come first");
+    }
+
+    #[test]
+    fn test_every_hint_selects_its_target(charset in charset(), count in 1usize..200) {
+        let targets: Vec<NavTarget> = (0..count)
+            .map(|i| NavTarget::new(i as u64, Rect::new(0, i as u16, 10, 1)))
+            .collect();
+        let hints = HintGenerator::new(charset).generate(&targets);
+
+        let mut matcher = HintMatcher::new();
+        for hint in &hints {
+            matcher.set_hints(hints.clone());
+            let chars: Vec<char> = hint.text.chars().collect();
+            for (idx, &c) in chars.iter().enumerate() {
+                let picked = matcher.push_char(c);
+                if idx + 1 < chars.len() {
+                    prop_assert_eq!(picked, None, "{:?} picked early", hint.text);
+                } else {
+                    prop_assert_eq!(picked, Some(hint.target_id));
+                }
+            }
+        }
+    }
+}
@@ -2,3 +2,5 @@
 
 #[cfg(test)]
 mod fuzzy_properties;
+#[cfg(test)]
+mod hint_properties;
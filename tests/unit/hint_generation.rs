@@ -1,7 +1,7 @@
 //! Unit tests for hint generation algorithm.
 
 use locust::core::targets::{NavTarget, TargetPriority};
-use locust::plugins::nav::hints::{Hint, HintGenerator, HintMatcher};
+use locust::plugins::nav::hints::{Hint, HintGenerator, HintMatcher, HintStrategy};
 use ratatui::layout::Rect;
 
 #[test]
@@ -26,7 +26,7 @@ fn test_hint_generation_sequential() {
 
 #[test]
 fn test_hint_generation_two_char_hints() {
-    let mut generator = HintGenerator::new("ab".to_string());
+    let mut generator = HintGenerator::new("ab".to_string()).with_strategy(HintStrategy::Bijective);
 
     let targets = vec![
         NavTarget::new(1, Rect::new(0, 0, 10, 1)),
@@ -260,7 +260,8 @@ fn test_hint_for_target() {
 
 #[test]
 fn test_large_hint_set() {
-    let mut generator = HintGenerator::new("asdfghjkl".to_string());
+    let mut generator =
+        HintGenerator::new("asdfghjkl".to_string()).with_strategy(HintStrategy::Bijective);
 
     // Generate 100 targets
     let targets: Vec<_> = (0..100)